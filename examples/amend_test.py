@@ -0,0 +1,52 @@
+import math
+import random
+
+from ta import indicator, rolling
+
+
+def same(a, b):
+    if isinstance(a, tuple):
+        return all(same(x, y) for x, y in zip(a, b))
+    return a == b or (math.isnan(a) and math.isnan(b))
+
+
+random.seed(0)
+closes = [10 + random.random() for _ in range(50)]
+
+for make in [lambda: rolling.Meaner(5), lambda: rolling.Container(5), lambda: indicator.MACD(3, 6, 2)]:
+    live = make()
+    for close in closes:
+        # intrabar ticks replace each other, then the bar closes at `close`
+        for tick in [close - 0.3, close + 0.2, close + 0.1]:
+            live.update(tick, is_final=False)
+        live.update(close, is_final=True)
+
+    expected = make()
+    # final updates only keep the state before them on request
+    expected.set_keep_last(True)
+    for close in closes:
+        expected.update(close)
+    closed = expected.value()
+    assert same(live.value(), closed)
+
+    # replace_last never advances the window
+    assert same(live.replace_last(closes[-1] + 1.0), expected.replace_last(closes[-1] + 1.0))
+    assert same(live.replace_last(closes[-1]), closed)
+
+# without keep_last a final update leaves nothing to replace
+ema = indicator.EMA(3)
+ema.update(1.0)
+try:
+    ema.replace_last(2.0)
+    raise AssertionError("replace_last without a kept state")
+except ValueError:
+    pass
+ema.update(2.0, is_final=False)
+assert ema.replace_last(3.0) == ema.peek(3.0)
+
+# multi-input classes take the flag after the inputs
+atr = indicator.ATR(3)
+atr.update(10.5, 9.5, 10.0)
+atr.update(10.8, 9.9, 10.2, is_final=False)
+print(atr.replace_last(10.9, 9.9, 10.3))
+print("ok")
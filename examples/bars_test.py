@@ -0,0 +1,114 @@
+import math
+import pickle
+import random
+
+from ta import bars, indicator
+
+random.seed(15)
+trades, t, price = [], 0, 100.0
+for _ in range(5000):
+    t += random.choice([0, 1, 10, 200, 3000])
+    price = round(price + random.choice([-0.01, 0, 0, 0.01]), 2)
+    trades.append((t, price, float(random.randint(1, 50))))
+# a bad print is ignored
+trades.insert(100, (trades[99][0], math.nan, 10.0))
+
+
+def aggregate(groups):
+    out = []
+    for group in groups:
+        prices = [p for _, p, _ in group]
+        out.append(
+            dict(
+                open=prices[0],
+                high=max(prices),
+                low=min(prices),
+                close=prices[-1],
+                volume=sum(s for _, _, s in group),
+                trades=len(group),
+            )
+        )
+    return out
+
+
+def check(builder, groups):
+    completed = [bar for bar in (builder.update(*trade) for trade in trades) if bar is not None]
+    current = builder.current()
+    expected = aggregate(groups)
+    assert len(completed) + (current is not None) == len(expected)
+    for bar, e in zip(completed + [current], expected):
+        for k, v in e.items():
+            assert abs(getattr(bar, k) - v) < 1e-9, (k, getattr(bar, k), v)
+    return completed
+
+
+valid = [trade for trade in trades if not math.isnan(trade[1])]
+
+# time bars over minutes, aligned to the interval
+minute = 60_000
+groups = {}
+for trade in valid:
+    groups.setdefault(trade[0] // minute, []).append(trade)
+completed = check(bars.TimeBarer(minute), list(groups.values()))
+assert all(bar.start % minute == 0 and bar.end == bar.start + minute for bar in completed)
+
+# tick bars
+check(bars.TickBarer(50), [valid[i : i + 50] for i in range(0, len(valid), 50)])
+
+
+def by_threshold(measure, threshold):
+    out, group, total = [], [], 0.0
+    for trade in valid:
+        group.append(trade)
+        total += measure(trade)
+        if total >= threshold:
+            out.append(group)
+            group, total = [], 0.0
+    return out + ([group] if group else [])
+
+
+check(bars.VolumeBarer(1000.0), by_threshold(lambda trade: trade[2], 1000.0))
+check(bars.DollarBarer(100_000.0), by_threshold(lambda trade: trade[1] * trade[2], 100_000.0))
+
+# imbalance bars cover every trade once, and the first one is a plain bar of initial_ticks trades
+for measure in ["tick", "volume", "dollar"]:
+    builder = bars.ImbalanceBarer(measure, 100, 0.1)
+    completed = [bar for bar in (builder.update(*trade) for trade in trades) if bar is not None]
+    assert completed[0].trades == 100
+    current = builder.current()
+    assert sum(bar.trades for bar in completed) + (current.trades if current else 0) == len(valid)
+    assert builder.threshold() > 0
+# a flat start has no imbalance to seed with, plain bars go on until the price moves
+builder = bars.ImbalanceBarer("tick", 10, 0.1)
+flat = [builder.update(i, 10.0, 1.0) for i in range(35)]
+assert [i for i, bar in enumerate(flat) if bar is not None] == [9, 19, 29] and math.isnan(builder.threshold())
+moving = [builder.update(35 + i, 10.0 + i, 1.0) for i in range(5)]
+assert moving[-1] is not None and moving[-1].trades == 10 and builder.threshold() > 0
+try:
+    bars.ImbalanceBarer("notional", 100, 0.1)
+    raise AssertionError("unknown measure")
+except ValueError:
+    pass
+
+# a clock completes a time bar without waiting for the next trade
+builder = bars.TimeBarer(1000)
+builder.update(1500, 10.0, 1.0)
+assert builder.advance(1999) is None
+bar = builder.advance(2000)
+assert (bar.start, bar.end, bar.close) == (1000, 2000, 10.0) and builder.current() is None
+
+# completed bars feed the indicators directly
+atr = indicator.ATR(14)
+for bar in check(bars.TickBarer(50), [valid[i : i + 50] for i in range(0, len(valid), 50)]):
+    atr.update(bar.high, bar.low, bar.close)
+assert atr.is_ready()
+
+# the forming bar survives pickle
+builder = bars.VolumeBarer(1000.0)
+for trade in trades[:333]:
+    builder.update(*trade)
+restored = pickle.loads(pickle.dumps(builder))
+assert repr(restored.current()) == repr(builder.current())
+for trade in trades[333:]:
+    assert repr(restored.update(*trade)) == repr(builder.update(*trade))
+print("ok")
@@ -0,0 +1,70 @@
+import numpy as np
+from ta import batch, indicator, pattern
+
+close = np.random.random(100) + 10
+high = close + 0.5
+low = close - 0.5
+
+# batch outputs equal the streaming outputs
+rsi = indicator.RSI(14)
+expected = np.array([rsi.update(x) for x in close])
+np.testing.assert_array_equal(batch.RSI(close, 14), expected)
+
+atr = indicator.ATR(14)
+expected = np.array([atr.update(h, l, c) for h, l, c in zip(high, low, close)])
+np.testing.assert_array_equal(batch.ATR(high, low, close, 14), expected)
+
+mama = indicator.MAMA(0.5, 0.05)
+expected = np.array([mama.update(x) for x in close]).T
+np.testing.assert_array_equal(np.array(batch.MAMA(close)), expected)
+
+sarext = indicator.SAREXT(0.0, 0.0, 0.02, 0.02, 0.2, 0.02, 0.02, 0.2)
+expected = np.array([stop * direction for stop, direction in (sarext.update(x + 0.5, x - 0.5) for x in close)])
+np.testing.assert_array_equal(batch.SAREXT(close + 0.5, close - 0.5), expected)
+
+macdext = indicator.MACDEXT(12, 1, 26, 1, 9, 1)
+expected = np.array([macdext.update(x) for x in close]).T
+np.testing.assert_array_equal(np.array(batch.MACDEXT(close, 12, 1, 26, 1, 9, 1)), expected)
+
+periods = np.random.randint(2, 40, 100).astype(float)
+mavp = indicator.MAVP(2, 30, 0)
+expected = np.array([mavp.update(x, p) for x, p in zip(close, periods)])
+np.testing.assert_array_equal(batch.MAVP(close, periods), expected)
+
+# the volume functions take float volumes, with the streaming parameters as defaults
+volume = np.random.random(100) * 1000
+ohlcv = list(zip(high, low, close, volume))
+for name, streaming, inputs in [
+    ("AD", indicator.AD(14), ohlcv),
+    ("ADOSC", indicator.ADOSC(14, 3, 10), ohlcv),
+    ("OBV", indicator.OBV(14), list(zip(close, volume))),
+    ("CMF", indicator.CMF(20), ohlcv),
+    ("FI", indicator.FI(13), list(zip(close, volume))),
+    ("EMV", indicator.EMV(14, 1e8), list(zip(high, low, volume))),
+    ("PVT", indicator.PVT(), list(zip(close, volume))),
+    ("NVI", indicator.NVI(), list(zip(close, volume))),
+    ("PVI", indicator.PVI(), list(zip(close, volume))),
+    ("KVO", indicator.KVO(34, 55, 13), ohlcv),
+    ("VWMA", indicator.VWMA(20), list(zip(close, volume))),
+    ("VOSC", indicator.VOSC(5, 10), [(v,) for v in volume]),
+]:
+    expected = np.array([streaming.update(*args) for args in inputs]).T
+    np.testing.assert_array_equal(np.array(getattr(batch, name)(*map(np.array, zip(*inputs)))), expected)
+
+open_ = close + np.random.random(100) - 0.5
+engulfing = pattern.Engulfing()
+expected = np.array([engulfing.update(o, max(o, c) + 0.1, min(o, c) - 0.1, c) for o, c in zip(open_, close)])
+np.testing.assert_array_equal(
+    batch.CDLENGULFING(open_, np.maximum(open_, close) + 0.1, np.minimum(open_, close) - 0.1, close), expected
+)
+
+upper, middle, lower = batch.BBANDS(close, 5, 2.0, 2.0)
+print(upper[-5:], middle[-5:], lower[-5:])
+
+# update_many warms up a stateful indicator, then update continues from there
+macd = indicator.MACD(12, 26, 9)
+dif, dea, hist = macd.update_many(close[:-1])
+streaming = indicator.MACD(12, 26, 9)
+for x in close[:-1]:
+    streaming.update(x)
+assert macd.update(close[-1]) == streaming.update(close[-1])
@@ -0,0 +1,104 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+random.seed(23)
+bars = []
+price = 100.0
+for i in range(400):
+    # a trend, then a quiet range, then a trend down
+    drift = 0.3 if i < 150 else (0.0 if i < 250 else -0.3)
+    scale = 0.2 if 150 <= i < 250 else 1.0
+    price += drift + random.gauss(0, scale)
+    bars.append((price + random.random() * scale, price - random.random() * scale, price))
+
+
+def close(a, b):
+    return abs(a - b) <= 1e-9 * max(1.0, abs(b))
+
+
+def midprice(window):
+    return (max(h for h, _, _ in window) + min(l for _, l, _ in window)) / 2
+
+
+# Donchian is the range of the period and its midpoint
+donchian = indicator.Donchian(20)
+for i, (h, l, c) in enumerate(bars):
+    lower, middle, upper = donchian.update(h, l)
+    if i >= 19:
+        window = bars[i - 19:i + 1]
+        assert (lower, upper) == (min(b[1] for b in window), max(b[0] for b in window))
+        assert close(middle, (lower + upper) / 2)
+assert donchian.lookback() == 19
+
+# Keltner is the EMA of the close with ATR bands
+keltner, ema, atr = indicator.Keltner(20, 10, 2.0), indicator.EMA(20), indicator.ATR(10)
+for h, l, c in bars:
+    lower, middle, upper = keltner.update(h, l, c)
+    m, a = ema.update(c), atr.update(h, l, c)
+    assert close(middle, m) and (math.isnan(a) and math.isnan(upper) or close(upper - middle, 2 * a))
+    assert math.isnan(a) or close(middle - lower, 2 * a)
+
+# Ichimoku: the spans of a bar were computed `displacement` bars before it
+ichimoku = indicator.Ichimoku(9, 26, 52, 26)
+outputs, leading = [], []
+for bar in bars:
+    outputs.append(ichimoku.update(*bar))
+    leading.append(ichimoku.leading())
+assert ichimoku.lookback() == 51 + 26
+for i in range(ichimoku.lookback(), len(bars)):
+    tenkan, kijun, span_a, span_b, chikou = outputs[i]
+    assert close(tenkan, midprice(bars[i - 8:i + 1])) and close(kijun, midprice(bars[i - 25:i + 1]))
+    j = i - 26
+    assert close(span_a, (midprice(bars[j - 8:j + 1]) + midprice(bars[j - 25:j + 1])) / 2)
+    assert close(span_b, midprice(bars[j - 51:j + 1]))
+    assert (span_a, span_b) == leading[j] and chikou == bars[i][2]
+assert all(math.isnan(outputs[i][3]) for i in range(ichimoku.lookback()))
+
+# Supertrend follows the trend: long in the rise, short in the fall, the stop on the right side
+supertrend = indicator.Supertrend(10, 3.0)
+outputs = [supertrend.update(*bar) for bar in bars]
+assert all(math.isnan(v) for v in outputs[8]) and not any(math.isnan(v) for v in outputs[9])
+assert all(d == 1.0 for _, d in outputs[9:120]) and sum(d for _, d in outputs[250:]) < -50
+assert all(s <= c if d > 0 else s >= c for (s, d), (_, _, c) in zip(outputs[9:], bars[9:]))
+# the stop only moves with the trend while the trend holds
+for (s, d), (s0, d0) in zip(outputs[10:], outputs[9:]):
+    assert d != d0 or (s >= s0 if d > 0 else s <= s0)
+
+# the classic final-band recursion
+atr, upper, lower, preclose, direction = indicator.ATR(10), math.nan, math.nan, math.nan, 1
+for (h, l, c), output in zip(bars, outputs):
+    a = atr.update(h, l, c)
+    if not math.isnan(a):
+        basic_upper, basic_lower = (h + l) / 2 + 3.0 * a, (h + l) / 2 - 3.0 * a
+        upper = basic_upper if math.isnan(upper) or basic_upper < upper or preclose > upper else upper
+        lower = basic_lower if math.isnan(lower) or basic_lower > lower or preclose < lower else lower
+        direction = 1 if direction < 0 and c > upper else (-1 if direction > 0 and c < lower else direction)
+        assert output == ((lower, 1.0) if direction > 0 else (upper, -1.0))
+    preclose = c
+
+# a NaN bar is ignored, update returns the same as value()
+before = supertrend.value()
+assert supertrend.update(math.nan, 1.0, 1.0) == before == supertrend.value()
+
+# Squeeze fires in the quiet range, when Bollinger Bands lie inside the Keltner Channels
+squeeze = indicator.Squeeze(20, 2.0, 1.5)
+bbands, kc = indicator.BBands(20, 2.0, 2.0, 0), indicator.Keltner(20, 20, 1.5)
+flags = []
+for h, l, c in bars:
+    flag = squeeze.update(h, l, c)
+    bb_lower, _, bb_upper = bbands.update(c)
+    kc_lower, _, kc_upper = kc.update(h, l, c)
+    assert flag == int(bb_lower > kc_lower and bb_upper < kc_upper)
+    flags.append(flag)
+assert all(f == 0 for f in flags[:19]) and set(flags) == {0, 1}
+
+# pickle continues the stream
+for obj in [indicator.Ichimoku(9, 26, 52, 26), indicator.Supertrend(10, 3.0)]:
+    for bar in bars[:200]:
+        obj.update(*bar)
+    copy = pickle.loads(pickle.dumps(obj))
+    assert all(copy.update(*bar) == obj.update(*bar) for bar in bars[200:])
+print("ok")
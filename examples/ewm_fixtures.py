@@ -0,0 +1,207 @@
+# writes examples/fixtures/ewm.json, the expected outputs of examples/ewm_test.py.
+# the outputs come from pandas itself, its version is recorded as the fixture's "source".
+# --transcription writes them from a line by line transcription of the ewm/ewmcov kernels in
+# pandas/_libs/window/aggregations.pyx instead, for machines without pandas.
+# run it again with pandas installed to refresh the fixture against a new pandas version.
+import json
+import math
+import random
+import sys
+from pathlib import Path
+
+random.seed(13)
+x = [random.gauss(100, 5) for _ in range(50)]
+y = [0.5 * v + random.gauss(0, 2) for v in x]
+# NaN at the start, in runs, and in only one of the two series
+for i in [0, 1, 12, 20, 21, 22, 45]:
+    x[i] = math.nan
+for i in [1, 2, 30, 47]:
+    y[i] = math.nan
+# a constant stretch, the means must stay exact there
+for i in range(35, 41):
+    x[i] = y[i] = 101.0
+
+DECAYS = [{"com": 0.5}, {"span": 10.0}, {"halflife": 3.0}, {"alpha": 0.3}]
+CASES = [
+    dict(decay, adjust=adjust, ignore_na=ignore_na, min_periods=1)
+    for decay in DECAYS
+    for adjust in [True, False]
+    for ignore_na in [False, True]
+] + [
+    dict(span=10.0, adjust=True, ignore_na=False, min_periods=5),
+    dict(alpha=0.3, adjust=False, ignore_na=True, min_periods=5),
+]
+
+
+def center_of_mass(com=None, span=None, halflife=None, alpha=None):
+    if com is not None:
+        return com
+    if span is not None:
+        return (span - 1) / 2
+    if halflife is not None:
+        decay = 1 - math.exp(math.log(0.5) / halflife)
+        return 1 / decay - 1
+    return 1 / alpha - 1
+
+
+def ewm(vals, com, adjust, ignore_na, minp):
+    minp = max(minp, 1)
+    alpha = 1.0 / (1.0 + com)
+    old_wt_factor = 1.0 - alpha
+    new_wt = 1.0 if adjust else alpha
+    weighted = vals[0]
+    is_observation = weighted == weighted
+    nobs = int(is_observation)
+    output = [weighted if nobs >= minp else math.nan]
+    old_wt = 1.0
+    for cur in vals[1:]:
+        is_observation = cur == cur
+        nobs += is_observation
+        if weighted == weighted:
+            if is_observation or not ignore_na:
+                old_wt *= old_wt_factor
+                if is_observation:
+                    if weighted != cur:
+                        weighted = old_wt * weighted + new_wt * cur
+                        weighted /= old_wt + new_wt
+                    if adjust:
+                        old_wt += new_wt
+                    else:
+                        old_wt = 1.0
+        elif is_observation:
+            weighted = cur
+        output.append(weighted if nobs >= minp else math.nan)
+    return output
+
+
+def ewmcov(input_x, input_y, com, adjust, ignore_na, minp, bias):
+    minp = max(minp, 1)
+    alpha = 1.0 / (1.0 + com)
+    old_wt_factor = 1.0 - alpha
+    new_wt = 1.0 if adjust else alpha
+    mean_x, mean_y = input_x[0], input_y[0]
+    is_observation = mean_x == mean_x and mean_y == mean_y
+    nobs = int(is_observation)
+    if not is_observation:
+        mean_x = mean_y = math.nan
+    cov = 0.0
+    sum_wt = sum_wt2 = old_wt = 1.0
+    output = []
+    for i in range(len(input_x)):
+        if i > 0:
+            cur_x, cur_y = input_x[i], input_y[i]
+            is_observation = cur_x == cur_x and cur_y == cur_y
+            nobs += is_observation
+            if mean_x == mean_x:
+                if is_observation or not ignore_na:
+                    sum_wt *= old_wt_factor
+                    sum_wt2 *= old_wt_factor * old_wt_factor
+                    old_wt *= old_wt_factor
+                    if is_observation:
+                        old_mean_x, old_mean_y = mean_x, mean_y
+                        if mean_x != cur_x:
+                            mean_x = (old_wt * old_mean_x + new_wt * cur_x) / (old_wt + new_wt)
+                        if mean_y != cur_y:
+                            mean_y = (old_wt * old_mean_y + new_wt * cur_y) / (old_wt + new_wt)
+                        cov = (
+                            old_wt * (cov + (old_mean_x - mean_x) * (old_mean_y - mean_y))
+                            + new_wt * (cur_x - mean_x) * (cur_y - mean_y)
+                        ) / (old_wt + new_wt)
+                        sum_wt += new_wt
+                        sum_wt2 += new_wt * new_wt
+                        old_wt += new_wt
+                        if not adjust:
+                            sum_wt /= old_wt
+                            sum_wt2 /= old_wt * old_wt
+                            old_wt = 1.0
+            elif is_observation:
+                mean_x, mean_y = cur_x, cur_y
+        if nobs >= minp:
+            if bias:
+                output.append(cov)
+            else:
+                numerator = sum_wt * sum_wt
+                denominator = numerator - sum_wt2
+                output.append(numerator / denominator * cov if denominator > 0 else math.nan)
+        else:
+            output.append(math.nan)
+    return output
+
+
+def zsqrt(v):
+    return 0.0 if v < 0 else math.sqrt(v)
+
+
+def divide(a, b):
+    # numpy semantics, 0 / 0 is NaN and a / 0 is a signed inf
+    if b == 0 or a != a or b != b:
+        return math.nan if a == 0 or a != a or b != b else math.copysign(math.inf, a)
+    return a / b
+
+
+def reference(case):
+    decay = {k: case[k] for k in ["com", "span", "halflife", "alpha"] if k in case}
+    args = (center_of_mass(**decay), case["adjust"], case["ignore_na"], case["min_periods"])
+    cov = ewmcov(x, y, *args, True)
+    x_var, y_var = ewmcov(x, x, *args, True), ewmcov(y, y, *args, True)
+    return {
+        "mean": ewm(x, *args),
+        "var": ewmcov(x, x, *args, False),
+        "var_bias": x_var,
+        "std": [zsqrt(v) for v in ewmcov(x, x, *args, False)],
+        "std_bias": [zsqrt(v) for v in x_var],
+        "cov": ewmcov(x, y, *args, False),
+        "cov_bias": cov,
+        "corr": [divide(c, zsqrt(vx * vy)) for c, vx, vy in zip(cov, x_var, y_var)],
+    }
+
+
+def from_pandas(case):
+    import pandas as pd
+
+    sx, sy = pd.Series(x), pd.Series(y)
+    kwargs = {k: v for k, v in case.items()}
+    ex, ey = sx.ewm(**kwargs), sy.ewm(**kwargs)
+    return {
+        "mean": ex.mean(),
+        "var": ex.var(),
+        "var_bias": ex.var(bias=True),
+        "std": ex.std(),
+        "std_bias": ex.std(bias=True),
+        "cov": ex.cov(sy),
+        "cov_bias": ex.cov(sy, bias=True),
+        "corr": ex.corr(sy),
+    }
+
+
+# the fixture is meant to come from pandas, the transcription only stands in where pandas cannot be
+# installed and has to be asked for with --transcription. with pandas both are compared
+if "--transcription" in sys.argv[1:]:
+    source = "transcription of pandas/_libs/window/aggregations.pyx"
+    outputs = [reference(case) for case in CASES]
+else:
+    try:
+        import pandas
+    except ImportError:
+        sys.exit("pandas is required to write the fixture, pass --transcription to write it without pandas")
+
+    source = f"pandas {pandas.__version__}"
+    outputs = [{k: [float(v) for v in s] for k, s in from_pandas(case).items()} for case in CASES]
+    worst = max(
+        abs(a - b) / max(abs(a), 1.0)
+        for output, case in zip(outputs, CASES)
+        for key, expected in reference(case).items()
+        for a, b in zip(output[key], expected)
+        if not (math.isnan(a) or math.isnan(b))
+    )
+    print(f"largest relative difference to the transcription: {worst:.3g}")
+
+fixture = {
+    "source": source,
+    "x": x,
+    "y": y,
+    "cases": [dict(case, expected=expected) for case, expected in zip(CASES, outputs)],
+}
+path = Path(__file__).parent / "fixtures" / "ewm.json"
+path.write_text(json.dumps(fixture) + "\n")
+print(f"wrote {len(CASES)} cases from {source} to {path}")
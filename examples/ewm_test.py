@@ -0,0 +1,67 @@
+import json
+import math
+import pickle
+from pathlib import Path
+
+from ta import ewm
+
+# the example of the pandas docs, B = [0, 1, 2, NaN, 4] with com=0.5
+b = [0.0, 1.0, 2.0, math.nan, 4.0]
+for adjust, ignore_na, expected in [
+    (True, False, [0.0, 0.75, 1.615385, 1.615385, 3.670213]),
+    (False, False, [0.0, 0.666667, 1.555556, 1.555556, 3.650794]),
+    (True, True, [0.0, 0.75, 1.615385, 1.615385, 3.225]),
+]:
+    meaner = ewm.Meaner(com=0.5, adjust=adjust, ignore_na=ignore_na)
+    assert all(abs(meaner.update(v) - e) < 1e-6 for v, e in zip(b, expected))
+
+# exactly one decay parameter, within range, for ewm.alpha and the constructors alike
+assert abs(ewm.alpha(span=9) - 0.2) < 1e-15
+assert abs(ewm.alpha(halflife=1) - 0.5) < 1e-15
+for kwargs in [{}, {"com": 1, "span": 3}, {"alpha": 0}, {"alpha": 1.5}, {"span": 0.5}, {"halflife": 0}]:
+    for make in [ewm.alpha, ewm.Meaner, ewm.Varer, ewm.Stder, ewm.Covarer, ewm.Correlationer]:
+        try:
+            make(**kwargs)
+        except ValueError:
+            continue
+        raise AssertionError((make, kwargs))
+
+# com is the first positional arg as in pandas, the others default to pandas' ewm
+meaner, explicit = ewm.Meaner(0.5), ewm.Meaner(alpha=ewm.alpha(com=0.5), min_periods=1, adjust=True, ignore_na=False)
+assert all(meaner.update(v) == explicit.update(v) for v in [1.0, 2.0, math.nan, 4.0])
+
+
+def same(a, b):
+    if math.isnan(b):
+        return math.isnan(a)
+    return abs(a - b) <= 1e-12 * max(1.0, abs(b))
+
+
+fixture = json.loads((Path(__file__).parent / "fixtures" / "ewm.json").read_text())
+x, y = fixture["x"], fixture["y"]
+for case in fixture["cases"]:
+    decay = {k: case[k] for k in ["com", "span", "halflife", "alpha"] if k in case}
+    kwargs = dict(decay, adjust=case["adjust"], ignore_na=case["ignore_na"], min_periods=case["min_periods"])
+    indicators = {
+        "mean": ewm.Meaner(**kwargs),
+        "var": ewm.Varer(**kwargs),
+        "var_bias": ewm.Varer(**kwargs, bias=True),
+        "std": ewm.Stder(**kwargs),
+        "std_bias": ewm.Stder(**kwargs, bias=True),
+        "cov": ewm.Covarer(**kwargs),
+        "cov_bias": ewm.Covarer(**kwargs, bias=True),
+        "corr": ewm.Correlationer(**kwargs),
+    }
+    for name, indicator in indicators.items():
+        inputs = [(v,) for v in x] if name in ["mean", "var", "var_bias", "std", "std_bias"] else zip(x, y)
+        got = [indicator.update(*v) for v in inputs]
+        assert all(same(g, e) for g, e in zip(got, case["expected"][name])), (name, case)
+
+# the state survives pickle
+corr = ewm.Correlationer(alpha=0.2, min_periods=3)
+for v in zip(x[:25], y[:25]):
+    corr.update(*v)
+restored = pickle.loads(pickle.dumps(corr))
+for v in zip(x[25:], y[25:]):
+    assert same(restored.update(*v), corr.update(*v))
+print("ok")
@@ -0,0 +1,241 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+# talib_hilbert is a line by line port of the array loops of TA-Lib's ta_MAMA.c and ta_HT_*.c,
+# the streaming classes must give the same outputs from the same index on
+RAD2DEG = 45.0 / math.atan(1)
+DEG2RAD = 1.0 / RAD2DEG
+TWO_PI = math.atan(1) * 8.0
+
+
+def talib_hilbert(real, func, fastlimit=0.5, slowlimit=0.05):
+    lookback = 32 if func in ("MAMA", "HT_DCPERIOD", "HT_PHASOR") else 63
+    warmup = 9 if lookback == 32 else 34
+    a, b = 0.0962, 0.5769
+    out = []
+
+    today = 0
+    trailing_idx = 0
+    wma_sub = wma_sum = 0.0
+    for k in range(3):
+        wma_sub += real[today]
+        wma_sum += real[today] * (k + 1)
+        today += 1
+    trailing = 0.0
+
+    def price_wma(price):
+        nonlocal wma_sub, wma_sum, trailing, trailing_idx
+        wma_sub += price
+        wma_sub -= trailing
+        wma_sum += price * 4.0
+        trailing = real[trailing_idx]
+        trailing_idx += 1
+        smoothed = wma_sum * 0.1
+        wma_sum -= wma_sub
+        return smoothed
+
+    for _ in range(warmup):
+        price_wma(real[today])
+        today += 1
+
+    hilbert_idx = 0
+    state = {name: {"odd": [0.0] * 3, "even": [0.0] * 3, "prev_odd": 0.0, "prev_even": 0.0,
+                    "prev_input_odd": 0.0, "prev_input_even": 0.0}
+             for name in ("detrender", "q1", "ji", "jq")}
+
+    def hilbert(name, value, parity, adjusted):
+        s = state[name]
+        temp = a * value
+        result = -s[parity][hilbert_idx]
+        s[parity][hilbert_idx] = temp
+        result += temp
+        result -= s["prev_" + parity]
+        s["prev_" + parity] = b * s["prev_input_" + parity]
+        result += s["prev_" + parity]
+        s["prev_input_" + parity] = value
+        return result * adjusted
+
+    period = prev_i2 = prev_q2 = re = im = 0.0
+    i1_odd_prev3 = i1_even_prev3 = i1_odd_prev2 = i1_even_prev2 = 0.0
+    smooth_period = 0.0
+    mama = fama = prev_phase = 0.0
+    smooth_price = [0.0] * 50
+    smooth_idx = 0
+    dc_phase = 0.0
+    trends = [0.0, 0.0, 0.0]
+    days_in_trend = 0
+    sine = lead_sine = 0.0
+
+    while today < len(real):
+        adjusted = 0.075 * period + 0.54
+        value = real[today]
+        smoothed = price_wma(value)
+        smooth_price[smooth_idx] = smoothed
+        parity = "even" if today % 2 == 0 else "odd"
+        i1 = i1_even_prev3 if parity == "even" else i1_odd_prev3
+        detrender = hilbert("detrender", smoothed, parity, adjusted)
+        q1 = hilbert("q1", detrender, parity, adjusted)
+        ji = hilbert("ji", i1, parity, adjusted)
+        jq = hilbert("jq", q1, parity, adjusted)
+        if parity == "even":
+            hilbert_idx = (hilbert_idx + 1) % 3
+        q2 = (0.2 * (q1 + ji)) + (0.8 * prev_q2)
+        i2 = (0.2 * (i1 - jq)) + (0.8 * prev_i2)
+        if parity == "even":
+            i1_odd_prev3, i1_odd_prev2 = i1_odd_prev2, detrender
+        else:
+            i1_even_prev3, i1_even_prev2 = i1_even_prev2, detrender
+
+        output = None
+        if func == "MAMA":
+            phase = math.atan(q1 / i1) * RAD2DEG if i1 != 0.0 else 0.0
+            delta = max(prev_phase - phase, 1.0)
+            prev_phase = phase
+            alpha = max(fastlimit / delta, slowlimit) if delta > 1.0 else fastlimit
+            mama = alpha * value + (1 - alpha) * mama
+            alpha *= 0.5
+            fama = alpha * mama + (1 - alpha) * fama
+            output = (mama, fama)
+        elif func == "HT_PHASOR":
+            output = (i1, q1)
+
+        re = (0.2 * ((i2 * prev_i2) + (q2 * prev_q2))) + (0.8 * re)
+        im = (0.2 * ((i2 * prev_q2) - (q2 * prev_i2))) + (0.8 * im)
+        prev_q2, prev_i2 = q2, i2
+        prev_period = period
+        if im != 0.0 and re != 0.0:
+            period = 360.0 / (math.atan(im / re) * RAD2DEG)
+        period = min(period, 1.5 * prev_period)
+        period = max(period, 0.67 * prev_period)
+        period = min(max(period, 6.0), 50.0)
+        period = 0.2 * period + 0.8 * prev_period
+        smooth_period = 0.33 * period + 0.67 * smooth_period
+
+        cycle = int(smooth_period + 0.5)
+        prev_dc_phase = dc_phase
+        if func in ("HT_DCPHASE", "HT_SINE", "HT_TRENDMODE"):
+            real_part = imag_part = 0.0
+            idx = smooth_idx
+            for k in range(cycle):
+                angle = (k * TWO_PI) / cycle
+                real_part += math.sin(angle) * smooth_price[idx]
+                imag_part += math.cos(angle) * smooth_price[idx]
+                idx = 49 if idx == 0 else idx - 1
+            if abs(imag_part) > 0.0:
+                dc_phase = math.atan(real_part / imag_part) * RAD2DEG
+            elif abs(imag_part) <= 0.01:
+                if real_part < 0.0:
+                    dc_phase -= 90.0
+                elif real_part > 0.0:
+                    dc_phase += 90.0
+            dc_phase += 90.0
+            dc_phase += 360.0 / smooth_period
+            if imag_part < 0.0:
+                dc_phase += 180.0
+            if dc_phase > 315.0:
+                dc_phase -= 360.0
+            prev_sine, prev_lead_sine = sine, lead_sine
+            sine = math.sin(dc_phase * DEG2RAD)
+            lead_sine = math.sin((dc_phase + 45) * DEG2RAD)
+
+        trendline = 0.0
+        if func in ("HT_TRENDLINE", "HT_TRENDMODE"):
+            total = 0.0
+            for k in range(cycle):
+                total += real[today - k]
+            if cycle > 0:
+                total /= cycle
+            trendline = (4.0 * total + 3.0 * trends[0] + 2.0 * trends[1] + trends[2]) / 10.0
+            trends = [total, trends[0], trends[1]]
+
+        if func == "HT_DCPERIOD":
+            output = smooth_period
+        elif func == "HT_DCPHASE":
+            output = dc_phase
+        elif func == "HT_SINE":
+            output = (sine, lead_sine)
+        elif func == "HT_TRENDLINE":
+            output = trendline
+        elif func == "HT_TRENDMODE":
+            trend = 1
+            if (sine > lead_sine and prev_sine <= prev_lead_sine) or (
+                    sine < lead_sine and prev_sine >= prev_lead_sine):
+                days_in_trend = 0
+                trend = 0
+            days_in_trend += 1
+            if days_in_trend < 0.5 * smooth_period:
+                trend = 0
+            advance = dc_phase - prev_dc_phase
+            if smooth_period != 0.0 and 0.67 * 360.0 / smooth_period < advance < 1.5 * 360.0 / smooth_period:
+                trend = 0
+            price = smooth_price[smooth_idx]
+            if trendline != 0.0 and abs((price - trendline) / trendline) >= 0.015:
+                trend = 1
+            output = trend
+
+        if today >= lookback:
+            out.append(output)
+        smooth_idx = (smooth_idx + 1) % 50
+        today += 1
+    return out
+
+
+def close(x, y):
+    xs = x if isinstance(x, tuple) else (x,)
+    ys = y if isinstance(y, tuple) else (y,)
+    return all(abs(a - b) <= 1e-9 * max(1.0, abs(b)) for a, b in zip(xs, ys))
+
+
+random.seed(7)
+prices = [100.0]
+for _ in range(600):
+    prices.append(prices[-1] * (1 + random.gauss(0, 0.01)) + 0.5 * math.sin(len(prices) / 4.0))
+
+for name, obj in [("MAMA", indicator.MAMA(0.5, 0.05)), ("HT_DCPERIOD", indicator.HTDCPeriod()),
+                  ("HT_PHASOR", indicator.HTPhasor()), ("HT_TRENDLINE", indicator.HTTrendline()),
+                  ("HT_DCPHASE", indicator.HTDCPhase()), ("HT_SINE", indicator.HTSine()),
+                  ("HT_TRENDMODE", indicator.HTTrendMode())]:
+    expected = talib_hilbert(prices, name)
+    assert obj.lookback() == len(prices) - len(expected), name
+    outputs = [obj.update(p) for p in prices]
+    warm, valid = outputs[: obj.lookback()], outputs[obj.lookback():]
+    if name == "HT_TRENDMODE":
+        assert all(v == 0 for v in warm)
+        assert valid == expected and set(valid) == {0, 1}
+    else:
+        flat = [v for w in warm for v in (w if isinstance(w, tuple) else (w,))]
+        assert all(math.isnan(v) for v in flat), name
+        assert all(close(v, e) for v, e in zip(valid, expected)), name
+
+    # a pickled copy continues the same stream, reset starts over
+    obj.reset()
+    for p in prices[:300]:
+        obj.update(p)
+    copy = pickle.loads(pickle.dumps(obj))
+    assert all(copy.update(p) == obj.update(p) for p in prices[300:]), name
+
+# MAMA hugs a trend and FAMA lags it, the dominant cycle of a sine wave is its period
+mama = indicator.MAMA(0.5, 0.05)
+mama_out = [mama.update(100.0 + i) for i in range(200)][-1]
+assert mama_out[1] < mama_out[0] < 300.0
+dcperiod = indicator.HTDCPeriod()
+cycle = [dcperiod.update(100.0 + 5.0 * math.sin(2 * math.pi * i / 20)) for i in range(400)][-1]
+assert abs(cycle - 20.0) < 2.0, cycle
+
+# NaN inputs are ignored, update returns the same as value()
+for cls in [indicator.HTTrendline, indicator.HTDCPeriod, indicator.HTDCPhase, indicator.HTPhasor,
+            indicator.HTSine, indicator.HTTrendMode]:
+    ht = cls()
+    for p in prices[:100]:
+        ht.update(p)
+    before = ht.value()
+    assert ht.update(math.nan) == before == ht.value() and ht.count() == 100, cls
+mama = indicator.MAMA(0.5, 0.05)
+for p in prices[:100]:
+    mama.update(p)
+before = mama.value()
+assert mama.update(math.inf) == before == mama.value()
+print("ok")
@@ -0,0 +1,48 @@
+import math
+import random
+
+from ta import indicator
+
+random.seed(3)
+closes = [100 + random.gauss(0, 1) for _ in range(300)]
+
+
+def talib_kama(closes, n, fast=2, slow=30):
+    # line by line port of TA-Lib's ta_KAMA.c: the efficiency ratio is |close[i] - close[i - n]|
+    # over the n absolute changes since, the first output at n starts from close[n - 1]
+    fast_sc, slow_sc = 2 / (fast + 1), 2 / (slow + 1)
+    outputs = [math.nan] * len(closes)
+    sum_roc = sum(abs(closes[j + 1] - closes[j]) for j in range(n))
+    kama = closes[n - 1]
+    for today in range(n, len(closes)):
+        if today > n:
+            sum_roc += abs(closes[today] - closes[today - 1]) - abs(closes[today - n] - closes[today - n - 1])
+        period_roc = closes[today] - closes[today - n]
+        er = 1.0 if sum_roc <= period_roc or sum_roc == 0 else abs(period_roc / sum_roc)
+        sc = (er * (fast_sc - slow_sc) + slow_sc) ** 2
+        kama += (closes[today] - kama) * sc
+        outputs[today] = kama
+    return outputs
+
+
+# KAMA matches TA-Lib from its lookback on; seeding while the efficiency ratio was still NaN
+# used to leave every later output NaN
+for n in [2, 10, 30]:
+    kama = indicator.KAMA(n, 2, 30)
+    outputs = [kama.update(x, p) for x, p in zip(closes, [math.nan] + closes)]
+    assert kama.lookback() == n and all(math.isnan(v) for v in outputs[:n]), n
+    for got, want in zip(outputs[n:], talib_kama(closes, n)[n:]):
+        assert math.isclose(got, want, rel_tol=1e-12), n
+
+# a flat window is fully efficient, as in TA-Lib
+flat = [100.0] * 12 + [101.0, 102.0]
+kama = indicator.KAMA(10, 2, 30)
+outputs = [kama.update(x, p) for x, p in zip(flat, [math.nan] + flat)]
+assert all(math.isclose(got, want, rel_tol=1e-12) for got, want in zip(outputs[10:], talib_kama(flat, 10)[10:]))
+
+# MA with matype KAMA feeds the previous price itself
+ma, kama = indicator.MA(10, 6), indicator.KAMA(10, 2, 30)
+for x, p in zip(closes, [math.nan] + closes):
+    got, want = ma.update(x), kama.update(x, p)
+    assert math.isnan(got) and math.isnan(want) or got == want
+print("ok")
@@ -0,0 +1,119 @@
+import math
+import random
+
+from ta import cum, indicator, rolling
+
+random.seed(0)
+closes = [10 + random.random() for _ in range(300)]
+bars = [(c + random.random(), c - random.random(), c) for c in closes]
+
+
+def valid(output):
+    values = output if isinstance(output, tuple) else (output,)
+    return not any(isinstance(v, float) and math.isnan(v) for v in values)
+
+
+def check(obj, inputs):
+    # lookback is the index of the first valid output, is_ready flips there as well
+    first = None
+    for i, args in enumerate(inputs):
+        output = obj.update(*args)
+        if first is None and valid(output):
+            first = i
+        assert obj.count() == i + 1
+        assert obj.is_ready() == (i >= obj.lookback()), (obj, i)
+    assert first == obj.lookback(), (type(obj).__name__, first, obj.lookback())
+    obj.reset()
+    assert obj.count() == 0 and not obj.is_ready() or obj.lookback() == 0
+
+
+c = [(x,) for x in closes]
+hl = [(h, l) for h, l, _ in bars]
+hlc = [(h, l, x) for h, l, x in bars]
+hlp = [(h, l, p) for (h, l, _), p in zip(bars[1:], closes)]
+ohlc = [(x, h, l, x + 0.1) for h, l, x in bars]
+
+for cls in [rolling.Sumer, rolling.Meaner, rolling.Stder, rolling.Skewer, rolling.Kurter,
+            rolling.Maxer, rolling.Miner, rolling.MinMaxer, rolling.Deltaer, rolling.Pctchanger,
+            rolling.TSF, rolling.LinearReg]:
+    check(cls(10), c)
+check(rolling.Quantiler(10, 0.5), c)
+check(rolling.Correlationer(10), hl)
+check(rolling.Beta(10), hl)
+
+for cls in [cum.Counter, cum.Sumer, cum.Meaner, cum.Stder, cum.Skewer, cum.Kurter,
+            cum.Maxer, cum.Miner, cum.Deltaer, cum.Pctchanger]:
+    check(cls(), c)
+check(cum.Quantiler(0.5), c)
+check(cum.Correlationer(), hl)
+
+for cls in [indicator.DEMA, indicator.EMA, indicator.SMA, indicator.TEMA, indicator.TRIMA, indicator.WMA,
+            indicator.MIDPOINT, indicator.CMO, indicator.MOM, indicator.ROC, indicator.ROCR,
+            indicator.RSI, indicator.TRIX]:
+    check(cls(10), c)
+check(indicator.BBands(10, 2.0, 2.0, 0), c)
+check(indicator.T3(5, 0.7), c)
+check(indicator.KST(30), c)
+check(indicator.MACD(12, 26, 9), c)
+for matype in range(9):
+    check(indicator.MA(10, matype), c)
+check(indicator.MACDEXT(12, 0, 26, 0, 9, 0), c)
+check(indicator.MAVP(2, 30, 1), [(x, 2 + i % 40) for i, (x,) in enumerate(c)])
+check(indicator.APO(12, 26, 0), c)
+check(indicator.PPO(12, 26, 1), c)
+check(indicator.PPOHist(12, 26, 9), c)
+check(indicator.StochRSI(14, 5, 3, 0), c)
+check(indicator.MAMA(0.5, 0.05), c)
+for cls in [indicator.HTTrendline, indicator.HTDCPeriod, indicator.HTDCPhase, indicator.HTPhasor, indicator.HTSine]:
+    check(cls(), c)
+check(indicator.KAMA(10, 2, 30), [(x, p) for (x,), p in zip(c, [math.nan] + closes)])
+
+for cls in [indicator.MIDPRICE, indicator.Aroon, indicator.AroonOsc, indicator.MinusDM, indicator.PlusDM]:
+    check(cls(10), hl)
+check(indicator.AO(5, 34), hl)
+check(indicator.SAR(0.02, 0.2), hl)
+check(indicator.SAREXT(0.0, 0.0, 0.02, 0.02, 0.2, 0.02, 0.02, 0.2), hl)
+
+for cls in [indicator.ATR, indicator.NATR, indicator.CCI, indicator.DX, indicator.PlusDI, indicator.MinusDI,
+            indicator.WILLR]:
+    check(cls(10), hlc)
+check(indicator.TR(), hlc)
+check(indicator.Donchian(20), hl)
+check(indicator.Keltner(20, 10, 2.0), hlc)
+check(indicator.Ichimoku(9, 26, 52, 26), hlc)
+check(indicator.Supertrend(10, 3.0), hlc)
+check(indicator.TypicalPrice(), hlc)
+check(indicator.WeightedClose(), hlc)
+check(indicator.MedPrice(), hl)
+check(indicator.AvgPrice(), ohlc)
+check(indicator.BOP(), ohlc)
+for cls in [indicator.Parkinson, indicator.GarmanKlass, indicator.RogersSatchell, indicator.YangZhang,
+            indicator.CloseToClose]:
+    check(cls(10, 252.0), ohlc)
+check(indicator.Stoch(5, 3, 0, 3, 0), hlc)
+check(indicator.StochCHF(5, 3, 0), hlc)
+check(indicator.KDJ(9, 3, 3), [(x, h, l) for h, l, x in bars])
+check(indicator.ADX(10), hlp)
+check(indicator.ADXR(10), hlp)
+check(indicator.ULTOSC(7, 14, 28), [(h, l, x, p) for (h, l, x), p in zip(bars[1:], closes)])
+check(indicator.MFI(10), [(h, l, x, 1000.0) for h, l, x in bars])
+hlcv = [(h, l, x, 1000.5) for h, l, x in bars]
+check(indicator.AD(10), hlcv)
+check(indicator.ADOSC(10, 3, 10), hlcv)
+check(indicator.OBV(10), [(x, 1000.5) for x in closes])
+check(indicator.CMF(10), hlcv)
+check(indicator.KVO(34, 55, 13), hlcv)
+check(indicator.EMV(10, 1e8), [(h, l, v) for h, l, _, v in hlcv])
+for cls in [indicator.FI, indicator.VWMA]:
+    check(cls(10), [(x, 1000.5) for x in closes])
+for cls in [indicator.PVT, indicator.NVI, indicator.PVI]:
+    check(cls(), [(x, 1000.5) for x in closes])
+check(indicator.VOSC(5, 10), [(1000.0 + x,) for x in closes])
+tohlcv = [(i * 60_000, h, l, x, 1000.0) for i, (h, l, x) in enumerate(bars)]
+check(indicator.SessionVWAP(3_600_000, 0), tohlcv)
+check(indicator.AnchoredVWAP(0), tohlcv)
+check(indicator.RollingVWAP(10), tohlcv)
+check(indicator.VWAPBands(3_600_000, 0, 2.0, 2.0), tohlcv)
+check(indicator.AnchoredVWAPBands(0, 2.0, 2.0), tohlcv)
+check(indicator.RollingVWAPBands(10, 2.0, 2.0), tohlcv)
+print("ok")
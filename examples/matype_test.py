@@ -0,0 +1,118 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+random.seed(5)
+closes = [100 + random.gauss(0, 1) for _ in range(300)]
+bars = [(c + random.random(), c - random.random(), c) for c in closes]
+
+
+def same(a, b):
+    a = a if isinstance(a, tuple) else (a,)
+    b = b if isinstance(b, tuple) else (b,)
+    return all(math.isnan(x) and math.isnan(y) or x == y for x, y in zip(a, b))
+
+
+# MA dispatches to the concrete classes, matype is a TA-Lib code or a name
+concrete = [lambda: indicator.SMA(10), lambda: indicator.EMA(10), lambda: indicator.WMA(10),
+            lambda: indicator.DEMA(10), lambda: indicator.TEMA(10), lambda: indicator.TRIMA(10),
+            None, lambda: indicator.MAMA(0.5, 0.05), lambda: indicator.T3(10, 0.7)]
+for code, name in enumerate(["sma", "ema", "wma", "dema", "tema", "trima", "kama", "mama", "t3"]):
+    by_code, by_name = indicator.MA(10, code), indicator.MA(10, name.upper())
+    outputs = [by_code.update(x) for x in closes]
+    assert all(same(by_name.update(x), v) for x, v in zip(closes, outputs)), name
+    if concrete[code] is None:
+        kama = indicator.KAMA(10, 2, 30)
+        expected = [kama.update(x, p) for x, p in zip(closes, [math.nan] + closes)]
+    else:
+        reference = concrete[code]()
+        expected = [reference.update(x) for x in closes]
+        expected = [v[0] if isinstance(v, tuple) else v for v in expected]
+    assert all(same(v, e) for v, e in zip(outputs, expected)), name
+    assert pickle.loads(pickle.dumps(by_code)).update(101.0) == by_code.update(101.0)
+
+for bad in [9, -1, "hull"]:
+    try:
+        indicator.MA(10, bad)
+    except (ValueError, OverflowError):
+        continue
+    raise AssertionError(bad)
+
+# APO/PPO with EMA match the EMA difference/ratio, BBANDS middle band is the chosen average
+apo, ppo, bbands = indicator.APO(5, 12, 1), indicator.PPO(5, 12, "ema"), indicator.BBands(5, 2.0, 2.0, 2)
+fast, slow, wma = indicator.EMA(5), indicator.EMA(12), indicator.WMA(5)
+for x in closes:
+    f, s = fast.update(x), slow.update(x)
+    assert apo.update(x) == f - s
+    assert ppo.update(x) == f / s - 1.0
+    assert same(bbands.update(x)[1], wma.update(x))
+
+# matype is optional where TA-Lib's parameter lists end with it and defaults to SMA like the batch functions
+defaults = [(indicator.APO(12, 26), indicator.APO(12, 26, 0)), (indicator.PPO(5, 12), indicator.PPO(5, 12, 0)),
+            (indicator.StochRSI(14, 5, 3), indicator.StochRSI(14, 5, 3, 0)),
+            (indicator.BBands(5, 2.0, 2.0), indicator.BBands(5, 2.0, 2.0, 0)),
+            (indicator.MA(10), indicator.MA(10, 0))]
+for default, explicit in defaults:
+    default.reset()
+    explicit.reset()
+    assert all(same(default.update(x), explicit.update(x)) for x in closes)
+    assert pickle.loads(pickle.dumps(default)).update(101.0) == default.update(101.0)
+stochf, explicit = indicator.StochCHF(5, 3), indicator.StochCHF(5, 3, 0)
+assert all(same(stochf.update(*bar), explicit.update(*bar)) for bar in bars)
+stoch, explicit = indicator.Stoch(5, 3, 0, 3), indicator.Stoch(5, 3, 0, 3, 0)
+assert all(same(stoch.update(*bar), explicit.update(*bar)) for bar in bars)
+mavp, explicit = indicator.MAVP(2, 30), indicator.MAVP(2, 30, 0)
+assert all(same(mavp.update(x, 5 + i % 7), explicit.update(x, 5 + i % 7)) for i, x in enumerate(closes))
+
+# MACDEXT with EMA everywhere is MACD, with SMA its signal starts after the macd line
+macd, macdext = indicator.MACD(12, 26, 9), indicator.MACDEXT(12, 1, 26, 1, 9, 1)
+assert all(macd.update(x) == macdext.update(x) for x in closes)
+macdext = indicator.MACDEXT(12, 0, 26, 0, 9, 0)
+outputs = [macdext.update(x) for x in closes]
+assert macdext.lookback() == 33
+assert math.isnan(outputs[32][1]) and not any(math.isnan(v) for v in outputs[33])
+sma = indicator.SMA(9)
+signal = [sma.update(m) for m, _, _ in outputs[25:]]
+assert all(same(s, o[1]) for s, o in zip(signal, outputs[25:]))
+
+# STOCH smooths with the chosen averages
+stoch, stochf = indicator.Stoch(5, 3, 1, 3, 2), indicator.StochCHF(5, 3, 1)
+slowk, slowd, fastd = indicator.EMA(3), indicator.WMA(3), indicator.EMA(3)
+window = []
+for h, l, c in bars:
+    window = (window + [(h, l)])[-5:]
+    if len(window) < 5:
+        stoch.update(h, l, c)
+        stochf.update(h, l, c)
+        continue
+    hh, ll = max(w[0] for w in window), min(w[1] for w in window)
+    fastk = (c - ll) / (hh - ll)
+    k = slowk.update(fastk)
+    assert same(stoch.update(h, l, c), (k, slowd.update(k)))
+    assert same(stochf.update(h, l, c), (fastk, fastd.update(fastk)))
+
+# STOCHRSI is STOCHF of the RSI over fastk_period, fastd averages fastk
+stochrsi, rsi, fastd = indicator.StochRSI(14, 5, 3, 1), indicator.RSI(14), indicator.EMA(3)
+window = []
+for x in closes:
+    r = rsi.update(x)
+    window = (window + [r])[-5:]
+    if math.isnan(r) or len(window) < 5 or math.isnan(window[0]):
+        stochrsi.update(x)
+        continue
+    fastk = (r - min(window)) / (max(window) - min(window))
+    assert same(stochrsi.update(x), (fastk, fastd.update(fastk)))
+assert indicator.StochRSI(14, 5, 3, 0).lookback() == 20
+
+# a NaN bar returns the current value for every matype, as value() does
+for matype in range(9):
+    ma = indicator.MA(5, matype)
+    macd = indicator.MACDEXT(3, matype, 6, matype, 3, matype)
+    for x in closes[:60]:
+        ma.update(x)
+        macd.update(x)
+    assert same(ma.update(math.nan), ma.value()), matype
+    assert same(macd.update(math.nan), macd.value()), matype
+print("ok")
@@ -0,0 +1,46 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+random.seed(11)
+closes = [100 + random.gauss(0, 1) for _ in range(300)]
+periods = [random.uniform(0, 40) for _ in closes]
+
+
+def same(a, b):
+    return math.isnan(a) and math.isnan(b) or a == b
+
+
+# each bar takes MA(period, matype) of the whole stream, the period truncated and clamped to [5, 20]
+for matype in range(9):
+    mavp = indicator.MAVP(5, 20, matype)
+    mas = {p: indicator.MA(p, matype) for p in range(5, 21)}
+    lookback = mas[20].lookback()
+    assert mavp.lookback() == lookback
+    for i, (x, p) in enumerate(zip(closes, periods)):
+        outputs = {period: ma.update(x) for period, ma in mas.items()}
+        expected = outputs[min(max(int(p), 5), 20)] if i >= lookback else math.nan
+        assert same(mavp.update(x, p), expected), (matype, i)
+
+# a constant period is MA of that period from the lookback of the longest one on
+mavp, sma = indicator.MAVP(2, 30, 0), indicator.SMA(10)
+outputs = [(mavp.update(x, 10.0), sma.update(x)) for x in closes]
+assert all(math.isnan(v) for v, _ in outputs[:29])
+assert all(v == e for v, e in outputs[29:])
+
+# a NaN period gives NaN but the averages keep running
+mavp = indicator.MAVP(2, 30, "ema")
+for x in closes[:100]:
+    mavp.update(x, 14.0)
+assert math.isnan(mavp.update(closes[100], math.nan)) and mavp.count() == 101
+ema = indicator.EMA(14)
+for x in closes[:101]:
+    ema.update(x)
+assert mavp.update(closes[101], 14.0) == ema.update(closes[101])
+
+# pickle continues the stream
+copy = pickle.loads(pickle.dumps(mavp))
+assert all(copy.update(x, p) == mavp.update(x, p) for x, p in zip(closes[102:], periods[102:]))
+print("ok")
@@ -0,0 +1,75 @@
+import math
+import pickle
+
+from ta import cum, rolling
+
+nan = float("nan")
+data = [1.0, 2.0, nan, 4.0, 5.0, nan, 7.0]
+
+
+def run(obj):
+    return [obj.update(x) for x in data]
+
+
+def same(a, b):
+    return all((math.isnan(x) and math.isnan(y)) or abs(x - y) < 1e-12 for x, y in zip(a, b))
+
+
+# default: rolling types propagate NaN until it leaves the window
+assert same(run(rolling.Sumer(3)), [nan, nan, nan, nan, nan, nan, nan])
+
+# skip with pandas-like min_periods: rolling(3, min_periods=2).sum()
+sumer = rolling.Sumer(3)
+sumer.set_nan_policy("skip")
+sumer.set_min_periods(2)
+assert same(run(sumer), [nan, 3.0, 3.0, 6.0, 9.0, 9.0, 12.0])
+assert sumer.lookback() == 1
+
+# rolling(3, min_periods=1).mean()
+meaner = rolling.Meaner(3)
+meaner.set_nan_policy("skip")
+meaner.set_min_periods(1)
+assert same(run(meaner), [1.0, 1.5, 1.5, 3.0, 4.5, 4.5, 6.0])
+
+# fill_previous: ffill() then rolling(3).max()
+maxer = rolling.Maxer(3)
+maxer.set_nan_policy("fill_previous")
+assert same(run(maxer), [nan, nan, 2.0, 4.0, 5.0, 5.0, 7.0])
+
+# fill_value: fillna(0) then rolling(2).min()
+miner = rolling.Miner(2)
+miner.set_nan_policy("fill_value", 0.0)
+assert same(run(miner), [nan, 1.0, 0.0, 0.0, 4.0, 0.0, 0.0])
+
+# indexers report the buffer slot of the extreme, NaN slots are never picked
+indexer = rolling.MaxIndexer(3)
+indexer.set_nan_policy("skip")
+indexer.set_min_periods(1)
+assert [indexer.update(x)[0] for x in data] == [0, 1, 1, 0, 1, 1, 0]
+
+# cum types skip NaN by default, propagate keeps NaN until reset
+assert same(run(cum.Meaner()), [1.0, 1.5, nan, 7 / 3, 3.0, nan, 19 / 5])
+sumer = cum.Sumer()
+sumer.set_nan_policy("propagate")
+assert same(run(sumer), [1.0, 3.0, nan, nan, nan, nan, nan])
+sumer.reset()
+assert sumer.update(1.0) == 1.0
+
+# min_periods on cum types delays the first output
+stder = cum.Stder()
+stder.set_min_periods(3)
+assert math.isnan(stder.update(1.0)) and math.isnan(stder.update(2.0))
+assert not math.isnan(stder.update(3.0))
+
+# the policy is part of the saved state
+quantiler = rolling.Quantiler(3, 0.5)
+quantiler.set_nan_policy("fill_value", 10.0)
+restored = pickle.loads(pickle.dumps(quantiler))
+assert same(run(restored), run(quantiler))
+
+try:
+    rolling.Sumer(3).set_nan_policy("drop")
+    raise AssertionError("unknown policy accepted")
+except ValueError:
+    pass
+print("ok")
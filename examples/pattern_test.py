@@ -0,0 +1,117 @@
+import math
+import pickle
+import random
+
+from ta import pattern
+
+# 12 plain white candles: body 0.5, range 1.0, so a long/short body is 0.5, a doji body 0.1
+base = [(10.0, 10.8, 9.8, 10.5)] * 12
+
+
+def last(cls, candles, *args):
+    detector = cls(*args)
+    return [detector.update(*c) for c in candles][-1]
+
+
+# TA-Lib lookbacks with the default candle settings
+lookbacks = {
+    "Doji": 10, "TwoCrows": 12, "ThreeBlackCrows": 13, "Engulfing": 2, "ThreeOutside": 3,
+    "Hikkake": 5, "HikkakeMod": 10, "XSideGapThreeMethods": 2, "Hammer": 11, "BeltHold": 10,
+    "Kicking": 11, "Breakaway": 14, "ConcealBabySwallow": 13, "ThreeLineStrike": 8,
+    "GapSideSideWhite": 7, "TasukiGap": 7, "StickSandwich": 7, "MatchingLow": 6,
+    "Counterattack": 11, "Tristar": 12, "SpinningTop": 10, "HighWave": 10,
+}
+for name, lookback in lookbacks.items():
+    assert getattr(pattern, name)().lookback() == lookback, name
+assert pattern.MatHold(0.5).lookback() == 14
+assert pattern.MorningStar(0.3).lookback() == 12
+
+# single candles
+doji = (10.0, 10.5, 9.5, 10.02)
+assert last(pattern.Doji, base + [doji]) == 100
+assert last(pattern.LongLeggedDoji, base + [doji]) == 100
+assert last(pattern.RickshawMan, base + [doji]) == 100
+assert last(pattern.Doji, base + [(10.0, 10.5, 9.5, 10.3)]) == 0
+assert last(pattern.Marubozu, base + [(10.0, 11.0, 10.0, 11.0)]) == 100
+assert last(pattern.Marubozu, base + [(11.0, 11.0, 10.0, 10.0)]) == -100
+
+# engulfing, ±80 when the bodies share an end
+black = (10.5, 10.6, 9.9, 10.0)
+assert last(pattern.Engulfing, base + [black, (9.9, 10.8, 9.8, 10.7)]) == 100
+assert last(pattern.Engulfing, base + [black, (10.0, 10.8, 9.8, 10.7)]) == 80
+assert last(pattern.Engulfing, base + [black, (10.1, 10.4, 10.0, 10.3)]) == 0
+assert last(pattern.Harami, base + [(11.0, 11.1, 9.9, 10.0), (10.3, 10.6, 10.2, 10.5)]) == 100
+
+# kicking: a white marubozu gapping up from a black one, by length the longer one decides
+kick = base + [(11.0, 11.0, 10.0, 10.0), (11.2, 12.4, 11.2, 12.4)]
+assert last(pattern.Kicking, kick) == 100 and last(pattern.KickingByLength, kick) == 100
+kick = base + [(11.0, 11.0, 10.0, 10.0), (11.2, 11.9, 11.2, 11.9)]
+assert last(pattern.Kicking, kick) == 100 and last(pattern.KickingByLength, kick) == -100
+
+# hammer near the prior low, the same shape near the prior high is not a hanging man
+hammer = (9.9, 10.01, 9.3, 10.0)
+assert last(pattern.Hammer, base + [hammer]) == 100
+assert last(pattern.HangingMan, base + [hammer]) == 0
+assert last(pattern.Takuri, base + [(10.0, 10.01, 9.0, 10.0)]) == 100
+
+# morning star: long black, a doji gapping down with its shadows, a white one gapping back up
+star = base + [(11.0, 11.1, 9.9, 10.0), (9.7, 9.8, 9.5, 9.75), (9.9, 10.7, 9.85, 10.6)]
+assert last(pattern.MorningStar, star, 0.3) == 100
+assert last(pattern.MorningDojiStar, star, 0.3) == 100
+assert last(pattern.AbandonedBaby, star, 0.3) == 100
+assert last(pattern.MorningStar, star, 0.7) == 0  # closes within 70% of the black body
+assert last(pattern.EveningStar, star, 0.3) == 0
+
+# three black crows after a white candle
+crows = base + [(10.4, 10.45, 9.9, 9.95), (10.2, 10.25, 9.45, 9.5), (9.7, 9.75, 8.95, 9.0)]
+assert last(pattern.ThreeBlackCrows, crows) == -100
+assert last(pattern.IdenticalThreeCrows, crows) == 0
+
+# hikkake: an inside bar broken to the downside, then a close above the inside bar confirms it
+setup = base + [(10.0, 11.0, 9.0, 10.5), (10.2, 10.8, 9.5, 10.4), (10.0, 10.5, 9.2, 9.4)]
+hikkake = pattern.Hikkake()
+assert [hikkake.update(*c) for c in setup][-1] == 100
+assert hikkake.update(9.5, 11.0, 9.4, 10.9) == 200
+assert hikkake.update(9.5, 11.0, 9.4, 10.9) == 0
+
+# NaN candles are ignored
+doji_detector = pattern.Doji()
+for c in base:
+    doji_detector.update(*c)
+assert doji_detector.update(math.nan, 10.0, 9.0, 9.5) == 0 and doji_detector.count() == len(base)
+assert doji_detector.update(*doji) == 100
+
+# random walk: 0 during the lookback, TA-Lib codes only, pickle continues the same stream
+random.seed(7)
+candles, close = [], 10.0
+for _ in range(2000):
+    open_ = close + random.gauss(0, 0.1)
+    close = open_ + random.gauss(0, 0.3)
+    high = max(open_, close) + abs(random.gauss(0, 0.15))
+    low = min(open_, close) - abs(random.gauss(0, 0.15))
+    candles.append((open_, high, low, close))
+
+classes = [getattr(pattern, name) for name in dir(pattern) if isinstance(getattr(pattern, name), type)]
+assert len(classes) == 61
+PENETRATION = {"AbandonedBaby": 0.3, "DarkCloudCover": 0.5, "EveningDojiStar": 0.3, "EveningStar": 0.3,
+               "MatHold": 0.5, "MorningDojiStar": 0.3, "MorningStar": 0.3}
+hits = {}
+for cls in classes:
+    # penetration defaults to TA-Lib's, like the batch functions
+    detector = cls()
+    outputs = [detector.update(*c) for c in candles[:1000]]
+    if cls.__name__ in PENETRATION:
+        explicit = cls(PENETRATION[cls.__name__])
+        assert [explicit.update(*c) for c in candles[:1000]] == outputs, cls.__name__
+    assert not any(outputs[: detector.lookback()]), cls.__name__
+    assert set(outputs) <= {-200, -100, -80, 0, 80, 100, 200}, cls.__name__
+    restored = pickle.loads(pickle.dumps(detector))
+    for c in candles[1000:]:
+        out = detector.update(*c)
+        assert restored.update(*c) == out
+        outputs.append(out)
+    hits[cls.__name__] = sum(o != 0 for o in outputs)
+    detector.reset()
+    assert detector.count() == 0 and detector.update(*candles[0]) == 0
+print(sorted(hits.items(), key=lambda kv: -kv[1])[:10])
+print("ok")
@@ -0,0 +1,28 @@
+from ta import cum, indicator, rolling
+
+rsi = indicator.RSI(14)
+for i in range(30):
+    rsi.update(10 + i % 5)
+
+before = rsi.value()
+what_if = rsi.peek(12.0)
+assert rsi.value() == before
+assert rsi.update(12.0) == what_if
+
+bbands = indicator.BBands(5, 2.0, 2.0, 0)
+quantiler = cum.Quantiler(0.5)
+container = rolling.Container(3)
+for i in range(10):
+    bbands.update(i)
+    quantiler.update(i)
+    container.update(i)
+print(bbands.peek(20.0), bbands.value())
+print(quantiler.peek(100.0), quantiler.value())
+assert container.peek(99.0) == (8.0, 99.0) and container.value() == (7.0, 9.0)
+
+# a forming bar is replaced by the next update, so peek looks past it
+ema = indicator.EMA(3)
+ema.update(1.0)
+ema.update(5.0, is_final=False)
+assert ema.peek(2.0) == ema.update(2.0)
+print("ok")
@@ -0,0 +1,49 @@
+import math
+import pickle
+import random
+
+from ta import cum, indicator, rolling
+
+
+def same(a, b):
+    if isinstance(a, tuple):
+        return all(same(x, y) for x, y in zip(a, b))
+    return a == b or (math.isnan(a) and math.isnan(b))
+
+
+def check(make, inputs):
+    # feed half, snapshot, then the restored copy must match the original step by step
+    half = len(inputs) // 2
+    original = make()
+    for args in inputs[:half]:
+        original.update(*args)
+
+    restored = pickle.loads(pickle.dumps(original))
+    from_json = make()
+    from_json.load_json(original.to_json())
+
+    for args in inputs[half:]:
+        expected = original.update(*args)
+        assert same(restored.update(*args), expected), (make, args)
+        assert same(from_json.update(*args), expected), (make, args)
+
+
+random.seed(0)
+closes = [(10 + random.random(),) for _ in range(100)]
+bars = [(c + 0.5, c - 0.5, c) for (c,) in closes]
+
+check(lambda: rolling.Container(10), closes)
+check(lambda: rolling.Sumer(10), closes)
+check(lambda: rolling.Stder(10), closes)
+check(lambda: rolling.Quantiler(10, 0.3), closes)
+check(lambda: rolling.MaxIndexer(10), closes)
+check(lambda: cum.Maxer(), closes)
+check(lambda: cum.Quantiler(0.5), closes)
+check(lambda: cum.Pctchanger(), closes)
+check(lambda: indicator.EMA(10), closes)
+check(lambda: indicator.KAMA(10, 2, 30), [(c, p) for (c,), (p,) in zip(closes[1:], closes)])
+check(lambda: indicator.MACD(12, 26, 9), closes)
+check(lambda: indicator.ATR(14), bars)
+check(lambda: indicator.ADX(14), bars)
+check(lambda: indicator.Stoch(5, 3, 0, 3, 0), bars)
+print("ok")
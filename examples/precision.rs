@@ -0,0 +1,255 @@
+// Precision suite for the rolling moments: compares Sumer, Stder, Skewer and Kurter
+// against a two-pass computation over the same window on adversarial inputs,
+// next to the naive power sums the rolling types used before, and the VWAP std the same way.
+// cargo run --release --example precision
+use std::collections::VecDeque;
+use ta::indicator::volume::{RollingVWAP, SessionVWAP};
+use ta::rolling::statis::{Kurter, Skewer, Stder, Sumer};
+use ta::Indicator;
+
+// small deterministic generator, uniform in [-1, 1)
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }
+}
+
+// sum, sample std, population skew and bias-corrected kurt, as the rolling types define them
+fn two_pass(window: &VecDeque<f64>) -> [f64; 4] {
+    let n = window.len() as f64;
+    let sum: f64 = window.iter().sum();
+    // corrected two-pass mean, the plain one is off by a few ulps of the offset
+    let mean = sum / n + window.iter().map(|x| x - sum / n).sum::<f64>() / n;
+    let central = |p: i32| window.iter().map(|x| (x - mean).powi(p)).sum::<f64>();
+    let (m2, m3, m4) = (central(2), central(3), central(4));
+
+    let std = (m2 / (n - 1.0)).sqrt();
+    let skew = (m3 / n) / (m2 / n).powf(1.5);
+    let variance = m2 / (n - 1.0);
+    let factor01 = n * (n + 1.0) / ((n - 1.0) * (n - 2.0) * (n - 3.0));
+    let factor02 = (n - 1.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0));
+    let kurt = factor01 * m4 / variance.powi(2) - factor02;
+    [sum, std, skew, kurt]
+}
+
+// raw power sums updated by add/subtract, the approach this suite guards against
+struct Naive {
+    sums: [f64; 4],
+}
+
+impl Naive {
+    fn update(&mut self, new_val: f64, old_val: Option<f64>) {
+        for (p, sum) in self.sums.iter_mut().enumerate() {
+            *sum += new_val.powi(p as i32 + 1);
+            if let Some(old_val) = old_val {
+                *sum -= old_val.powi(p as i32 + 1);
+            }
+        }
+    }
+
+    fn std(&self, n: f64) -> f64 {
+        let [s1, s2, _, _] = self.sums;
+        ((s2 - s1 * s1 / n) / (n - 1.0)).sqrt()
+    }
+}
+
+// volume-weighted mean and population std of (price, volume) pairs in two passes
+fn weighted_two_pass(window: &VecDeque<(f64, f64)>) -> (f64, f64) {
+    let volume: f64 = window.iter().map(|(_, v)| v).sum();
+    let mean = window.iter().map(|(p, v)| p * v).sum::<f64>() / volume;
+    let variance = window
+        .iter()
+        .map(|(p, v)| v * (p - mean).powi(2))
+        .sum::<f64>()
+        / volume;
+    (mean, variance.sqrt())
+}
+
+// error relative to the two-pass value, absolute when it is near 0
+fn error(got: f64, want: f64) -> f64 {
+    if got.is_nan() || want.is_nan() {
+        if got.is_nan() == want.is_nan() {
+            0.0
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        (got - want).abs() / want.abs().max(1.0)
+    }
+}
+
+struct Case {
+    name: &'static str,
+    window: usize,
+    data: Vec<f64>,
+}
+
+fn cases() -> Vec<Case> {
+    let mut rng = Lcg(42);
+    let mut walk = 50000.0;
+    vec![
+        Case {
+            name: "btc-like prices around 50000",
+            window: 20,
+            data: (0..200_000)
+                .map(|_| {
+                    walk += rng.next() * 25.0;
+                    walk
+                })
+                .collect(),
+        },
+        Case {
+            name: "tiny noise on a 1e9 offset",
+            window: 30,
+            data: (0..100_000).map(|_| 1e9 + rng.next()).collect(),
+        },
+        Case {
+            name: "1e8 spike block then unit values",
+            window: 10,
+            data: (0..100_000)
+                .map(|i| {
+                    if (i / 1000) % 2 == 0 {
+                        1e8 + rng.next() * 1e4
+                    } else {
+                        1.0 + rng.next()
+                    }
+                })
+                .collect(),
+        },
+        Case {
+            name: "alternating magnitudes 1e6 and 1e-3",
+            window: 15,
+            data: (0..100_000)
+                .map(|i| if i % 2 == 0 { 1e6 } else { 1e-3 } * (1.0 + rng.next() * 0.1))
+                .collect(),
+        },
+        Case {
+            name: "1M ticks of a slow drift",
+            window: 50,
+            data: (0..1_000_000)
+                .map(|i| 100.0 + i as f64 * 1e-4 + rng.next() * 1e-3)
+                .collect(),
+        },
+    ]
+}
+
+fn main() {
+    // max error allowed for sum, std, skew and kurt
+    let tolerance = [1e-12, 1e-8, 1e-6, 1e-6];
+    let mut failed = false;
+
+    println!(
+        "{:<40} {:>10} {:>10} {:>10} {:>10} {:>12}",
+        "case", "sum", "std", "skew", "kurt", "naive std"
+    );
+    for case in cases() {
+        let n = case.window;
+        let mut sumer = Sumer::new(n);
+        let mut stder = Stder::new(n);
+        let mut skewer = Skewer::new(n);
+        let mut kurter = Kurter::new(n);
+        let mut naive = Naive { sums: [0.0; 4] };
+        let mut window = VecDeque::with_capacity(n);
+        let mut worst = [0.0f64; 4];
+        let mut naive_worst = 0.0f64;
+
+        for &x in &case.data {
+            let got = [
+                sumer.update(x),
+                stder.update(x),
+                skewer.update(x),
+                kurter.update(x),
+            ];
+            window.push_back(x);
+            let old = if window.len() > n {
+                window.pop_front()
+            } else {
+                None
+            };
+            naive.update(x, old);
+            if window.len() < n {
+                continue;
+            }
+
+            let want = two_pass(&window);
+            for k in 0..4 {
+                worst[k] = worst[k].max(error(got[k], want[k]));
+            }
+            naive_worst = naive_worst.max(error(naive.std(n as f64), want[1]));
+        }
+
+        println!(
+            "{:<40} {:>10.1e} {:>10.1e} {:>10.1e} {:>10.1e} {:>12.1e}",
+            case.name, worst[0], worst[1], worst[2], worst[3], naive_worst
+        );
+        failed |= worst.iter().zip(tolerance).any(|(e, tol)| *e > tol);
+    }
+
+    assert!(!failed, "rolling moments exceed the tolerance");
+    vwap_case();
+    println!("ok");
+}
+
+// 100k bars around 50000 with a std of 0.0374, where sum(p^2 * v) / sum(v) - vwap^2 is all
+// rounding: the bands of the naive std collapse onto the vwap
+fn vwap_case() {
+    let window = 1000;
+    let mut rng = Lcg(7);
+    let bars: Vec<(f64, f64)> = (0..100_000)
+        .map(|_| (50000.0 + rng.next() * 0.0648, 5.0 + rng.next() * 4.0))
+        .collect();
+
+    let mut session = SessionVWAP::new(i64::MAX, 0);
+    let mut rolling = RollingVWAP::new(window);
+    let mut all = VecDeque::new();
+    let mut last = VecDeque::with_capacity(window);
+    let (mut square_volume, mut price_volume, mut volume) = (0.0, 0.0, 0.0);
+    let mut worst = [0.0f64; 3];
+    let mut naive_worst = 0.0f64;
+
+    for (i, &(price, v)) in bars.iter().enumerate() {
+        session.update(i as i64, price, price, price, v);
+        rolling.update(i as i64, price, price, price, v);
+        all.push_back((price, v));
+        last.push_back((price, v));
+        if last.len() > window {
+            last.pop_front();
+        }
+        square_volume += price * price * v;
+        price_volume += price * v;
+        volume += v;
+        if i % 1000 != 999 {
+            continue;
+        }
+
+        let (mean, std) = weighted_two_pass(&all);
+        worst[0] = worst[0].max(error(session.std(), std) / std);
+        worst[1] = worst[1].max(error(session.value(), mean));
+        let naive = (square_volume / volume - (price_volume / volume).powi(2))
+            .max(0.0)
+            .sqrt();
+        naive_worst = naive_worst.max(error(naive, std) / std);
+
+        let (_, std) = weighted_two_pass(&last);
+        worst[2] = worst[2].max(error(rolling.std(), std) / std);
+    }
+
+    println!(
+        "{:<40} {:>10} {:>10} {:>10} {:>10} {:>12}",
+        "vwap", "session", "vwap", "rolling", "", "naive std"
+    );
+    println!(
+        "{:<40} {:>10.1e} {:>10.1e} {:>10.1e} {:>10} {:>12.1e}",
+        "price 50000, std 0.0374", worst[0], worst[1], worst[2], "", naive_worst
+    );
+    assert!(
+        worst[0] < 1e-6 && worst[1] < 1e-12 && worst[2] < 1e-6,
+        "VWAP std exceeds the tolerance"
+    );
+}
@@ -0,0 +1,56 @@
+import math
+import pickle
+import random
+
+from ta import rolling
+
+random.seed(5)
+n = 20
+data = [random.choice([float(random.randint(0, 9)), random.gauss(50, 10)]) for _ in range(500)]
+
+
+def quantile(values, q):
+    values = sorted(values)
+    index = (len(values) - 1) * q
+    lower = math.floor(index)
+    upper = min(lower + 1, len(values) - 1)
+    return values[lower] + (index - lower) * (values[upper] - values[lower])
+
+
+def pct_rank(values, x):
+    below = sum(v < x for v in values)
+    equal = sum(v == x for v in values)
+    return (below + (equal + 1) / 2) / len(values)
+
+
+qs = [0.05, 0.25, 0.5, 0.75, 0.95]
+medianer = rolling.Medianer(n)
+ranker = rolling.PctRanker(n)
+multi = rolling.MultiQuantiler(n, qs)
+assert multi.quantiles() == qs
+for i, x in enumerate(data):
+    median, rank, values = medianer.update(x), ranker.update(x), multi.update(x)
+    if i < n - 1:
+        assert math.isnan(median) and math.isnan(rank) and all(math.isnan(v) for v in values)
+        continue
+    window = data[i + 1 - n : i + 1]
+    assert abs(median - quantile(window, 0.5)) < 1e-9
+    assert abs(rank - pct_rank(window, x)) < 1e-12
+    assert all(abs(v - quantile(window, q)) < 1e-9 for v, q in zip(values, qs))
+
+# quantiles outside [0, 1] are NaN instead of a panic
+bad = rolling.Quantiler(1, 1.5)
+assert math.isnan(bad.update(1.0))
+
+# the latest value being NaN has no rank
+ranker = rolling.PctRanker(3)
+ranker.set_nan_policy("skip")
+ranker.set_min_periods(1)
+assert [ranker.update(x) for x in [1.0, 3.0, 2.0]] == [1.0, 1.0, 2 / 3]
+assert math.isnan(ranker.update(float("nan")))
+
+# the order-statistic tree survives pickle
+restored = pickle.loads(pickle.dumps(multi))
+for x in data[:100]:
+    assert restored.update(x) == multi.update(x)
+print("ok")
@@ -0,0 +1,87 @@
+import math
+import pickle
+import random
+
+from ta import bars, indicator
+
+random.seed(16)
+minute, hour = 60_000, 3_600_000
+rows, price, ts = [], 100.0, 0
+for _ in range(3000):
+    # gaps, e.g. a lunch break or missing minutes
+    ts += minute * random.choice([1, 1, 1, 2, 45])
+    o = price
+    c = o + random.gauss(0, 0.3)
+    h, l = max(o, c) + random.random() * 0.2, min(o, c) - random.random() * 0.2
+    rows.append((ts, o, h, l, c, float(random.randint(100, 1000))))
+    price = c
+
+
+def hourly(rows):
+    out = {}
+    for ts, o, h, l, c, v in rows:
+        if ts // hour not in out:
+            out[ts // hour] = [o, h, l, c, v]
+        else:
+            bar = out[ts // hour]
+            bar[1], bar[2], bar[3], bar[4] = max(bar[1], h), min(bar[2], l), c, bar[4] + v
+    return list(out.values())
+
+
+def same(a, b):
+    if isinstance(a, tuple):
+        return all(same(x, y) for x, y in zip(a, b))
+    return (math.isnan(a) and math.isnan(b)) or abs(a - b) < 1e-9
+
+
+cases = [
+    (lambda: indicator.ATR(14), ("high", "low", "close")),
+    (lambda: indicator.RSI(14), ("close",)),
+    (lambda: indicator.MACD(12, 26, 9), ("close",)),
+    (lambda: indicator.ADX(5), ("high", "low", "preclose")),
+]
+for make, fields in cases:
+    resampler = bars.Resampler(make(), hour, fields)
+    reference, closed_bars, preclose = make(), [], math.nan
+    for i, row in enumerate(rows):
+        closed, provisional = resampler.update(*row)
+        bucket = row[0] // hour
+        done = [r for r in rows[:i] if r[0] // hour < bucket]
+        bars_so_far = hourly(done)
+        while len(closed_bars) < len(bars_so_far):
+            o, h, l, c, v = bars_so_far[len(closed_bars)]
+            args = {"open": o, "high": h, "low": l, "close": c, "volume": v, "preclose": preclose}
+            reference.update(*[args[f] for f in fields])
+            closed_bars.append(c)
+            preclose = c
+        o, h, l, c, v = hourly([r for r in rows[: i + 1] if r[0] // hour == bucket])[0]
+        args = {"open": o, "high": h, "low": l, "close": c, "volume": v, "preclose": preclose}
+        assert same(closed, reference.value()), (fields, i)
+        assert same(provisional, reference.peek(*[args[f] for f in fields])), (fields, i)
+
+    current = resampler.current()
+    assert current.start % hour == 0 and current.end == current.start + hour
+
+# a clock closes the hour without waiting for the next bar
+resampler = bars.Resampler(indicator.SMA(2), hour)
+resampler.update(0, 1.0, 1.0, 1.0, 1.0, 1.0)
+resampler.update(hour - minute, 3.0, 3.0, 3.0, 3.0, 1.0)
+closed, provisional = resampler.advance(hour)
+assert math.isnan(closed) and resampler.current() is None and resampler.indicator.count() == 1
+closed, provisional = resampler.update(hour, 5.0, 5.0, 5.0, 5.0, 1.0)
+assert math.isnan(closed) and provisional == 4.0
+
+try:
+    bars.Resampler(indicator.SMA(2), hour, ("close", "vwap"))
+    raise AssertionError("unknown field")
+except ValueError:
+    pass
+
+# the indicator and the forming bar survive pickle
+resampler = bars.Resampler(indicator.ATR(14), hour, ("high", "low", "close"))
+for row in rows[:1000]:
+    resampler.update(*row)
+restored = pickle.loads(pickle.dumps(resampler))
+for row in rows[1000:]:
+    assert same(restored.update(*row), resampler.update(*row))
+print("ok")
@@ -0,0 +1,102 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+
+# talib_sarext is a port of the array loop of TA-Lib's ta_SAREXT.c, SAR is the same with symmetric parameters
+def talib_sarext(high, low, start=0.0, offset=0.0, init_long=0.02, step_long=0.02, max_long=0.2,
+                 init_short=0.02, step_short=0.02, max_short=0.2):
+    init_long, step_long = min(init_long, max_long), min(step_long, max_long)
+    init_short, step_short = min(init_short, max_short), min(step_short, max_short)
+    af_long, af_short = init_long, init_short
+    if start == 0.0:
+        diff_m, diff_p = low[0] - low[1], high[1] - high[0]
+        is_long = not (diff_m > 0 and diff_p < diff_m)
+        ep, sar = (high[1], low[0]) if is_long else (low[1], high[0])
+    else:
+        is_long = start > 0
+        ep, sar = (high[1] if is_long else low[1]), abs(start)
+    new_low, new_high = low[1], high[1]
+    out = []
+    for today in range(1, len(high)):
+        prev_low, prev_high = new_low, new_high
+        new_low, new_high = low[today], high[today]
+        if is_long:
+            if new_low <= sar:
+                is_long = False
+                sar = max(ep, prev_high, new_high)
+                if offset != 0.0:
+                    sar += sar * offset
+                out.append(-sar)
+                af_short = init_short
+                ep = new_low
+                sar = max(sar + af_short * (ep - sar), prev_high, new_high)
+            else:
+                out.append(sar)
+                if new_high > ep:
+                    ep = new_high
+                    af_long = min(af_long + step_long, max_long)
+                sar = min(sar + af_long * (ep - sar), prev_low, new_low)
+        else:
+            if new_high >= sar:
+                is_long = True
+                sar = min(ep, prev_low, new_low)
+                if offset != 0.0:
+                    sar -= sar * offset
+                out.append(sar)
+                af_long = init_long
+                ep = new_high
+                sar = min(sar + af_long * (ep - sar), prev_low, new_low)
+            else:
+                out.append(-sar)
+                if new_low < ep:
+                    ep = new_low
+                    af_short = min(af_short + step_short, max_short)
+                sar = max(sar + af_short * (ep - sar), prev_high, new_high)
+    return out
+
+
+# a steady rise: the stop starts at the first low and accelerates towards the new highs,
+# the first close below it reverses to short at the highest high
+sar = indicator.SAR(0.02, 0.2)
+assert all(math.isnan(v) for v in sar.update(10.0, 9.0))
+assert sar.update(11.0, 10.0) == (9.0, 1.0)
+stop, direction = sar.update(12.0, 11.0)
+assert direction == 1.0 and abs(stop - (9.0 + 0.02 * (11.0 - 9.0))) < 1e-12
+stop, direction = sar.update(12.5, 8.0)
+assert direction == -1.0 and stop == 12.5
+
+random.seed(3)
+high, low = [], []
+price = 100.0
+for _ in range(500):
+    price += random.gauss(0, 1)
+    high.append(price + random.random())
+    low.append(price - random.random())
+
+for args in [(0.0, 0.0, 0.02, 0.02, 0.2, 0.02, 0.02, 0.2), (0.0, 0.01, 0.01, 0.03, 0.3, 0.04, 0.02, 0.1),
+             (95.0, 0.0, 0.02, 0.02, 0.2, 0.02, 0.02, 0.2), (-110.0, 0.05, 0.3, 0.3, 0.2, 0.02, 0.02, 0.2)]:
+    sarext = indicator.SAREXT(*args)
+    expected = talib_sarext(high, low, *args)
+    outputs = [sarext.update(h, l) for h, l in zip(high, low)]
+    assert all(math.isnan(v) for v in outputs[0])
+    assert all(abs(s * d - e) < 1e-9 for (s, d), e in zip(outputs[1:], expected)), args
+    assert {d for _, d in outputs[1:]} == {1.0, -1.0}
+
+# SAR is SAREXT with the same acceleration on both sides
+sar, sarext = indicator.SAR(0.02, 0.2), indicator.SAREXT(0.0, 0.0, 0.02, 0.02, 0.2, 0.02, 0.02, 0.2)
+sar.update(high[0], low[0])
+sarext.update(high[0], low[0])
+assert all(sar.update(h, l) == sarext.update(h, l) for h, l in zip(high[1:], low[1:]))
+
+# NaN bars are ignored, pickle continues the stream
+sar = indicator.SAR(0.02, 0.2)
+for h, l in zip(high[:200], low[:200]):
+    sar.update(h, l)
+before = sar.value()
+assert sar.update(math.nan, 99.0) == before == sar.value() and sar.count() == 200
+copy = pickle.loads(pickle.dumps(sar))
+assert all(copy.update(h, l) == sar.update(h, l) for h, l in zip(high[200:], low[200:]))
+print("ok")
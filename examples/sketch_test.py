@@ -0,0 +1,56 @@
+import math
+import pickle
+import random
+
+from ta import cum
+
+random.seed(9)
+
+
+def exact(values, q):
+    values = sorted(values)
+    index = (len(values) - 1) * q
+    lower = math.floor(index)
+    upper = min(lower + 1, len(values) - 1)
+    return values[lower] + (index - lower) * (values[upper] - values[lower])
+
+
+def rank_error(values, estimate, q):
+    values = sorted(values)
+    below = sum(v < estimate for v in values)
+    return abs(below / len(values) - q)
+
+
+# small streams are kept exactly, the sketch agrees with the exact mode
+sketch, exact_mode = cum.SketchQuantiler(0.3, 100.0), cum.Quantiler(0.3)
+for x in [random.gauss(0, 1) for _ in range(50)]:
+    assert abs(sketch.update(x) - exact_mode.update(x)) < 1e-12
+
+# large streams stay within the rank error and the memory bound
+data = [random.lognormvariate(0, 1) for _ in range(200_000)]
+sketch = cum.SketchQuantiler(0.5, 100.0)
+for x in data:
+    sketch.update(x)
+assert sketch.size() <= 200, sketch.size()
+for q in [0.001, 0.01, 0.1, 0.5, 0.9, 0.99, 0.999]:
+    assert rank_error(data, sketch.get_quantile(q), q) < 0.005, q
+assert sketch.get_quantile(0.0) == min(data) and sketch.get_quantile(1.0) == max(data)
+
+# per-symbol sketches merge into one summary of all streams
+parts = [data[i::4] for i in range(4)]
+sketches = [cum.SketchQuantiler(0.5, 100.0) for _ in parts]
+for s, part in zip(sketches, parts):
+    for x in part:
+        s.update(x)
+merged = sketches[0]
+for s in sketches[1:]:
+    merged.merge(s)
+assert merged.count() == len(data)
+for q in [0.01, 0.5, 0.99]:
+    assert rank_error(data, merged.get_quantile(q), q) < 0.01, q
+
+# the sketch survives pickle
+restored = pickle.loads(pickle.dumps(merged))
+for x in data[:1000]:
+    assert restored.update(x) == merged.update(x)
+print("ok")
@@ -0,0 +1,60 @@
+// snapshot indicators mid-stream with serde, restore them and check the outputs stay identical
+// cargo run --example state_roundtrip
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use ta::indicator::{momentum, overlap, volatility};
+use ta::{cum, rolling, Indicator};
+
+fn same(a: f64, b: f64) -> bool {
+    a == b || (a.is_nan() && b.is_nan())
+}
+
+fn check<I>(mut indicator: I, inputs: &[I::Input], eq: impl Fn(I::Output, I::Output) -> bool)
+where
+    I: Indicator + Serialize + DeserializeOwned,
+    I::Input: Copy,
+    I::Output: Copy + Debug,
+{
+    let (history, live) = inputs.split_at(inputs.len() / 2);
+    indicator.update_many(history.iter().copied());
+
+    let json = serde_json::to_string(&indicator).unwrap();
+    let bytes = bincode::serialize(&indicator).unwrap();
+    let mut from_json: I = serde_json::from_str(&json).unwrap();
+    let mut from_bytes: I = bincode::deserialize(&bytes).unwrap();
+
+    for &input in live {
+        let expected = indicator.update(input);
+        let (a, b) = (from_json.update(input), from_bytes.update(input));
+        assert!(eq(a, expected), "{:?} != {:?}", a, expected);
+        assert!(eq(b, expected), "{:?} != {:?}", b, expected);
+    }
+}
+
+fn main() {
+    let closes: Vec<f64> = (0..100).map(|i| 10.0 + (i as f64 * 0.7).sin()).collect();
+    let bars: Vec<(f64, f64, f64)> = closes.iter().map(|&c| (c + 0.5, c - 0.5, c)).collect();
+    let pairs: Vec<(f64, f64)> = closes.windows(2).map(|w| (w[1], w[0])).collect();
+
+    check(rolling::container::Container::new(10), &closes, |a, b| {
+        same(a.0, b.0) && same(a.1, b.1)
+    });
+    check(rolling::statis::Kurter::new(10), &closes, same);
+    check(rolling::quantile::Quantiler::new(10, 0.3), &closes, same);
+    check(rolling::quantile::Medianer::new(10), &closes, same);
+    check(rolling::quantile::PctRanker::new(10), &closes, same);
+    check(cum::minmax::Maxer::new(), &closes, same);
+    check(cum::delta::Pctchanger::new(), &closes, same);
+    check(
+        cum::quantile::SketchQuantiler::new(0.5, 20.0),
+        &closes,
+        same,
+    );
+    check(overlap::EMA::new(10), &closes, same);
+    check(overlap::KAMA::new(10, 2, 30), &pairs, same);
+    check(volatility::ATR::new(14), &bars, same);
+    check(momentum::MACD::new(12, 26, 9), &closes, |a, b| {
+        same(a.0, b.0) && same(a.1, b.1) && same(a.2, b.2)
+    });
+    println!("ok");
+}
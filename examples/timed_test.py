@@ -0,0 +1,129 @@
+import math
+import pickle
+import random
+import statistics
+
+from ta import timed
+
+random.seed(14)
+# irregular ticks in milliseconds, with bursts on the same timestamp and some NaN
+timestamps, t = [], 0
+for _ in range(2000):
+    t += random.choice([0, 0, 1, 5, 40, 300, 2500])
+    timestamps.append(t)
+values = [math.nan if random.random() < 0.02 else random.gauss(100, 3) for _ in timestamps]
+others = [v * 0.3 + random.gauss(0, 1) for v in values]
+duration = 1000
+
+
+def window(i, series):
+    # inputs in (t - duration, t]
+    return [v for t, v in zip(timestamps[: i + 1], series[: i + 1]) if t > timestamps[i] - duration]
+
+
+def quantile(values, q):
+    values = sorted(values)
+    index = (len(values) - 1) * q
+    lower = math.floor(index)
+    upper = min(lower + 1, len(values) - 1)
+    return values[lower] + (index - lower) * (values[upper] - values[lower])
+
+
+def corr(xs, ys):
+    n = len(xs)
+    mx, my = sum(xs) / n, sum(ys) / n
+    cov = sum((x - mx) * (y - my) for x, y in zip(xs, ys))
+    return cov / math.sqrt(sum((x - mx) ** 2 for x in xs) * sum((y - my) ** 2 for y in ys))
+
+
+def same(a, b, tol=1e-9):
+    if math.isnan(b):
+        return math.isnan(a)
+    return abs(a - b) <= tol * max(1.0, abs(b))
+
+
+# skip NaN and ask for 3 values, like pandas rolling("1000ms", min_periods=3) which always skips NaN
+expected = {
+    "Sumer": sum,
+    "Meaner": statistics.fmean,
+    "Stder": statistics.stdev,
+    "Maxer": max,
+    "Miner": min,
+}
+for name, f in expected.items():
+    indicator = getattr(timed, name)(duration)
+    indicator.set_nan_policy("skip")
+    indicator.set_min_periods(3)
+    for i, (t, v) in enumerate(zip(timestamps, values)):
+        got = indicator.update(t, v)
+        valid = [x for x in window(i, values) if not math.isnan(x)]
+        assert same(got, f(valid) if len(valid) >= 3 else math.nan), (name, i)
+
+median = timed.Quantiler(duration, 0.5)
+median.set_nan_policy("skip")
+for i, (t, v) in enumerate(zip(timestamps, values)):
+    got = median.update(t, v)
+    valid = [x for x in window(i, values) if not math.isnan(x)]
+    assert same(got, quantile(valid, 0.5) if valid else math.nan)
+    assert same(median.get_quantile(0.9), quantile(valid, 0.9) if valid else math.nan)
+
+# by default a NaN in the window makes the output NaN, like the count-based rolling types
+meaner = timed.Meaner(duration)
+for i, (t, v) in enumerate(zip(timestamps, values)):
+    got = meaner.update(t, v)
+    w = window(i, values)
+    assert same(got, math.nan if any(math.isnan(x) for x in w) else statistics.fmean(w))
+
+correlationer = timed.Correlationer(duration)
+for i, (t, x, y) in enumerate(zip(timestamps, values, others)):
+    got = correlationer.update(t, x, y)
+    xs, ys = window(i, values), window(i, others)
+    if any(math.isnan(v) for v in xs) or len(xs) < 2:
+        assert math.isnan(got) or len(xs) < 2
+    else:
+        assert same(got, corr(xs, ys), 1e-6), i
+
+# prices around 50000 with small moves: the co-moments stay exact and |corr| <= 1
+rng = random.Random(9)
+correlationer, t, level, pairs = timed.Correlationer(50), 0, 50000.0, []
+for i in range(3000):
+    t += rng.randint(1, 10)
+    level += rng.gauss(0, 0.01)
+    x = level + rng.gauss(0, 0.001)
+    pairs.append((t, x, 2 * x - 50000.0 if i % 500 < 250 else level + rng.gauss(0, 0.001)))
+    got = correlationer.update(*pairs[-1])
+    w = [(a, b) for s, a, b in pairs if s > t - 50]
+    want = corr([a for a, _ in w], [b for _, b in w]) if len(w) >= 2 else math.nan
+    assert -1.0 <= got <= 1.0 or math.isnan(got)
+    assert same(got, min(max(want, -1.0), 1.0), 1e-7), (i, got, want)
+
+# std and corr need two values, min_periods cannot go below that
+stder, correlationer = timed.Stder(100), timed.Correlationer(100)
+stder.set_min_periods(1)
+assert stder.lookback() == 1 and correlationer.lookback() == 1
+assert math.isnan(stder.update(0, 1.0)) and not stder.is_ready()
+assert math.isnan(correlationer.update(0, 1.0, 2.0)) and not correlationer.is_ready()
+assert stder.update(1, 2.0) > 0 and stder.is_ready()
+assert same(correlationer.update(1, 2.0, 3.0), 1.0) and correlationer.is_ready()
+
+# the time-decayed mean weighs every valid input by 0.5^(age / halflife)
+halflife = 700.0
+decay = timed.DecayMeaner(halflife)
+for i, (t, v) in enumerate(zip(timestamps, values)):
+    got = decay.update(t, v)
+    weights = [(0.5 ** ((t - s) / halflife), x) for s, x in zip(timestamps[: i + 1], values[: i + 1]) if not math.isnan(x)]
+    total = sum(w for w, _ in weights)
+    assert same(got, sum(w * x for w, x in weights) / total if weights else math.nan)
+
+# a gap longer than the window empties it down to the latest input
+sumer = timed.Sumer(10)
+assert [sumer.update(t, 1.0) for t in [0, 5, 9, 10, 30]] == [1.0, 2.0, 3.0, 3.0, 1.0]
+
+# the state survives pickle
+maxer = timed.Maxer(duration)
+for t, v in zip(timestamps[:500], values[:500]):
+    maxer.update(t, v)
+restored = pickle.loads(pickle.dumps(maxer))
+for t, v in zip(timestamps[500:], values[500:]):
+    assert same(restored.update(t, v), maxer.update(t, v))
+print("ok")
@@ -0,0 +1,72 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+random.seed(19)
+bars = []
+price = 100.0
+for _ in range(500):
+    open_ = price * math.exp(random.gauss(0, 0.005))
+    price = open_ * math.exp(random.gauss(0, 0.01))
+    high = max(open_, price) * math.exp(abs(random.gauss(0, 0.004)))
+    low = min(open_, price) * math.exp(-abs(random.gauss(0, 0.004)))
+    bars.append((open_, high, low, price))
+
+
+def close(a, b):
+    return abs(a - b) <= 1e-9 * max(1.0, abs(b))
+
+
+def mean(xs):
+    return sum(xs) / len(xs)
+
+
+def var(xs):
+    m = mean(xs)
+    return sum((x - m) ** 2 for x in xs) / (len(xs) - 1)
+
+
+def parkinson(window):
+    return mean([math.log(h / l) ** 2 for _, h, l, _ in window]) / (4 * math.log(2))
+
+
+def garman_klass(window):
+    return mean([0.5 * math.log(h / l) ** 2 - (2 * math.log(2) - 1) * math.log(c / o) ** 2 for o, h, l, c in window])
+
+
+def rogers_satchell(window):
+    return mean([math.log(h / c) * math.log(h / o) + math.log(l / c) * math.log(l / o) for o, h, l, c in window])
+
+
+n, year = 20, 252.0
+k = 0.34 / (1.34 + (n + 1) / (n - 1))
+estimators = [
+    (indicator.Parkinson(n, year), 0, lambda i: parkinson(bars[i - n + 1:i + 1])),
+    (indicator.GarmanKlass(n, year), 0, lambda i: garman_klass(bars[i - n + 1:i + 1])),
+    (indicator.RogersSatchell(n, year), 0, lambda i: rogers_satchell(bars[i - n + 1:i + 1])),
+    (indicator.CloseToClose(n, year), 1,
+     lambda i: var([math.log(bars[j][3] / bars[j - 1][3]) for j in range(i - n + 1, i + 1)])),
+    (indicator.YangZhang(n, year), 1,
+     lambda i: var([math.log(bars[j][0] / bars[j - 1][3]) for j in range(i - n + 1, i + 1)])
+     + k * var([math.log(c / o) for o, _, _, c in bars[i - n + 1:i + 1]])
+     + (1 - k) * rogers_satchell(bars[i - n + 1:i + 1])),
+]
+for obj, first, variance in estimators:
+    outputs = [obj.update(*bar) for bar in bars]
+    assert obj.lookback() == n - 1 + first
+    assert all(math.isnan(v) for v in outputs[:obj.lookback()])
+    assert all(close(outputs[i], math.sqrt(variance(i) * year)) for i in range(obj.lookback(), len(bars)))
+    # about 1% a bar, the annualized volatility is near 16%
+    assert 0.08 < outputs[-1] < 0.3, (type(obj).__name__, outputs[-1])
+
+    copy = pickle.loads(pickle.dumps(obj))
+    assert all(copy.update(*bar) == obj.update(*bar) for bar in bars[:50])
+
+# an annualization of 1 gives the volatility of one bar
+per_bar = indicator.Parkinson(n, 1.0)
+for bar in bars:
+    per_bar.update(*bar)
+assert close(per_bar.value(), math.sqrt(parkinson(bars[-n:])))
+print("ok")
@@ -0,0 +1,126 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+random.seed(17)
+bars = []
+price = 100.0
+for _ in range(400):
+    price += random.gauss(0, 1)
+    high, low = price + random.random(), price - random.random()
+    # fractional volumes, as traded on crypto venues
+    bars.append((high, low, price, random.uniform(0.01, 5.0)))
+
+
+def close(a, b):
+    return abs(a - b) <= 1e-9 * max(1.0, abs(b))
+
+
+def ema(values, period):
+    alpha, out, prev = 2.0 / (period + 1), [], None
+    for v in values:
+        if not math.isnan(v):
+            prev = v if prev is None else prev * (1 - alpha) + v * alpha
+        out.append(math.nan if prev is None else prev)
+    return out
+
+
+# the A/D family and OBV take float volume
+ad, obv = indicator.AD(10), indicator.OBV(10)
+for h, l, c, v in bars:
+    ad.update(h, l, c, v)
+    obv.update(c, v)
+window = bars[-10:]
+assert close(ad.value(), sum((2 * c - l - h) / (h - l) * v for h, l, c, v in window))
+signs = [(c > p[2]) - (c < p[2]) for (_, _, c, _), p in zip(bars[-10:], bars[-11:-1])]
+assert close(obv.value(), sum(s * b[3] for s, b in zip(signs, window)))
+
+# a flat bar (high == low) adds nothing instead of dividing by zero, ADOSC stays finite
+ad, adosc = indicator.AD(3), indicator.ADOSC(3, 2, 4)
+flat = [(10.0, 9.0, 9.5, 100.0), (10.0, 10.0, 10.0, 50.0), (11.0, 10.0, 10.8, 80.0)]
+outputs = [(ad.update(*b), adosc.update(*b)) for b in flat]
+assert close(outputs[-1][0], 0.6 * 80)
+assert all(math.isfinite(o) for o in outputs[-1])
+
+# CMF and VWMA over the last 20 bars
+cmf, vwma = indicator.CMF(20), indicator.VWMA(20)
+for i, (h, l, c, v) in enumerate(bars):
+    cmf_out, vwma_out = cmf.update(h, l, c, v), vwma.update(c, v)
+    if i < 19:
+        assert math.isnan(cmf_out) and math.isnan(vwma_out)
+        continue
+    window = bars[i - 19:i + 1]
+    volume = sum(b[3] for b in window)
+    assert close(cmf_out, sum((2 * c - l - h) / (h - l) * v for h, l, c, v in window) / volume)
+    assert close(vwma_out, sum(c * v for _, _, c, v in window) / volume)
+
+# Force Index is the EMA of the close change times the volume
+fi = indicator.FI(13)
+outputs = [fi.update(c, v) for _, _, c, v in bars]
+expected = ema([math.nan] + [(c - p[2]) * v for (_, _, c, v), p in zip(bars[1:], bars)], 13)
+assert math.isnan(outputs[0]) and all(close(o, e) for o, e in zip(outputs[1:], expected[1:]))
+
+# Ease of Movement is the SMA of the midpoint move over the box ratio
+emv = indicator.EMV(14, 1e4)
+outputs = [emv.update(h, l, v) for h, l, _, v in bars]
+raw = [((h + l) / 2 - (p[0] + p[1]) / 2) / ((v / 1e4) / (h - l)) for (h, l, _, v), p in zip(bars[1:], bars)]
+assert emv.lookback() == 14 and all(math.isnan(o) for o in outputs[:14])
+assert all(close(outputs[i], sum(raw[i - 14:i]) / 14) for i in range(14, len(bars)))
+
+# a bar without volume adds 0 to the average instead of inf or NaN
+emv = indicator.EMV(3, 1e4)
+outputs = [emv.update(h, l, v) for h, l, v in [(11, 9, 100), (12, 10, 0), (13, 10, 0), (12, 9, 100), (13, 11, 100)]]
+assert outputs[3] == (0.0 + 0.0 + (-1.0 / (100 / 1e4 / 3))) / 3
+assert math.isfinite(outputs[4])
+
+# PVT, NVI and PVI follow the close change, NVI on falling and PVI on rising volume
+pvt, nvi, pvi = indicator.PVT(), indicator.NVI(), indicator.PVI()
+total, neg, pos = 0.0, 1000.0, 1000.0
+for i, (_, _, c, v) in enumerate(bars):
+    if i > 0:
+        p, pv = bars[i - 1][2], bars[i - 1][3]
+        total += (c / p - 1) * v
+        neg *= c / p if v < pv else 1.0
+        pos *= c / p if v > pv else 1.0
+    assert close(pvt.update(c, v), total) and close(nvi.update(c, v), neg) and close(pvi.update(c, v), pos)
+
+# Klinger: volume force from the trend of high + low + close and the cumulative range
+kvo = indicator.KVO(34, 55, 13)
+outputs = [kvo.update(*bar) for bar in bars]
+forces, trend, cm = [math.nan], 0, 0.0
+for (h, l, c, v), (ph, pl, pc, _) in zip(bars[1:], bars):
+    t = 1 if h + l + c > ph + pl + pc else -1
+    cm = cm + (h - l) if t == trend else (ph - pl) + (h - l)
+    trend = t
+    forces.append(v * abs(2 * ((h - l) / cm - 1)) * t * 100)
+line = [f - s for f, s in zip(ema(forces, 34), ema(forces, 55))]
+signal = ema(line, 13)
+assert all(math.isnan(v) for v in outputs[0]) and kvo.lookback() == 1
+assert all(close(o[0], k) and close(o[1], s) for o, k, s in zip(outputs[1:], line[1:], signal[1:]))
+# a NaN bar is ignored, update returns the same as value()
+before = kvo.value()
+assert kvo.update(math.nan, 1.0, 1.0, 1.0) == before == kvo.value()
+before, count = pvt.value(), pvt.count()
+assert pvt.update(math.nan, 1.0) == before == pvt.value() and pvt.count() == count
+
+# Volume Oscillator is the percentage difference of the volume EMAs
+vosc = indicator.VOSC(5, 10)
+outputs = [vosc.update(v) for *_, v in bars]
+fast, slow = ema([b[3] for b in bars], 5), ema([b[3] for b in bars], 10)
+assert all(close(o, (f - s) / s * 100) for o, f, s in zip(outputs, fast, slow))
+
+# NaN bars are skipped by the cumulative indices, pickle continues the stream
+nvi = indicator.NVI()
+for _, _, c, v in bars[:200]:
+    nvi.update(c, v)
+before = nvi.value()
+assert nvi.update(math.nan, 1.0) == before == nvi.value()
+# cumulative types count the valid inputs only
+pvi = indicator.PVI()
+pvi.update(math.nan, 1.0)
+assert nvi.count() == 200 and pvi.count() == 0 and not pvi.is_ready()
+copy = pickle.loads(pickle.dumps(nvi))
+assert all(copy.update(c, v) == nvi.update(c, v) for _, _, c, v in bars[200:])
+print("ok")
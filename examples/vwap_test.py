@@ -0,0 +1,86 @@
+import math
+import pickle
+import random
+
+from ta import indicator
+
+random.seed(13)
+minute, day = 60_000, 86_400_000
+bars = []
+ts, price = 1_700_000_000_000, 100.0
+for _ in range(3000):
+    ts += minute
+    price += random.gauss(0, 0.1)
+    high, low = price + random.random() * 0.2, price - random.random() * 0.2
+    bars.append((ts, high, low, price, random.uniform(0.1, 10.0)))
+
+
+def vwap_std(window):
+    volume = sum(b[4] for b in window)
+    prices = [(b[1] + b[2] + b[3]) / 3.0 for b in window]
+    vwap = sum(p * b[4] for p, b in zip(prices, window)) / volume
+    var = sum((p - vwap) ** 2 * b[4] for p, b in zip(prices, window)) / volume
+    return vwap, math.sqrt(var)
+
+
+def close(a, b, tol=1e-7):
+    return abs(a - b) <= tol * max(1.0, abs(b))
+
+
+# session VWAP starts over on every day starting at 13:30 UTC
+offset = 13 * 3_600_000 + 30 * minute
+session, bands = indicator.SessionVWAP(day, offset), indicator.VWAPBands(day, offset, 2.0, 1.0)
+window, sessions = [], set()
+for bar in bars:
+    key = (bar[0] - offset) // day
+    if window and (window[-1][0] - offset) // day != key:
+        window = []
+    window.append(bar)
+    sessions.add(key)
+    vwap, std = vwap_std(window)
+    assert close(session.update(*bar), vwap) and close(session.std(), std, 1e-5)
+    lower, middle, upper = bands.update(*bar)
+    assert close(middle, vwap) and close(upper, vwap + 2.0 * session.std()) and close(lower, vwap - session.std())
+assert len(sessions) == 3
+
+# anchored VWAP is NaN before the anchor and covers every bar from it on, anchor_at starts over
+anchored = indicator.AnchoredVWAP(bars[100][0])
+outputs = [anchored.update(*bar) for bar in bars[:500]]
+assert all(math.isnan(v) for v in outputs[:100])
+assert close(outputs[-1], vwap_std(bars[100:500])[0])
+anchored.anchor_at(bars[700][0])
+outputs = [anchored.update(*bar) for bar in bars[500:1000]]
+assert all(math.isnan(v) for v in outputs[:200]) and close(outputs[-1], vwap_std(bars[700:1000])[0])
+
+# rolling VWAP over the last 30 bars
+rolling = indicator.RollingVWAP(30)
+outputs = [rolling.update(*bar) for bar in bars]
+assert rolling.lookback() == 29 and all(math.isnan(v) for v in outputs[:29])
+assert all(close(outputs[i], vwap_std(bars[i - 29:i + 1])[0]) for i in range(29, len(bars)))
+assert close(rolling.std(), vwap_std(bars[-30:])[1], 1e-5)
+
+# the anchored and rolling bands sit around their VWAPs like the session ones
+anchored, anchored_bands = indicator.AnchoredVWAP(bars[100][0]), indicator.AnchoredVWAPBands(bars[100][0], 2.0, 1.0)
+rolling, rolling_bands = indicator.RollingVWAP(30), indicator.RollingVWAPBands(30, 2.0, 1.0)
+for vwap, bands in [(anchored, anchored_bands), (rolling, rolling_bands)]:
+    for bar in bars:
+        middle = vwap.update(*bar)
+        lower, mid, upper = bands.update(*bar)
+        assert all(math.isnan(v) for v in (lower, mid, upper)) if math.isnan(middle) else (
+            mid == middle and close(upper, middle + 2.0 * vwap.std()) and close(lower, middle - vwap.std()))
+    assert bands.lookback() == vwap.lookback() and bands.count() == vwap.count()
+    copy = pickle.loads(pickle.dumps(bands))
+    assert copy.update(*bars[0]) == bands.update(*bars[0])
+anchored_bands.anchor_at(bars[-10][0])
+assert all(math.isnan(v) for v in anchored_bands.update(*bars[-11]))
+assert close(anchored_bands.update(*bars[-10])[1], (bars[-10][1] + bars[-10][2] + bars[-10][3]) / 3.0)
+
+# a NaN volume leaves the session VWAP unchanged, pickle continues the stream
+session = indicator.SessionVWAP(day, offset)
+for bar in bars[:1000]:
+    session.update(*bar)
+before = session.value()
+assert session.update(bars[1000][0], 100.0, 99.0, 99.5, math.nan) == before
+copy = pickle.loads(pickle.dumps(session))
+assert all(copy.update(*bar) == session.update(*bar) for bar in bars[1001:])
+print("ok")
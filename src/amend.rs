@@ -0,0 +1,153 @@
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+// Amend lets the last input be replaced while a bar is still forming.
+// update(x, false) feeds a forming bar, the following updates replace it until update(x, true) closes it.
+// Only an update with is_final=false copies the state before it, so a stream of final updates costs
+// the same as the bare type. replace_last after a final update needs keep_last, which copies the state
+// before every update, reusing the allocations of the previous copy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Amend<I> {
+    current: I,
+    prev: Option<I>,
+    // prev holds the state before the last input
+    saved: bool,
+    pending: bool,
+    keep_last: bool,
+}
+
+impl<I: Indicator + Clone> Amend<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            current: indicator,
+            prev: None,
+            saved: false,
+            pending: false,
+            keep_last: false,
+        }
+    }
+
+    // whether final updates keep the state before them, so replace_last works after any update
+    pub fn set_keep_last(&mut self, keep_last: bool) {
+        self.keep_last = keep_last;
+    }
+
+    pub fn update(&mut self, input: I::Input, is_final: bool) -> I::Output {
+        match &self.prev {
+            Some(prev) if self.pending && self.saved => self.current.clone_from(prev),
+            _ if !is_final || self.keep_last => self.save(),
+            _ => self.saved = false,
+        }
+        self.pending = !is_final;
+        self.current.update(input)
+    }
+
+    // recompute as if the last input were `input`, without advancing the window.
+    // None when the state before the last input was not kept, see keep_last
+    pub fn replace_last(&mut self, input: I::Input) -> Option<I::Output> {
+        match &self.prev {
+            Some(prev) if self.saved => self.current.clone_from(prev),
+            _ => return None,
+        }
+        Some(self.current.update(input))
+    }
+
+    fn save(&mut self) {
+        match &mut self.prev {
+            Some(prev) => prev.clone_from(&self.current),
+            None => self.prev = Some(self.current.clone()),
+        }
+        self.saved = true;
+    }
+
+    // whether the last input belongs to a bar that is not closed yet
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    // apply a change that is not an input, e.g. a setting or a merge, to the saved state too,
+    // so replace_last agrees with update
+    pub fn modify(&mut self, f: impl Fn(&mut I)) {
+        f(&mut self.current);
+        if let Some(prev) = self.prev.as_mut().filter(|_| self.saved) {
+            f(prev);
+        }
+    }
+
+    pub fn into_inner(self) -> I {
+        self.current
+    }
+}
+
+impl<I> Deref for Amend<I> {
+    type Target = I;
+
+    fn deref(&self) -> &I {
+        &self.current
+    }
+}
+
+impl<I: Indicator + Clone> Indicator for Amend<I> {
+    type Input = I::Input;
+    type Output = I::Output;
+
+    fn update(&mut self, input: I::Input) -> I::Output {
+        self.update(input, true)
+    }
+
+    // only the last input can be replaced later, so at most the state before it is copied
+    fn update_many<T>(&mut self, inputs: T) -> Vec<I::Output>
+    where
+        T: IntoIterator<Item = I::Input>,
+    {
+        let mut inputs = inputs.into_iter().peekable();
+        let mut outputs = Vec::new();
+        while let Some(input) = inputs.next() {
+            if self.pending || inputs.peek().is_none() {
+                outputs.push(self.update(input, true));
+            } else {
+                self.saved = false;
+                outputs.push(self.current.update(input));
+            }
+        }
+        outputs
+    }
+
+    // a forming bar is replaced by the next update, so peek from the state before it
+    fn peek(&self, input: I::Input) -> I::Output {
+        match (&self.prev, self.pending) {
+            (Some(prev), true) => prev.peek(input),
+            _ => self.current.peek(input),
+        }
+    }
+
+    fn value(&self) -> I::Output {
+        self.current.value()
+    }
+
+    fn reset(&mut self) {
+        self.current.reset();
+        self.prev = None;
+        self.saved = false;
+        self.pending = false;
+    }
+
+    fn lookback(&self) -> usize {
+        self.current.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.current.count()
+    }
+}
+
+impl<I: Indicator + Clone + NanHandling> NanHandling for Amend<I> {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.modify(|inner| inner.set_nan_policy(policy));
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.modify(|inner| inner.set_min_periods(min_periods));
+    }
+}
@@ -0,0 +1,105 @@
+use crate::utils::is_nan_or_inf;
+use serde::{Deserialize, Serialize};
+
+// Bar is an OHLCV bar, start/end are the interval of a time bar
+// and the timestamps of the first and last trade of the other bars
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bar {
+    pub start: i64,
+    pub end: i64,
+    #[serde(with = "crate::utils::float")]
+    pub open: f64,
+    #[serde(with = "crate::utils::float")]
+    pub high: f64,
+    #[serde(with = "crate::utils::float")]
+    pub low: f64,
+    #[serde(with = "crate::utils::float")]
+    pub close: f64,
+    #[serde(with = "crate::utils::float")]
+    pub volume: f64,
+    // traded value, sum of price * size
+    #[serde(with = "crate::utils::float")]
+    pub dollar: f64,
+    pub trades: usize,
+}
+
+impl Bar {
+    pub fn new(timestamp: i64, price: f64, size: f64) -> Self {
+        Self {
+            start: timestamp,
+            end: timestamp,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: size,
+            dollar: price * size,
+            trades: 1,
+        }
+    }
+
+    pub fn add(&mut self, timestamp: i64, price: f64, size: f64) {
+        self.end = self.end.max(timestamp);
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += size;
+        self.dollar += price * size;
+        self.trades += 1;
+    }
+
+    // folds in the next bar, e.g. to resample minute bars to hours
+    pub fn merge(&mut self, next: &Bar) {
+        self.end = self.end.max(next.end);
+        self.high = self.high.max(next.high);
+        self.low = self.low.min(next.low);
+        self.close = next.close;
+        self.volume += next.volume;
+        self.dollar += next.dollar;
+        self.trades += next.trades;
+    }
+
+    // volume weighted average price, NaN without volume
+    pub fn vwap(&self) -> f64 {
+        self.dollar / self.volume
+    }
+}
+
+// Forming is the bar being built, shared by the bar types
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Forming {
+    bar: Option<Bar>,
+}
+
+impl Forming {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // whether a trade is usable, NaN/inf prices and sizes are ignored
+    pub fn accepts(price: f64, size: f64) -> bool {
+        !is_nan_or_inf(price) && !is_nan_or_inf(size)
+    }
+
+    // adds the trade to the forming bar, or starts a bar with it
+    pub fn add(&mut self, timestamp: i64, price: f64, size: f64) -> &mut Bar {
+        let is_new = self.bar.is_none();
+        let bar = self.bar.get_or_insert(Bar::new(timestamp, price, size));
+        if !is_new {
+            bar.add(timestamp, price, size);
+        }
+        bar
+    }
+
+    pub fn get(&self) -> Option<Bar> {
+        self.bar
+    }
+
+    pub fn take(&mut self) -> Option<Bar> {
+        self.bar.take()
+    }
+
+    pub fn reset(&mut self) {
+        self.bar = None;
+    }
+}
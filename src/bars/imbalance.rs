@@ -0,0 +1,141 @@
+use super::bar::{Bar, Forming};
+use super::Measure;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// ImbalanceBarer builds the imbalance bars of Lopez de Prado: a trade is signed by the tick rule
+// (+1 on an uptick, -1 on a downtick, the previous sign when unchanged, 0 before the first move)
+// and a bar is completed once |sum of sign * measure| reaches expected trades per bar * |expected imbalance per trade|.
+// Both expectations are EWMAs over the completed bars with weight `alpha`,
+// the first bars are plain bars of `initial_ticks` trades that seed them, until one has a non-zero
+// imbalance: a zero expected imbalance would give a zero threshold and a bar per trade.
+// On balanced order flow the expected imbalance is small and so are the bars, as in the original definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImbalanceBarer {
+    measure: Measure,
+    initial_ticks: usize,
+    #[serde(with = "crate::utils::float")]
+    alpha: f64,
+    #[serde(with = "crate::utils::float")]
+    expected_ticks: f64,
+    #[serde(with = "crate::utils::float")]
+    expected_imbalance: f64,
+    #[serde(with = "crate::utils::float")]
+    imbalance: f64,
+    #[serde(with = "crate::utils::float")]
+    last_price: f64,
+    #[serde(with = "crate::utils::float")]
+    sign: f64,
+    forming: Forming,
+    last: Option<Bar>,
+    count: usize,
+}
+
+impl ImbalanceBarer {
+    pub fn new(measure: Measure, initial_ticks: usize, alpha: f64) -> Self {
+        Self {
+            measure,
+            initial_ticks: initial_ticks.max(1),
+            alpha,
+            expected_ticks: initial_ticks.max(1) as f64,
+            expected_imbalance: f64::NAN,
+            imbalance: 0.0,
+            last_price: f64::NAN,
+            sign: 0.0,
+            forming: Forming::new(),
+            last: None,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, price: f64, size: f64) -> Option<Bar> {
+        self.count += 1;
+        self.last = None;
+        if !Forming::accepts(price, size) {
+            return None;
+        }
+
+        if price > self.last_price {
+            self.sign = 1.0;
+        } else if price < self.last_price {
+            self.sign = -1.0;
+        }
+        self.last_price = price;
+        self.imbalance += self.sign * self.measure.of(price, size);
+
+        let trades = self.forming.add(timestamp, price, size).trades as f64;
+        let is_complete = if self.expected_imbalance.is_nan() {
+            trades >= self.expected_ticks
+        } else {
+            self.imbalance.abs() >= self.threshold()
+        };
+        if is_complete {
+            let per_trade = self.imbalance / trades;
+            if self.expected_imbalance.is_nan() {
+                if per_trade != 0.0 {
+                    self.expected_imbalance = per_trade;
+                }
+            } else {
+                self.expected_imbalance =
+                    self.alpha * per_trade + (1.0 - self.alpha) * self.expected_imbalance;
+            }
+            self.expected_ticks = self.alpha * trades + (1.0 - self.alpha) * self.expected_ticks;
+            self.imbalance = 0.0;
+            self.last = self.forming.take();
+        }
+        self.last
+    }
+
+    // the |imbalance| that completes the forming bar, NaN while seeding
+    pub fn threshold(&self) -> f64 {
+        self.expected_ticks * self.expected_imbalance.abs()
+    }
+
+    // signed imbalance of the forming bar
+    pub fn imbalance(&self) -> f64 {
+        self.imbalance
+    }
+
+    // the bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.forming.get()
+    }
+
+    // completes the forming bar now, e.g. at the end of a session, the expectations are left as they are
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.imbalance = 0.0;
+        self.forming.take()
+    }
+}
+
+impl Indicator for ImbalanceBarer {
+    type Input = (i64, f64, f64);
+    type Output = Option<Bar>;
+
+    fn update(&mut self, (timestamp, price, size): (i64, f64, f64)) -> Option<Bar> {
+        self.update(timestamp, price, size)
+    }
+
+    fn value(&self) -> Option<Bar> {
+        self.last
+    }
+
+    fn reset(&mut self) {
+        self.expected_ticks = self.initial_ticks as f64;
+        self.expected_imbalance = f64::NAN;
+        self.imbalance = 0.0;
+        self.last_price = f64::NAN;
+        self.sign = 0.0;
+        self.forming.reset();
+        self.last = None;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
@@ -0,0 +1,32 @@
+// Bar aggregation: trades (timestamp, price, size) in, OHLCV bars out.
+// update returns the bar the trade completed, if any, and current() is the bar still forming,
+// so the fields can go straight into the indicators, e.g. atr.update(bar.high, bar.low, bar.close).
+// Trades with a NaN/inf price or size are ignored.
+pub mod bar;
+pub mod imbalance;
+pub mod resample;
+pub mod threshold;
+pub mod time;
+
+pub use bar::Bar;
+
+// Measure is what a threshold or an imbalance counts per trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Measure {
+    // 1 per trade
+    Tick,
+    // the size
+    Volume,
+    // price * size
+    Dollar,
+}
+
+impl Measure {
+    pub fn of(self, price: f64, size: f64) -> f64 {
+        match self {
+            Measure::Tick => 1.0,
+            Measure::Volume => size,
+            Measure::Dollar => price * size,
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use super::bar::Bar;
+use crate::indicator::{channel, cycle, momentum, overlap, price, volatility, volume};
+use crate::pattern::{double, hikkake, multi, single, triple};
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// BarInput builds the input of an indicator from a bar and the close of the bar before it,
+// the same way the batch functions build it from OHLCV arrays
+pub trait BarInput: Indicator {
+    fn bar_input(bar: &Bar, preclose: f64) -> Self::Input;
+}
+
+macro_rules! bar_input {
+    ($($ty:ty),+ => |$bar:ident, $preclose:ident| $input:expr) => {
+        $(
+            impl BarInput for $ty {
+                #[allow(unused_variables)]
+                fn bar_input($bar: &Bar, $preclose: f64) -> Self::Input {
+                    $input
+                }
+            }
+        )+
+    };
+}
+
+bar_input!(
+    momentum::APO, momentum::CMO, momentum::KST, momentum::MACD, momentum::MACDEXT, momentum::MOM,
+    momentum::PPO, momentum::PPOHist, momentum::ROC, momentum::ROCR, momentum::RSI,
+    momentum::StochRSI, momentum::TRIX, overlap::BBands, overlap::DEMA, overlap::EMA,
+    overlap::HTTrendline, overlap::MA, overlap::MAMA, overlap::MIDPOINT, overlap::SMA, overlap::T3,
+    overlap::TEMA, overlap::TRIMA, overlap::WMA, cycle::HTDCPeriod, cycle::HTDCPhase,
+    cycle::HTPhasor, cycle::HTSine, cycle::HTTrendMode
+    => |bar, preclose| bar.close
+);
+bar_input!(
+    momentum::AO, momentum::Aroon, momentum::AroonOsc, momentum::MinusDM, momentum::PlusDM,
+    overlap::MIDPRICE, overlap::SAR, overlap::SAREXT, price::MedPrice, channel::Donchian
+    => |bar, preclose| (bar.high, bar.low)
+);
+bar_input!(
+    momentum::CCI, momentum::DX, momentum::PlusDI, momentum::MinusDI, momentum::Stoch,
+    momentum::StochCHF, momentum::WILLR, price::TypicalPrice, price::WeightedClose,
+    volatility::TR, volatility::ATR, volatility::NATR, channel::Keltner, channel::Ichimoku,
+    channel::Supertrend, channel::Squeeze
+    => |bar, preclose| (bar.high, bar.low, bar.close)
+);
+bar_input!(momentum::ADX, momentum::ADXR => |bar, preclose| (bar.high, bar.low, preclose));
+bar_input!(momentum::ULTOSC => |bar, preclose| (bar.high, bar.low, bar.close, preclose));
+bar_input!(momentum::KDJ => |bar, preclose| (bar.close, bar.high, bar.low));
+bar_input!(
+    momentum::BOP, price::AvgPrice, volatility::Parkinson, volatility::GarmanKlass,
+    volatility::RogersSatchell, volatility::YangZhang, volatility::CloseToClose
+    => |bar, preclose| (bar.open, bar.high, bar.low, bar.close)
+);
+bar_input!(momentum::MFI => |bar, preclose| (bar.high, bar.low, bar.close, bar.volume));
+bar_input!(overlap::KAMA => |bar, preclose| (bar.close, preclose));
+bar_input!(
+    volume::AD, volume::ADOSC, volume::CMF, volume::KVO
+    => |bar, preclose| (bar.high, bar.low, bar.close, bar.volume)
+);
+bar_input!(
+    volume::FI, volume::NVI, volume::OBV, volume::PVI, volume::PVT, volume::VWMA
+    => |bar, preclose| (bar.close, bar.volume)
+);
+bar_input!(volume::EMV => |bar, preclose| (bar.high, bar.low, bar.volume));
+bar_input!(volume::VOSC => |bar, preclose| bar.volume);
+bar_input!(
+    volume::SessionVWAP, volume::AnchoredVWAP, volume::RollingVWAP, volume::VWAPBands,
+    volume::AnchoredVWAPBands, volume::RollingVWAPBands
+    => |bar, preclose| (bar.start, bar.high, bar.low, bar.close, bar.volume)
+);
+bar_input!(
+    single::BeltHold, single::ClosingMarubozu, single::Doji, single::DragonflyDoji,
+    single::GravestoneDoji, single::HighWave, single::LongLeggedDoji, single::LongLine,
+    single::Marubozu, single::RickshawMan, single::ShortLine, single::SpinningTop, single::Takuri,
+    double::Counterattack, double::DarkCloudCover, double::DojiStar, double::Engulfing,
+    double::Hammer, double::HangingMan, double::Harami, double::HaramiCross, double::HomingPigeon,
+    double::InNeck, double::InvertedHammer, double::Kicking, double::KickingByLength,
+    double::MatchingLow, double::OnNeck, double::Piercing, double::SeparatingLines,
+    double::ShootingStar, double::Thrusting, triple::TwoCrows, triple::ThreeInside,
+    triple::ThreeOutside, triple::ThreeStarsInSouth, triple::ThreeWhiteSoldiers,
+    triple::AbandonedBaby, triple::AdvanceBlock, triple::EveningDojiStar, triple::EveningStar,
+    triple::GapSideSideWhite, triple::IdenticalThreeCrows, triple::MorningDojiStar,
+    triple::MorningStar, triple::StalledPattern, triple::StickSandwich, triple::TasukiGap,
+    triple::Tristar, triple::UniqueThreeRiver, triple::UpsideGapTwoCrows,
+    triple::XSideGapThreeMethods, multi::ThreeBlackCrows, multi::ThreeLineStrike, multi::Breakaway,
+    multi::ConcealBabySwallow, multi::LadderBottom, multi::MatHold, multi::RiseFallThreeMethods,
+    hikkake::Hikkake, hikkake::HikkakeMod
+    => |bar, preclose| (bar.open, bar.high, bar.low, bar.close)
+);
+
+// Resample aggregates OHLCV bars into bars of a higher timeframe aligned to multiples of `timeframe`,
+// e.g. minute bars into hours with 3_600_000 for millisecond timestamps.
+// The timestamps are the open times of the input bars: the bar stamped 10:00 goes into the 10:00 hour.
+// Bars stamped at their close, like the 10:00 bar of 09:59-10:00, belong to the hour before and need
+// their own length subtracted first, otherwise every hour is one bar off.
+// A bar is completed by the first input of a later period, or by advance() from a clock.
+// dollar is NaN and trades counts the merged bars, as the inputs carry neither.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resample {
+    timeframe: i64,
+    forming: Option<Bar>,
+    #[serde(with = "crate::utils::float")]
+    preclose: f64,
+}
+
+impl Resample {
+    pub fn new(timeframe: i64) -> Self {
+        Self {
+            timeframe: timeframe.max(1),
+            forming: None,
+            preclose: f64::NAN,
+        }
+    }
+
+    // returns the bar completed by this input with the close before it
+    pub fn update(
+        &mut self,
+        timestamp: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Option<(Bar, f64)> {
+        let completed = self.advance(timestamp);
+        let bar = Bar {
+            start: timestamp,
+            end: timestamp,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            dollar: f64::NAN,
+            trades: 1,
+        };
+        match &mut self.forming {
+            Some(forming) => forming.merge(&bar),
+            None => {
+                let start = timestamp.div_euclid(self.timeframe) * self.timeframe;
+                self.forming = Some(Bar { start, ..bar });
+            }
+        }
+        if let Some(forming) = &mut self.forming {
+            forming.end = forming.start.saturating_add(self.timeframe);
+        }
+        completed
+    }
+
+    // completes the forming bar once `timestamp` is past its period
+    pub fn advance(&mut self, timestamp: i64) -> Option<(Bar, f64)> {
+        let bar = self.forming.filter(|bar| timestamp >= bar.end)?;
+        self.forming = None;
+        let preclose = self.preclose;
+        self.preclose = bar.close;
+        Some((bar, preclose))
+    }
+
+    // the bar still forming with the close before it
+    pub fn current(&self) -> Option<(Bar, f64)> {
+        self.forming.map(|bar| (bar, self.preclose))
+    }
+
+    pub fn timeframe(&self) -> i64 {
+        self.timeframe
+    }
+
+    pub fn reset(&mut self) {
+        self.forming = None;
+        self.preclose = f64::NAN;
+    }
+}
+
+// Resampler runs an indicator on a higher timeframe while it is fed lower timeframe bars,
+// e.g. an hourly ATR from minute bars. Every update returns (closed, provisional):
+// the output as of the last completed bar, and the output if the forming bar closed now.
+// lookback and count are in bars of the higher timeframe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resampler<I> {
+    resample: Resample,
+    indicator: I,
+}
+
+impl<I: BarInput + Clone> Resampler<I> {
+    pub fn new(indicator: I, timeframe: i64) -> Self {
+        Self {
+            resample: Resample::new(timeframe),
+            indicator,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        timestamp: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> (I::Output, I::Output) {
+        if let Some((bar, preclose)) = self
+            .resample
+            .update(timestamp, open, high, low, close, volume)
+        {
+            self.indicator.update(I::bar_input(&bar, preclose));
+        }
+
+        self.value()
+    }
+
+    // completes the forming bar once `timestamp` is past its period, e.g. at the end of a session
+    pub fn advance(&mut self, timestamp: i64) -> (I::Output, I::Output) {
+        if let Some((bar, preclose)) = self.resample.advance(timestamp) {
+            self.indicator.update(I::bar_input(&bar, preclose));
+        }
+
+        self.value()
+    }
+
+    // the higher timeframe bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.resample.current().map(|(bar, _)| bar)
+    }
+
+    // the indicator as of the last completed bar
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I: BarInput + Clone> Indicator for Resampler<I> {
+    type Input = (i64, f64, f64, f64, f64, f64);
+    type Output = (I::Output, I::Output);
+
+    fn update(
+        &mut self,
+        (timestamp, open, high, low, close, volume): (i64, f64, f64, f64, f64, f64),
+    ) -> (I::Output, I::Output) {
+        self.update(timestamp, open, high, low, close, volume)
+    }
+
+    fn value(&self) -> (I::Output, I::Output) {
+        let closed = self.indicator.value();
+        let provisional = match self.resample.current() {
+            Some((bar, preclose)) => self.indicator.peek(I::bar_input(&bar, preclose)),
+            None => closed.clone(),
+        };
+        (closed, provisional)
+    }
+
+    fn reset(&mut self) {
+        self.resample.reset();
+        self.indicator.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.indicator.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.indicator.count()
+    }
+}
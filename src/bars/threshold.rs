@@ -0,0 +1,218 @@
+use super::bar::{Bar, Forming};
+use super::Measure;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// Threshold completes a bar once the trades add up to `threshold` of a measure,
+// the trade crossing it is the last one of the bar, trades are not split
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Threshold {
+    measure: Measure,
+    #[serde(with = "crate::utils::float")]
+    threshold: f64,
+    #[serde(with = "crate::utils::float")]
+    total: f64,
+    forming: Forming,
+    last: Option<Bar>,
+    count: usize,
+}
+
+impl Threshold {
+    fn new(measure: Measure, threshold: f64) -> Self {
+        Self {
+            measure,
+            threshold,
+            total: 0.0,
+            forming: Forming::new(),
+            last: None,
+            count: 0,
+        }
+    }
+
+    fn update(&mut self, timestamp: i64, price: f64, size: f64) -> Option<Bar> {
+        self.count += 1;
+        self.last = None;
+        if !Forming::accepts(price, size) {
+            return None;
+        }
+
+        self.forming.add(timestamp, price, size);
+        self.total += self.measure.of(price, size);
+        if self.total >= self.threshold {
+            self.total = 0.0;
+            self.last = self.forming.take();
+        }
+        self.last
+    }
+
+    fn flush(&mut self) -> Option<Bar> {
+        self.total = 0.0;
+        self.forming.take()
+    }
+
+    fn reset(&mut self) {
+        self.total = 0.0;
+        self.forming.reset();
+        self.last = None;
+        self.count = 0;
+    }
+}
+
+// TickBarer completes a bar every `ticks` trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TickBarer {
+    threshold: Threshold,
+}
+
+impl TickBarer {
+    pub fn new(ticks: usize) -> Self {
+        Self {
+            threshold: Threshold::new(Measure::Tick, ticks.max(1) as f64),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, price: f64, size: f64) -> Option<Bar> {
+        self.threshold.update(timestamp, price, size)
+    }
+
+    // the bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.threshold.forming.get()
+    }
+
+    // completes the forming bar now, e.g. at the end of a session
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.threshold.flush()
+    }
+}
+
+impl Indicator for TickBarer {
+    type Input = (i64, f64, f64);
+    type Output = Option<Bar>;
+
+    fn update(&mut self, (timestamp, price, size): (i64, f64, f64)) -> Option<Bar> {
+        self.update(timestamp, price, size)
+    }
+
+    fn value(&self) -> Option<Bar> {
+        self.threshold.last
+    }
+
+    fn reset(&mut self) {
+        self.threshold.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.threshold.count
+    }
+}
+
+// VolumeBarer completes a bar once the traded size reaches `volume`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeBarer {
+    threshold: Threshold,
+}
+
+impl VolumeBarer {
+    pub fn new(volume: f64) -> Self {
+        Self {
+            threshold: Threshold::new(Measure::Volume, volume),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, price: f64, size: f64) -> Option<Bar> {
+        self.threshold.update(timestamp, price, size)
+    }
+
+    // the bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.threshold.forming.get()
+    }
+
+    // completes the forming bar now, e.g. at the end of a session
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.threshold.flush()
+    }
+}
+
+impl Indicator for VolumeBarer {
+    type Input = (i64, f64, f64);
+    type Output = Option<Bar>;
+
+    fn update(&mut self, (timestamp, price, size): (i64, f64, f64)) -> Option<Bar> {
+        self.update(timestamp, price, size)
+    }
+
+    fn value(&self) -> Option<Bar> {
+        self.threshold.last
+    }
+
+    fn reset(&mut self) {
+        self.threshold.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.threshold.count
+    }
+}
+
+// DollarBarer completes a bar once the traded value, price * size, reaches `dollars`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DollarBarer {
+    threshold: Threshold,
+}
+
+impl DollarBarer {
+    pub fn new(dollars: f64) -> Self {
+        Self {
+            threshold: Threshold::new(Measure::Dollar, dollars),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, price: f64, size: f64) -> Option<Bar> {
+        self.threshold.update(timestamp, price, size)
+    }
+
+    // the bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.threshold.forming.get()
+    }
+
+    // completes the forming bar now, e.g. at the end of a session
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.threshold.flush()
+    }
+}
+
+impl Indicator for DollarBarer {
+    type Input = (i64, f64, f64);
+    type Output = Option<Bar>;
+
+    fn update(&mut self, (timestamp, price, size): (i64, f64, f64)) -> Option<Bar> {
+        self.update(timestamp, price, size)
+    }
+
+    fn value(&self) -> Option<Bar> {
+        self.threshold.last
+    }
+
+    fn reset(&mut self) {
+        self.threshold.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.threshold.count
+    }
+}
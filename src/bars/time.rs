@@ -0,0 +1,89 @@
+use super::bar::{Bar, Forming};
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// TimeBarer builds bars over fixed intervals aligned to multiples of `interval`, e.g. 60_000 for
+// minute bars of millisecond timestamps. A bar is completed by the first trade of a later interval,
+// or by advance() from a clock, and intervals without trades give no bar.
+// A trade older than the forming bar is added to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeBarer {
+    interval: i64,
+    forming: Forming,
+    last: Option<Bar>,
+    count: usize,
+}
+
+impl TimeBarer {
+    pub fn new(interval: i64) -> Self {
+        Self {
+            interval: interval.max(1),
+            forming: Forming::new(),
+            last: None,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, price: f64, size: f64) -> Option<Bar> {
+        self.count += 1;
+        self.last = None;
+        if !Forming::accepts(price, size) {
+            return None;
+        }
+
+        self.last = self.advance(timestamp);
+        let is_new = self.forming.get().is_none();
+        let bar = self.forming.add(timestamp, price, size);
+        if is_new {
+            bar.start = timestamp.div_euclid(self.interval) * self.interval;
+        }
+        bar.end = bar.start.saturating_add(self.interval);
+
+        self.last
+    }
+
+    // completes the forming bar once `timestamp` is past its interval
+    pub fn advance(&mut self, timestamp: i64) -> Option<Bar> {
+        match self.forming.get() {
+            Some(bar) if timestamp >= bar.end => self.forming.take(),
+            _ => None,
+        }
+    }
+
+    // the bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.forming.get()
+    }
+
+    // completes the forming bar now, e.g. at the end of a session
+    pub fn flush(&mut self) -> Option<Bar> {
+        self.forming.take()
+    }
+}
+
+impl Indicator for TimeBarer {
+    type Input = (i64, f64, f64);
+    type Output = Option<Bar>;
+
+    fn update(&mut self, (timestamp, price, size): (i64, f64, f64)) -> Option<Bar> {
+        self.update(timestamp, price, size)
+    }
+
+    fn value(&self) -> Option<Bar> {
+        self.last
+    }
+
+    fn reset(&mut self) {
+        self.forming.reset();
+        self.last = None;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
@@ -0,0 +1,24 @@
+use crate::Indicator;
+
+// run feeds a whole history through a freshly built indicator in one tight loop.
+// the outputs are exactly the streaming outputs, so the NaN warmup matches the update() calls.
+pub fn run<I, T>(mut indicator: I, inputs: T) -> Vec<I::Output>
+where
+    I: Indicator,
+    T: IntoIterator<Item = I::Input>,
+{
+    inputs
+        .into_iter()
+        .map(|input| indicator.update(input))
+        .collect()
+}
+
+// previous element of a series, NaN for the first one
+// e.g. ADX, ULTOSC and KAMA take the previous close as an input
+pub fn prev(values: &[f64], idx: usize) -> f64 {
+    if idx == 0 {
+        f64::NAN
+    } else {
+        values[idx - 1]
+    }
+}
@@ -1,20 +1,19 @@
 use super::statis::Meaner;
 use crate::utils::is_nan_or_inf;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Correlationer {
     x_meaner: Meaner,
     x_sq_meaner: Meaner,
     y_meaner: Meaner,
     y_sq_meaner: Meaner,
     xy_meaner: Meaner,
+    count: usize,
 }
 
-#[pymethods]
 impl Correlationer {
-    #[new]
     pub fn new() -> Self {
         Self {
             x_meaner: Meaner::new(),
@@ -22,23 +21,67 @@ impl Correlationer {
             y_meaner: Meaner::new(),
             y_sq_meaner: Meaner::new(),
             xy_meaner: Meaner::new(),
+            count: 0,
         }
     }
 
     pub fn update(&mut self, x: f64, y: f64) -> f64 {
         if is_nan_or_inf(x) || is_nan_or_inf(y) {
-            NAN
+            f64::NAN
         } else {
-            let mean_x = self.x_meaner.update(x);
-            let mean_x_sq = self.x_sq_meaner.update(x * x);
-            let mean_y = self.y_meaner.update(y);
-            let mean_y_sq = self.y_sq_meaner.update(y * y);
-            let mean_xy = self.xy_meaner.update(x * y);
+            self.count += 1;
+            self.x_meaner.update(x);
+            self.x_sq_meaner.update(x * x);
+            self.y_meaner.update(y);
+            self.y_sq_meaner.update(y * y);
+            self.xy_meaner.update(x * y);
 
-            let sigmax_sq = (mean_x_sq - mean_x.powi(2)).sqrt();
-            let sigmay_sq = (mean_y_sq - mean_y.powi(2)).sqrt();
-
-            (mean_xy - mean_x * mean_y) / (sigmax_sq * sigmay_sq)
+            self.value()
         }
     }
 }
+
+impl Default for Correlationer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Correlationer {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (x, y): (f64, f64)) -> f64 {
+        self.update(x, y)
+    }
+
+    fn value(&self) -> f64 {
+        let mean_x = self.x_meaner.value();
+        let mean_x_sq = self.x_sq_meaner.value();
+        let mean_y = self.y_meaner.value();
+        let mean_y_sq = self.y_sq_meaner.value();
+        let mean_xy = self.xy_meaner.value();
+
+        let sigmax_sq = (mean_x_sq - mean_x.powi(2)).sqrt();
+        let sigmay_sq = (mean_y_sq - mean_y.powi(2)).sqrt();
+
+        (mean_xy - mean_x * mean_y) / (sigmax_sq * sigmay_sq)
+    }
+
+    fn reset(&mut self) {
+        self.x_meaner.reset();
+        self.x_sq_meaner.reset();
+        self.y_meaner.reset();
+        self.y_sq_meaner.reset();
+        self.xy_meaner.reset();
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
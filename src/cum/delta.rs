@@ -1,49 +1,99 @@
 use crate::utils::is_nan_or_inf;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deltaer {
+    #[serde(with = "crate::utils::float")]
     first_value: Option<f64>,
+    #[serde(with = "crate::utils::float")]
+    last_value: f64,
+    count: usize,
 }
 
-#[pymethods]
 impl Deltaer {
-    #[new]
     pub fn new() -> Self {
-        Self { first_value: None }
+        Self {
+            first_value: None,
+            last_value: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, x: f64) -> f64 {
         if is_nan_or_inf(x) {
-            NAN
+            f64::NAN
         } else {
-            if self.first_value.is_none() {
+            self.last_value = x;
+            self.count += 1;
+            if let Some(first) = self.first_value {
+                x - first
+            } else {
                 self.first_value = Some(x);
                 0.0 // Delta is zero at the first value
-            } else {
-                x - self.first_value.unwrap()
             }
         }
     }
 }
 
-#[pyclass]
+impl Default for Deltaer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Deltaer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.first_value
+            .map_or(f64::NAN, |first| self.last_value - first)
+    }
+
+    fn reset(&mut self) {
+        self.first_value = None;
+        self.last_value = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pctchanger {
+    #[serde(with = "crate::utils::float")]
     first_value: Option<f64>,
+    #[serde(with = "crate::utils::float")]
+    last_value: f64,
+    count: usize,
 }
 
-#[pymethods]
 impl Pctchanger {
-    #[new]
     pub fn new() -> Self {
-        Self { first_value: None }
+        Self {
+            first_value: None,
+            last_value: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, x: f64) -> f64 {
         if is_nan_or_inf(x) {
-            NAN
+            f64::NAN
         } else {
+            self.last_value = x;
+            self.count += 1;
             if let Some(first) = self.first_value {
                 (x - first) / first
             } else {
@@ -53,3 +103,37 @@ impl Pctchanger {
         }
     }
 }
+
+impl Default for Pctchanger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Pctchanger {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.first_value
+            .map_or(f64::NAN, |first| (self.last_value - first) / first)
+    }
+
+    fn reset(&mut self) {
+        self.first_value = None;
+        self.last_value = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
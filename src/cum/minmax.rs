@@ -1,42 +1,159 @@
-use pyo3::prelude::*;
-use std::f64::{INFINITY, NEG_INFINITY};
+use crate::nan::NanGuard;
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Maxer {
+    #[serde(with = "crate::utils::float")]
     max: f64,
+    count: usize,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Maxer {
-    #[new]
     pub fn new() -> Self {
-        Self { max: NEG_INFINITY }
+        Self {
+            max: f64::NEG_INFINITY,
+            count: 0,
+            guard: NanGuard::new(NanPolicy::Skip, 1),
+        }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        if new_val > self.max {
-            self.max = new_val;
+        let new_val = self.guard.fill(new_val);
+        if new_val.is_nan() {
+            self.guard.skip();
+        } else {
+            self.count += 1;
+            if new_val > self.max {
+                self.max = new_val;
+            }
+        }
+        self.value()
+    }
+}
+
+impl Default for Maxer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Maxer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.guard.accepts_all(self.count) {
+            self.max
+        } else {
+            f64::NAN
         }
-        self.max
+    }
+
+    fn reset(&mut self) {
+        self.max = f64::NEG_INFINITY;
+        self.count = 0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Miner {
+    #[serde(with = "crate::utils::float")]
     min: f64,
+    count: usize,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Miner {
-    #[new]
     pub fn new() -> Self {
-        Self { min: INFINITY }
+        Self {
+            min: f64::INFINITY,
+            count: 0,
+            guard: NanGuard::new(NanPolicy::Skip, 1),
+        }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        if new_val < self.min {
-            self.min = new_val;
+        let new_val = self.guard.fill(new_val);
+        if new_val.is_nan() {
+            self.guard.skip();
+        } else {
+            self.count += 1;
+            if new_val < self.min {
+                self.min = new_val;
+            }
+        }
+        self.value()
+    }
+}
+
+impl Default for Miner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Miner {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.guard.accepts_all(self.count) {
+            self.min
+        } else {
+            f64::NAN
         }
-        self.min
+    }
+
+    fn reset(&mut self) {
+        self.min = f64::INFINITY;
+        self.count = 0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl NanHandling for Maxer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+impl NanHandling for Miner {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
     }
 }
@@ -1,39 +1,45 @@
+use super::tdigest::TDigest;
+use crate::nan::NanGuard;
 use crate::utils::is_nan_or_inf;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Quantiler {
+    #[serde(with = "crate::utils::float")]
     dataset: Vec<f64>,
+    #[serde(with = "crate::utils::float")]
     quantile: f64,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Quantiler {
-    #[new]
     pub fn new(quantile: f64) -> Self {
         Self {
             dataset: Vec::new(),
             quantile,
+            guard: NanGuard::new(NanPolicy::Skip, 1),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         if !is_nan_or_inf(new_val) {
             let pos = self
                 .dataset
                 .binary_search_by(|v| v.partial_cmp(&new_val).unwrap())
                 .unwrap_or_else(|e| e);
             self.dataset.insert(pos, new_val);
-            self.get_quantile()
+            self.value()
         } else {
-            NAN
+            self.guard.skip();
+            f64::NAN
         }
     }
 
     pub fn get_quantile(&self) -> f64 {
         if self.dataset.is_empty() {
-            NAN
+            f64::NAN
         } else {
             let index = (self.dataset.len() - 1) as f64 * self.quantile;
             let lower_index = index.floor() as usize;
@@ -50,3 +56,129 @@ impl Quantiler {
         }
     }
 }
+
+impl Indicator for Quantiler {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.guard.accepts_all(self.dataset.len()) {
+            self.get_quantile()
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.dataset.clear();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+impl NanHandling for Quantiler {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+// SketchQuantiler is the bounded-memory counterpart of Quantiler, backed by a TDigest.
+// Sketches of different streams, e.g. one per symbol, can be merged into one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SketchQuantiler {
+    digest: TDigest,
+    #[serde(with = "crate::utils::float")]
+    quantile: f64,
+    guard: NanGuard,
+}
+
+impl SketchQuantiler {
+    // compression 100 keeps under 200 values with a rank error well under 1%
+    pub fn new(quantile: f64, compression: f64) -> Self {
+        Self {
+            digest: TDigest::new(compression),
+            quantile,
+            guard: NanGuard::new(NanPolicy::Skip, 1),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
+        if !is_nan_or_inf(new_val) {
+            self.digest.add(new_val);
+            self.value()
+        } else {
+            self.guard.skip();
+            f64::NAN
+        }
+    }
+
+    // any other quantile of the same stream
+    pub fn get_quantile(&self, quantile: f64) -> f64 {
+        self.digest.quantile(quantile)
+    }
+
+    pub fn merge(&mut self, other: &SketchQuantiler) {
+        self.digest.merge(&other.digest);
+    }
+
+    pub fn digest(&self) -> &TDigest {
+        &self.digest
+    }
+}
+
+impl Indicator for SketchQuantiler {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.guard.accepts_all(self.count()) {
+            self.digest.quantile(self.quantile)
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.digest.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    // merged sketches count the values of both streams
+    fn count(&self) -> usize {
+        self.digest.weight() as usize
+    }
+}
+
+impl NanHandling for SketchQuantiler {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
@@ -1,22 +1,22 @@
+use crate::nan::NanGuard;
 use crate::utils::is_nan_or_inf;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Counter {
+    #[serde(with = "crate::utils::float")]
     count: f64,
 }
 
-#[pymethods]
 impl Counter {
-    #[new]
     pub fn new() -> Self {
         Self { count: 0.0 }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
         if is_nan_or_inf(new_val) {
-            NAN
+            f64::NAN
         } else {
             self.count += 1.0;
             self.count
@@ -24,135 +24,337 @@ impl Counter {
     }
 }
 
-#[pyclass]
+impl Default for Counter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Counter {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.count
+    }
+
+    fn reset(&mut self) {
+        self.count = 0.0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count as usize
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sumer {
+    #[serde(with = "crate::utils::float")]
     sum: f64,
+    count: usize,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Sumer {
-    #[new]
     pub fn new() -> Self {
-        Self { sum: 0.0 }
+        Self {
+            sum: 0.0,
+            count: 0,
+            guard: NanGuard::new(NanPolicy::Skip, 1),
+        }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         if is_nan_or_inf(new_val) {
-            NAN
+            self.guard.skip();
+            f64::NAN
         } else {
+            self.count += 1;
             self.sum += new_val;
+            self.value()
+        }
+    }
+}
+
+impl Default for Sumer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Sumer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.guard.accepts_all(self.count) {
             self.sum
+        } else {
+            f64::NAN
         }
     }
+
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.count = 0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Meaner {
+    #[serde(with = "crate::utils::float")]
     count: f64,
+    #[serde(with = "crate::utils::float")]
     sum: f64,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Meaner {
-    #[new]
     pub fn new() -> Self {
         Self {
             count: 0.0,
             sum: 0.0,
+            guard: NanGuard::new(NanPolicy::Skip, 1),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         if is_nan_or_inf(new_val) {
-            NAN
+            self.guard.skip();
+            f64::NAN
         } else {
             self.count += 1.0;
             self.sum += new_val;
+            self.value()
+        }
+    }
+}
+
+impl Default for Meaner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Meaner {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.guard.accepts_all(self.count as usize) {
             self.sum / self.count
+        } else {
+            f64::NAN
         }
     }
+
+    fn reset(&mut self) {
+        self.count = 0.0;
+        self.sum = 0.0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count as usize
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stder {
+    #[serde(with = "crate::utils::float")]
     count: f64,
     sumer: Sumer,
     sq_sumer: Sumer,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Stder {
-    #[new]
     pub fn new() -> Self {
         Self {
             count: 0.0,
             sumer: Sumer::new(),
             sq_sumer: Sumer::new(),
+            guard: NanGuard::new(NanPolicy::Skip, 2),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         if is_nan_or_inf(new_val) {
-            NAN
+            self.guard.skip();
+            f64::NAN
         } else {
             self.count += 1.0;
-            let sum = self.sumer.update(new_val);
-            let sq_sum = self.sq_sumer.update(new_val * new_val);
+            self.sumer.update(new_val);
+            self.sq_sumer.update(new_val * new_val);
+            self.value()
+        }
+    }
+}
 
-            let variance = (sq_sum - sum * sum / self.count as f64) / (self.count as f64 - 1.0);
-            variance.sqrt()
+impl Default for Stder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Stder {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if !self.guard.accepts_all(self.count as usize) {
+            return f64::NAN;
         }
+        let sum = self.sumer.value();
+        let sq_sum = self.sq_sumer.value();
+
+        let variance = (sq_sum - sum * sum / self.count) / (self.count - 1.0);
+        variance.sqrt()
+    }
+
+    fn reset(&mut self) {
+        self.guard.reset();
+        self.count = 0.0;
+        self.sumer.reset();
+        self.sq_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count as usize
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skewer {
+    #[serde(with = "crate::utils::float")]
     count: f64,
     meaner: Meaner,
     sq_sumer: Sumer,
     cub_sumer: Sumer,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Skewer {
-    #[new]
     pub fn new() -> Self {
         Self {
             count: 0.0,
             meaner: Meaner::new(),
             sq_sumer: Sumer::new(),
             cub_sumer: Sumer::new(),
+            guard: NanGuard::new(NanPolicy::Skip, 2),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         if is_nan_or_inf(new_val) {
-            NAN
+            self.guard.skip();
+            f64::NAN
         } else {
             self.count += 1.0;
-            let mean = self.meaner.update(new_val);
-            let sq_sum = self.sq_sumer.update(new_val * new_val);
-            let cub_sum = self.cub_sumer.update(new_val * new_val * new_val);
+            self.meaner.update(new_val);
+            self.sq_sumer.update(new_val * new_val);
+            self.cub_sumer.update(new_val * new_val * new_val);
+            self.value()
+        }
+    }
+}
 
-            let variance = sq_sum / self.count as f64 - mean * mean;
-            (cub_sum / self.count as f64 - 3.0 * mean * variance - mean.powi(3))
-                / variance.powf(1.5)
+impl Default for Skewer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Skewer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if !self.guard.accepts_all(self.count as usize) {
+            return f64::NAN;
         }
+        let mean = self.meaner.value();
+        let sq_sum = self.sq_sumer.value();
+        let cub_sum = self.cub_sumer.value();
+
+        let variance = sq_sum / self.count - mean * mean;
+        (cub_sum / self.count - 3.0 * mean * variance - mean.powi(3)) / variance.powf(1.5)
+    }
+
+    fn reset(&mut self) {
+        self.guard.reset();
+        self.count = 0.0;
+        self.meaner.reset();
+        self.sq_sumer.reset();
+        self.cub_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count as usize
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Kurter {
+    #[serde(with = "crate::utils::float")]
     count: f64,
     sumer: Sumer,
     sq_sumer: Sumer,
     cub_sumer: Sumer,
     quad_sumer: Sumer,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Kurter {
-    #[new]
     pub fn new() -> Self {
         Self {
             count: 0.0,
@@ -160,32 +362,127 @@ impl Kurter {
             sq_sumer: Sumer::new(),
             cub_sumer: Sumer::new(),
             quad_sumer: Sumer::new(),
+            guard: NanGuard::new(NanPolicy::Skip, 4),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         if is_nan_or_inf(new_val) {
-            NAN
+            self.guard.skip();
+            f64::NAN
         } else {
             self.count += 1.0;
 
-            let quad_sum = self.quad_sumer.update(new_val.powi(4));
-            let cub_sum = self.cub_sumer.update(new_val.powi(3));
-            let sq_sum = self.sq_sumer.update(new_val.powi(2));
-            let sum = self.sumer.update(new_val);
-            let mean = sum / self.count as f64;
-            let variance = (sq_sum - 2.0 * sum * mean + self.count as f64 * mean.powi(2))
-                / (self.count as f64 - 1.0);
+            self.quad_sumer.update(new_val.powi(4));
+            self.cub_sumer.update(new_val.powi(3));
+            self.sq_sumer.update(new_val.powi(2));
+            self.sumer.update(new_val);
+            self.value()
+        }
+    }
+}
 
-            let value = quad_sum - 4.0 * cub_sum * mean + 6.0 * sq_sum * mean.powi(2)
-                - 4.0 * sum * mean.powi(3)
-                + self.count as f64 * mean.powi(4);
+impl Default for Kurter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-            let n = self.count;
-            let factor01 = n * (n + 1.0) / ((n - 1.0) * (n - 2.0) * (n - 3.0));
-            let factor02 = (n - 1.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0));
+impl Indicator for Kurter {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
 
-            factor01 * value / variance.powi(2) - factor02
+    fn value(&self) -> f64 {
+        if !self.guard.accepts_all(self.count as usize) {
+            return f64::NAN;
         }
+        let quad_sum = self.quad_sumer.value();
+        let cub_sum = self.cub_sumer.value();
+        let sq_sum = self.sq_sumer.value();
+        let sum = self.sumer.value();
+        let mean = sum / self.count;
+        let variance = (sq_sum - 2.0 * sum * mean + self.count * mean.powi(2)) / (self.count - 1.0);
+
+        let value = quad_sum - 4.0 * cub_sum * mean + 6.0 * sq_sum * mean.powi(2)
+            - 4.0 * sum * mean.powi(3)
+            + self.count * mean.powi(4);
+
+        let n = self.count;
+        let factor01 = n * (n + 1.0) / ((n - 1.0) * (n - 2.0) * (n - 3.0));
+        let factor02 = (n - 1.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0));
+
+        factor01 * value / variance.powi(2) - factor02
+    }
+
+    fn reset(&mut self) {
+        self.guard.reset();
+        self.count = 0.0;
+        self.sumer.reset();
+        self.sq_sumer.reset();
+        self.cub_sumer.reset();
+        self.quad_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count as usize
+    }
+}
+
+impl NanHandling for Sumer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+impl NanHandling for Meaner {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+impl NanHandling for Stder {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+impl NanHandling for Skewer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+impl NanHandling for Kurter {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
     }
 }
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+// TDigest is a mergeable sketch of a distribution for approximate quantiles in bounded memory.
+// Values are grouped into centroids whose size is capped by the k1 scale function,
+// so centroids near the tails stay small and tail quantiles stay accurate.
+// compression trades memory for accuracy: about compression / 2 centroids plus a buffer of up to
+// compression new values, which waits sorted and is folded in once it fills up.
+// The rank error is well under 1 / compression in the middle and far lower in the tails.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TDigest {
+    #[serde(with = "crate::utils::float")]
+    compression: f64,
+    centroids: Vec<Centroid>,
+    #[serde(with = "crate::utils::float")]
+    buffer: Vec<f64>,
+    #[serde(with = "crate::utils::float")]
+    weight: f64,
+    #[serde(with = "crate::utils::float")]
+    min: f64,
+    #[serde(with = "crate::utils::float")]
+    max: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+impl TDigest {
+    pub fn new(compression: f64) -> Self {
+        Self {
+            compression: compression.max(10.0),
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            weight: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    // x must be finite
+    pub fn add(&mut self, x: f64) {
+        let pos = self.buffer.partition_point(|&v| v < x);
+        self.buffer.insert(pos, x);
+        self.weight += 1.0;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+
+        if self.buffer.len() >= self.compression as usize {
+            let points: Vec<Centroid> = self.points().collect();
+            self.compress(points);
+        }
+    }
+
+    // fold another digest in, the result summarizes both streams
+    pub fn merge(&mut self, other: &TDigest) {
+        if other.weight == 0.0 {
+            return;
+        }
+        let mut points: Vec<Centroid> = self.points().chain(other.points()).collect();
+        points.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        self.weight += other.weight;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress(points);
+    }
+
+    // approximate quantile, linear interpolation like numpy when every centroid is a single value
+    pub fn quantile(&self, quantile: f64) -> f64 {
+        if self.weight == 0.0 || !(0.0..=1.0).contains(&quantile) {
+            return f64::NAN;
+        }
+
+        // a centroid sits at the middle of its weight, the min and max at the first and last value
+        let index = quantile * (self.weight - 1.0) + 0.5;
+        let anchors = self
+            .points()
+            .scan(0.0, |cum, c| {
+                let pos = *cum + c.weight / 2.0;
+                *cum += c.weight;
+                Some((pos, c.mean))
+            })
+            .chain(std::iter::once((self.weight - 0.5, self.max)));
+
+        let mut prev = (0.5, self.min);
+        for cur in anchors {
+            if index <= cur.0 {
+                if cur.0 - prev.0 <= 0.0 {
+                    return cur.1;
+                }
+                let fraction = (index - prev.0) / (cur.0 - prev.0);
+                return prev.1 + fraction * (cur.1 - prev.1);
+            }
+            prev = cur;
+        }
+        self.max
+    }
+
+    // number of values summarized
+    pub fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    // centroids plus buffered values kept, the memory footprint
+    pub fn size(&self) -> usize {
+        self.centroids.len() + self.buffer.len()
+    }
+
+    pub fn reset(&mut self) {
+        self.centroids.clear();
+        self.buffer.clear();
+        self.weight = 0.0;
+        self.min = f64::INFINITY;
+        self.max = f64::NEG_INFINITY;
+    }
+
+    // centroids and buffered values in ascending order
+    fn points(&self) -> impl Iterator<Item = Centroid> + '_ {
+        let mut centroids = self.centroids.iter().copied().peekable();
+        let mut buffer = self
+            .buffer
+            .iter()
+            .map(|&mean| Centroid { mean, weight: 1.0 })
+            .peekable();
+        std::iter::from_fn(move || match (centroids.peek(), buffer.peek()) {
+            (Some(c), Some(b)) if c.mean <= b.mean => centroids.next(),
+            (Some(_), None) => centroids.next(),
+            _ => buffer.next(),
+        })
+    }
+
+    // k1 scale function and its inverse, a centroid spans at most 1 in k
+    fn k(&self, q: f64) -> f64 {
+        self.compression / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+
+    fn k_inv(&self, k: f64) -> f64 {
+        // k(1) is compression / 4, past it the sine would wrap around
+        let k = k.min(self.compression / 4.0);
+        ((2.0 * PI * k / self.compression).sin() + 1.0) / 2.0
+    }
+
+    // merge sorted points into as few centroids as the scale function allows
+    fn compress(&mut self, points: Vec<Centroid>) {
+        self.buffer.clear();
+        self.centroids.clear();
+        let mut points = points.into_iter();
+        let Some(mut cur) = points.next() else {
+            return;
+        };
+
+        let total = self.weight;
+        let mut done = 0.0;
+        let mut limit = self.k_inv(self.k(0.0) + 1.0) * total;
+        for p in points {
+            if done + cur.weight + p.weight <= limit {
+                cur.weight += p.weight;
+                cur.mean += (p.mean - cur.mean) * p.weight / cur.weight;
+            } else {
+                done += cur.weight;
+                self.centroids.push(cur);
+                limit = self.k_inv(self.k((done / total).min(1.0)) + 1.0) * total;
+                cur = p;
+            }
+        }
+        self.centroids.push(cur);
+    }
+}
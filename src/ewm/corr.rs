@@ -0,0 +1,204 @@
+use super::Decay;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// Covarer is the exponentially weighted covariance, pandas `ewm(...).cov(other, bias)`.
+// an input pair is an observation only when both values are not NaN.
+// bias=false applies the weighted degrees of freedom correction sum_wt^2 / (sum_wt^2 - sum_wt2).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Covarer {
+    #[serde(with = "crate::utils::float")]
+    alpha: f64,
+    adjust: bool,
+    ignore_na: bool,
+    min_periods: usize,
+    bias: bool,
+    #[serde(with = "crate::utils::float")]
+    mean_x: f64,
+    #[serde(with = "crate::utils::float")]
+    mean_y: f64,
+    #[serde(with = "crate::utils::float")]
+    cov: f64,
+    #[serde(with = "crate::utils::float")]
+    sum_wt: f64,
+    #[serde(with = "crate::utils::float")]
+    sum_wt2: f64,
+    #[serde(with = "crate::utils::float")]
+    old_wt: f64,
+    nobs: usize,
+}
+
+impl Covarer {
+    pub fn new(
+        decay: Decay,
+        adjust: bool,
+        ignore_na: bool,
+        min_periods: usize,
+        bias: bool,
+    ) -> Self {
+        Self {
+            alpha: decay.expect_alpha(),
+            adjust,
+            ignore_na,
+            min_periods: min_periods.max(1),
+            bias,
+            mean_x: f64::NAN,
+            mean_y: f64::NAN,
+            cov: 0.0,
+            sum_wt: 1.0,
+            sum_wt2: 1.0,
+            old_wt: 1.0,
+            nobs: 0,
+        }
+    }
+
+    pub fn update(&mut self, x: f64, y: f64) -> f64 {
+        let is_observation = !x.is_nan() && !y.is_nan();
+        if is_observation {
+            self.nobs += 1;
+        }
+
+        if !self.mean_x.is_nan() {
+            if is_observation || !self.ignore_na {
+                let old_wt_factor = 1.0 - self.alpha;
+                let new_wt = if self.adjust { 1.0 } else { self.alpha };
+                self.sum_wt *= old_wt_factor;
+                self.sum_wt2 *= old_wt_factor * old_wt_factor;
+                self.old_wt *= old_wt_factor;
+                if is_observation {
+                    let (old_mean_x, old_mean_y) = (self.mean_x, self.mean_y);
+                    let total_wt = self.old_wt + new_wt;
+                    // a constant series keeps its exact mean
+                    if self.mean_x != x {
+                        self.mean_x = (self.old_wt * old_mean_x + new_wt * x) / total_wt;
+                    }
+                    if self.mean_y != y {
+                        self.mean_y = (self.old_wt * old_mean_y + new_wt * y) / total_wt;
+                    }
+                    self.cov = (self.old_wt
+                        * (self.cov + (old_mean_x - self.mean_x) * (old_mean_y - self.mean_y))
+                        + new_wt * (x - self.mean_x) * (y - self.mean_y))
+                        / total_wt;
+                    self.sum_wt += new_wt;
+                    self.sum_wt2 += new_wt * new_wt;
+                    self.old_wt += new_wt;
+                    if !self.adjust {
+                        self.sum_wt /= self.old_wt;
+                        self.sum_wt2 /= self.old_wt * self.old_wt;
+                        self.old_wt = 1.0;
+                    }
+                }
+            }
+        } else if is_observation {
+            self.mean_x = x;
+            self.mean_y = y;
+        }
+
+        self.value()
+    }
+
+    // the weighted means of x and y
+    pub fn means(&self) -> (f64, f64) {
+        (self.mean_x, self.mean_y)
+    }
+}
+
+impl Indicator for Covarer {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (x, y): (f64, f64)) -> f64 {
+        self.update(x, y)
+    }
+
+    fn value(&self) -> f64 {
+        if self.nobs < self.min_periods {
+            f64::NAN
+        } else if self.bias {
+            self.cov
+        } else {
+            let numerator = self.sum_wt * self.sum_wt;
+            let denominator = numerator - self.sum_wt2;
+            if denominator > 0.0 {
+                numerator / denominator * self.cov
+            } else {
+                f64::NAN
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.mean_x = f64::NAN;
+        self.mean_y = f64::NAN;
+        self.cov = 0.0;
+        self.sum_wt = 1.0;
+        self.sum_wt2 = 1.0;
+        self.old_wt = 1.0;
+        self.nobs = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.min_periods - 1
+    }
+
+    fn count(&self) -> usize {
+        self.nobs
+    }
+}
+
+// Correlationer is pandas `ewm(...).corr(other)`: the biased covariance over the square root of
+// the biased variances, each variance only looks at the NaN of its own series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Correlationer {
+    xy_cover: Covarer,
+    x_varer: Covarer,
+    y_varer: Covarer,
+}
+
+impl Correlationer {
+    pub fn new(decay: Decay, adjust: bool, ignore_na: bool, min_periods: usize) -> Self {
+        Self {
+            xy_cover: Covarer::new(decay, adjust, ignore_na, min_periods, true),
+            x_varer: Covarer::new(decay, adjust, ignore_na, min_periods, true),
+            y_varer: Covarer::new(decay, adjust, ignore_na, min_periods, true),
+        }
+    }
+
+    pub fn update(&mut self, x: f64, y: f64) -> f64 {
+        self.xy_cover.update(x, y);
+        self.x_varer.update(x, x);
+        self.y_varer.update(y, y);
+
+        self.value()
+    }
+}
+
+impl Indicator for Correlationer {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (x, y): (f64, f64)) -> f64 {
+        self.update(x, y)
+    }
+
+    fn value(&self) -> f64 {
+        let variance = self.x_varer.value() * self.y_varer.value();
+        // rounding can leave a tiny negative product, pandas' zsqrt maps it to 0
+        let sigma = if variance < 0.0 { 0.0 } else { variance.sqrt() };
+        self.xy_cover.value() / sigma
+    }
+
+    fn reset(&mut self) {
+        self.xy_cover.reset();
+        self.x_varer.reset();
+        self.y_varer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.xy_cover.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.xy_cover.count()
+    }
+}
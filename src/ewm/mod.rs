@@ -0,0 +1,42 @@
+// Exponentially weighted statistics with the semantics of pandas `ewm`:
+// adjust=True weighs the inputs by (1 - alpha)^i normalized by the sum of weights,
+// adjust=False is the recursive y = (1 - alpha) * y + alpha * x;
+// with ignore_na=False the weights keep decaying over NaN inputs, like pandas' default.
+// min_periods counts valid inputs and is at least 1.
+// The constructors take a Decay and panic when it is out of range.
+pub mod corr;
+pub mod statis;
+
+// Decay is the pandas way to give the smoothing factor, exactly one of com/span/halflife/alpha
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decay {
+    // center of mass, alpha = 1 / (1 + com), com >= 0
+    Com(f64),
+    // alpha = 2 / (span + 1), span >= 1
+    Span(f64),
+    // alpha = 1 - exp(-ln(2) / halflife), halflife > 0
+    HalfLife(f64),
+    // 0 < alpha <= 1
+    Alpha(f64),
+}
+
+impl Decay {
+    // the smoothing factor, None when the parameter is out of range like pandas' ValueError
+    pub fn alpha(self) -> Option<f64> {
+        match self {
+            Decay::Com(com) if com >= 0.0 => Some(1.0 / (1.0 + com)),
+            Decay::Span(span) if span >= 1.0 => Some(2.0 / (span + 1.0)),
+            Decay::HalfLife(halflife) if halflife > 0.0 => {
+                Some(1.0 - (-std::f64::consts::LN_2 / halflife).exp())
+            }
+            Decay::Alpha(alpha) if alpha > 0.0 && alpha <= 1.0 => Some(alpha),
+            _ => None,
+        }
+    }
+
+    // the smoothing factor of a constructor, which panics on an out of range decay
+    fn expect_alpha(self) -> f64 {
+        self.alpha()
+            .unwrap_or_else(|| panic!("{self:?} is out of range"))
+    }
+}
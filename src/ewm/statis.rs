@@ -0,0 +1,200 @@
+use super::corr::Covarer;
+use super::Decay;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// Meaner is pandas `ewm(...).mean()`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Meaner {
+    #[serde(with = "crate::utils::float")]
+    alpha: f64,
+    adjust: bool,
+    ignore_na: bool,
+    min_periods: usize,
+    #[serde(with = "crate::utils::float")]
+    weighted: f64,
+    #[serde(with = "crate::utils::float")]
+    old_wt: f64,
+    nobs: usize,
+}
+
+impl Meaner {
+    pub fn new(decay: Decay, adjust: bool, ignore_na: bool, min_periods: usize) -> Self {
+        Self {
+            alpha: decay.expect_alpha(),
+            adjust,
+            ignore_na,
+            min_periods: min_periods.max(1),
+            weighted: f64::NAN,
+            old_wt: 1.0,
+            nobs: 0,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        let is_observation = !new_val.is_nan();
+        if is_observation {
+            self.nobs += 1;
+        }
+
+        if !self.weighted.is_nan() {
+            if is_observation || !self.ignore_na {
+                self.old_wt *= 1.0 - self.alpha;
+                if is_observation {
+                    let new_wt = if self.adjust { 1.0 } else { self.alpha };
+                    // a constant series keeps its exact mean
+                    if self.weighted != new_val {
+                        self.weighted = (self.old_wt * self.weighted + new_wt * new_val)
+                            / (self.old_wt + new_wt);
+                    }
+                    if self.adjust {
+                        self.old_wt += new_wt;
+                    } else {
+                        self.old_wt = 1.0;
+                    }
+                }
+            }
+        } else if is_observation {
+            self.weighted = new_val;
+        }
+
+        self.value()
+    }
+}
+
+impl Indicator for Meaner {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.nobs < self.min_periods {
+            f64::NAN
+        } else {
+            self.weighted
+        }
+    }
+
+    fn reset(&mut self) {
+        self.weighted = f64::NAN;
+        self.old_wt = 1.0;
+        self.nobs = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.min_periods - 1
+    }
+
+    fn count(&self) -> usize {
+        self.nobs
+    }
+}
+
+// Varer is pandas `ewm(...).var(bias)`, the covariance of the series with itself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Varer {
+    cover: Covarer,
+}
+
+impl Varer {
+    pub fn new(
+        decay: Decay,
+        adjust: bool,
+        ignore_na: bool,
+        min_periods: usize,
+        bias: bool,
+    ) -> Self {
+        Self {
+            cover: Covarer::new(decay, adjust, ignore_na, min_periods, bias),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        self.cover.update(new_val, new_val)
+    }
+}
+
+impl Indicator for Varer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.cover.value()
+    }
+
+    fn reset(&mut self) {
+        self.cover.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.cover.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.cover.count()
+    }
+}
+
+// Stder is pandas `ewm(...).std(bias)`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stder {
+    varer: Varer,
+}
+
+impl Stder {
+    pub fn new(
+        decay: Decay,
+        adjust: bool,
+        ignore_na: bool,
+        min_periods: usize,
+        bias: bool,
+    ) -> Self {
+        Self {
+            varer: Varer::new(decay, adjust, ignore_na, min_periods, bias),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        self.varer.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Stder {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        // pandas' zsqrt, rounding can leave a tiny negative variance
+        let variance = self.varer.value();
+        if variance < 0.0 {
+            0.0
+        } else {
+            variance.sqrt()
+        }
+    }
+
+    fn reset(&mut self) {
+        self.varer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.varer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.varer.count()
+    }
+}
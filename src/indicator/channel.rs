@@ -0,0 +1,361 @@
+use super::overlap::{BBands, MAType, EMA, MIDPRICE};
+use super::volatility::ATR;
+use crate::{rolling, utils::is_nan_or_inf, Indicator};
+use serde::{Deserialize, Serialize};
+
+// the channels return (lowerband, middleband, upperband) in the order of BBands
+
+// Donchian Channels
+// the lowest low and the highest high of the period, the middle band halfway between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Donchian {
+    maxer: rolling::minmax::Maxer,
+    miner: rolling::minmax::Miner,
+}
+
+impl Donchian {
+    pub fn new(timeperiod: usize) -> Self {
+        Self {
+            maxer: rolling::minmax::Maxer::new(timeperiod),
+            miner: rolling::minmax::Miner::new(timeperiod),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64) -> (f64, f64, f64) {
+        self.maxer.update(high);
+        self.miner.update(low);
+
+        self.value()
+    }
+}
+
+impl Indicator for Donchian {
+    type Input = (f64, f64);
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, (high, low): (f64, f64)) -> (f64, f64, f64) {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        let upperband = self.maxer.value();
+        let lowerband = self.miner.value();
+        (lowerband, (upperband + lowerband) / 2.0, upperband)
+    }
+
+    fn reset(&mut self) {
+        self.maxer.reset();
+        self.miner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.maxer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.maxer.count()
+    }
+}
+
+// Keltner Channels
+// the EMA of the close as the middle band, the bands are multiplier ATRs away
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keltner {
+    ema: EMA,
+    atr: ATR,
+    #[serde(with = "crate::utils::float")]
+    multiplier: f64,
+}
+
+impl Keltner {
+    pub fn new(timeperiod: usize, atr_period: usize, multiplier: f64) -> Self {
+        Self {
+            ema: EMA::new(timeperiod),
+            atr: ATR::new(atr_period),
+            multiplier,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> (f64, f64, f64) {
+        self.ema.update(close);
+        self.atr.update(high, low, close);
+
+        self.value()
+    }
+}
+
+impl Indicator for Keltner {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> (f64, f64, f64) {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        let middleband = self.ema.value();
+        let width = self.multiplier * self.atr.value();
+        (middleband - width, middleband, middleband + width)
+    }
+
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.atr.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ema.lookback().max(self.atr.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.atr.count()
+    }
+}
+
+// Ichimoku Kinko Hyo
+// tenkan, kijun, senkou_a, senkou_b, chikou = Ichimoku(high, low, close, tenkan=9, kijun=26, senkou=52, displacement=26)
+// tenkan, kijun and the raw senkou B are the midpoints of the high/low range over their periods,
+// the raw senkou A is the mean of tenkan and kijun. Both spans are drawn `displacement` bars ahead,
+// so the spans of the current bar are the raw ones of `displacement` bars ago, kept in buffers,
+// and leading() gives the raw ones, the cloud `displacement` bars ahead.
+// chikou is the close, drawn `displacement` bars back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ichimoku {
+    tenkan: MIDPRICE,
+    kijun: MIDPRICE,
+    senkou: MIDPRICE,
+    senkou_a_vec: rolling::container::Container,
+    senkou_b_vec: rolling::container::Container,
+    #[serde(with = "crate::utils::float")]
+    close: f64,
+    displacement: usize,
+}
+
+impl Ichimoku {
+    pub fn new(tenkan: usize, kijun: usize, senkou: usize, displacement: usize) -> Self {
+        Self {
+            tenkan: MIDPRICE::new(tenkan),
+            kijun: MIDPRICE::new(kijun),
+            senkou: MIDPRICE::new(senkou),
+            senkou_a_vec: rolling::container::Container::new(displacement + 1),
+            senkou_b_vec: rolling::container::Container::new(displacement + 1),
+            close: f64::NAN,
+            displacement,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> (f64, f64, f64, f64, f64) {
+        let tenkan = self.tenkan.update(high, low);
+        let kijun = self.kijun.update(high, low);
+        self.senkou_a_vec.update((tenkan + kijun) / 2.0);
+        self.senkou_b_vec.update(self.senkou.update(high, low));
+        self.close = close;
+
+        self.value()
+    }
+
+    // (senkou_a, senkou_b) of the bar `displacement` bars ahead
+    pub fn leading(&self) -> (f64, f64) {
+        (self.senkou_a_vec.tail(), self.senkou_b_vec.tail())
+    }
+}
+
+impl Indicator for Ichimoku {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64, f64, f64, f64);
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> (f64, f64, f64, f64, f64) {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> (f64, f64, f64, f64, f64) {
+        (
+            self.tenkan.value(),
+            self.kijun.value(),
+            self.senkou_a_vec.head(),
+            self.senkou_b_vec.head(),
+            self.close,
+        )
+    }
+
+    fn reset(&mut self) {
+        self.tenkan.reset();
+        self.kijun.reset();
+        self.senkou.reset();
+        self.senkou_a_vec.reset();
+        self.senkou_b_vec.reset();
+        self.close = f64::NAN;
+    }
+
+    // the displaced spans come last
+    fn lookback(&self) -> usize {
+        let raw = self
+            .tenkan
+            .lookback()
+            .max(self.kijun.lookback())
+            .max(self.senkou.lookback());
+        raw + self.displacement
+    }
+
+    fn count(&self) -> usize {
+        self.senkou_a_vec.count()
+    }
+}
+
+// Supertrend
+// the bands are multiplier ATRs around (high + low) / 2 and only tighten while the close stays
+// inside them. a close above the upper band turns the trend up, one below the lower band turns it
+// down, the trend starts up. outputs (supertrend, direction): the lower band and 1 in an up trend,
+// the upper band and -1 in a down trend. bars with a NaN input are ignored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Supertrend {
+    atr: ATR,
+    #[serde(with = "crate::utils::float")]
+    multiplier: f64,
+    #[serde(with = "crate::utils::float")]
+    upperband: f64,
+    #[serde(with = "crate::utils::float")]
+    lowerband: f64,
+    #[serde(with = "crate::utils::float")]
+    preclose: f64,
+    direction: i8,
+    count: usize,
+}
+
+impl Supertrend {
+    pub fn new(timeperiod: usize, multiplier: f64) -> Self {
+        Self {
+            atr: ATR::new(timeperiod),
+            multiplier,
+            upperband: f64::NAN,
+            lowerband: f64::NAN,
+            preclose: f64::NAN,
+            direction: 1,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> (f64, f64) {
+        self.count += 1;
+        if is_nan_or_inf(high) || is_nan_or_inf(low) || is_nan_or_inf(close) {
+            return self.value();
+        }
+
+        let atr = self.atr.update(high, low, close);
+        if !is_nan_or_inf(atr) {
+            let median = (high + low) / 2.0;
+            let upperband = median + self.multiplier * atr;
+            let lowerband = median - self.multiplier * atr;
+            // a band only widens again after the close broke through it
+            if is_nan_or_inf(self.upperband)
+                || upperband < self.upperband
+                || self.preclose > self.upperband
+            {
+                self.upperband = upperband;
+            }
+            if is_nan_or_inf(self.lowerband)
+                || lowerband > self.lowerband
+                || self.preclose < self.lowerband
+            {
+                self.lowerband = lowerband;
+            }
+            if self.direction < 0 && close > self.upperband {
+                self.direction = 1;
+            } else if self.direction > 0 && close < self.lowerband {
+                self.direction = -1;
+            }
+        }
+        self.preclose = close;
+
+        self.value()
+    }
+}
+
+impl Indicator for Supertrend {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> (f64, f64) {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        if is_nan_or_inf(self.upperband) {
+            (f64::NAN, f64::NAN)
+        } else if self.direction > 0 {
+            (self.lowerband, 1.0)
+        } else {
+            (self.upperband, -1.0)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.upperband = f64::NAN;
+        self.lowerband = f64::NAN;
+        self.preclose = f64::NAN;
+        self.direction = 1;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.atr.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// Squeeze - Bollinger Bands inside Keltner Channels
+// 1 while both Bollinger Bands (SMA and nbdev stds) lie inside the Keltner Channels
+// (EMA and multiplier ATRs) of the same period, 0 otherwise and before both are ready
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Squeeze {
+    bbands: BBands,
+    keltner: Keltner,
+}
+
+impl Squeeze {
+    pub fn new(timeperiod: usize, nbdev: f64, multiplier: f64) -> Self {
+        Self {
+            bbands: BBands::new(timeperiod, nbdev, nbdev, MAType::SMA),
+            keltner: Keltner::new(timeperiod, timeperiod, multiplier),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> i32 {
+        self.bbands.update(close);
+        self.keltner.update(high, low, close);
+
+        self.value()
+    }
+}
+
+impl Indicator for Squeeze {
+    type Input = (f64, f64, f64);
+    type Output = i32;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> i32 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> i32 {
+        let (bb_lower, _, bb_upper) = self.bbands.value();
+        let (kc_lower, _, kc_upper) = self.keltner.value();
+        (bb_lower > kc_lower && bb_upper < kc_upper) as i32
+    }
+
+    fn reset(&mut self) {
+        self.bbands.reset();
+        self.keltner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.bbands.lookback().max(self.keltner.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.keltner.count()
+    }
+}
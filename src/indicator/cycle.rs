@@ -0,0 +1,692 @@
+use crate::{utils::is_nan_or_inf, Indicator};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f64::consts::PI;
+
+// the dominant cycle is clamped to 6..50 bars, so no more than 50 past bars are ever read
+const MAX_CYCLE: usize = 50;
+const A: f64 = 0.0962;
+const B: f64 = 0.5769;
+pub(crate) const RAD2DEG: f64 = 180.0 / PI;
+const DEG2RAD: f64 = 1.0 / RAD2DEG;
+
+// one parity of a Hilbert filter, TA-Lib runs odd and even bars through separate buffers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Half {
+    #[serde(with = "crate::utils::float")]
+    buf: Vec<f64>,
+    #[serde(with = "crate::utils::float")]
+    prev: f64,
+    #[serde(with = "crate::utils::float")]
+    prev_input: f64,
+}
+
+impl Half {
+    fn new() -> Self {
+        Self {
+            buf: vec![0.0; 3],
+            prev: 0.0,
+            prev_input: 0.0,
+        }
+    }
+}
+
+// Hilbert FIR filter of Ehlers, written in the operation order of TA-Lib's DO_HILBERT_TRANSFORM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Filter {
+    odd: Half,
+    even: Half,
+}
+
+impl Filter {
+    fn new() -> Self {
+        Self {
+            odd: Half::new(),
+            even: Half::new(),
+        }
+    }
+
+    fn update(&mut self, input: f64, idx: usize, even: bool, adjusted_period: f64) -> f64 {
+        let half = if even { &mut self.even } else { &mut self.odd };
+        let temp = A * input;
+        let mut value = -half.buf[idx];
+        half.buf[idx] = temp;
+        value += temp;
+        value -= half.prev;
+        half.prev = B * half.prev_input;
+        value += half.prev;
+        half.prev_input = input;
+        value * adjusted_period
+    }
+}
+
+// HilbertTransform is the streaming core shared by MAMA and the HT_* indicators:
+// a 4-bar WMA of the price, the detrender, the in-phase/quadrature components and the dominant cycle period.
+// TA-Lib runs the price WMA alone for `warmup` bars after the first 3 (9 or 34 depending on the function),
+// and the parity of the bar index picks the filter buffers, so both are kept to match its outputs.
+// NaN/inf inputs are ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HilbertTransform {
+    warmup: usize,
+    count: usize,
+    #[serde(with = "crate::utils::float")]
+    prices: VecDeque<f64>,
+    #[serde(with = "crate::utils::float")]
+    smoothed: VecDeque<f64>,
+    #[serde(with = "crate::utils::float")]
+    wma_sub: f64,
+    #[serde(with = "crate::utils::float")]
+    wma_sum: f64,
+    #[serde(with = "crate::utils::float")]
+    wma_trailing: f64,
+    idx: usize,
+    detrender: Filter,
+    q1_filter: Filter,
+    ji_filter: Filter,
+    jq_filter: Filter,
+    #[serde(with = "crate::utils::float")]
+    i1_odd_prev2: f64,
+    #[serde(with = "crate::utils::float")]
+    i1_odd_prev3: f64,
+    #[serde(with = "crate::utils::float")]
+    i1_even_prev2: f64,
+    #[serde(with = "crate::utils::float")]
+    i1_even_prev3: f64,
+    #[serde(with = "crate::utils::float")]
+    i1: f64,
+    #[serde(with = "crate::utils::float")]
+    q1: f64,
+    #[serde(with = "crate::utils::float")]
+    prev_i2: f64,
+    #[serde(with = "crate::utils::float")]
+    prev_q2: f64,
+    #[serde(with = "crate::utils::float")]
+    re: f64,
+    #[serde(with = "crate::utils::float")]
+    im: f64,
+    #[serde(with = "crate::utils::float")]
+    period: f64,
+    #[serde(with = "crate::utils::float")]
+    smooth_period: f64,
+}
+
+impl HilbertTransform {
+    pub fn new(warmup: usize) -> Self {
+        Self {
+            warmup,
+            count: 0,
+            prices: VecDeque::with_capacity(MAX_CYCLE),
+            smoothed: VecDeque::with_capacity(MAX_CYCLE),
+            wma_sub: 0.0,
+            wma_sum: 0.0,
+            wma_trailing: 0.0,
+            idx: 0,
+            detrender: Filter::new(),
+            q1_filter: Filter::new(),
+            ji_filter: Filter::new(),
+            jq_filter: Filter::new(),
+            i1_odd_prev2: 0.0,
+            i1_odd_prev3: 0.0,
+            i1_even_prev2: 0.0,
+            i1_even_prev3: 0.0,
+            i1: 0.0,
+            q1: 0.0,
+            prev_i2: 0.0,
+            prev_q2: 0.0,
+            re: 0.0,
+            im: 0.0,
+            period: 0.0,
+            smooth_period: 0.0,
+        }
+    }
+
+    // returns true once the transform itself has run, false while only the price WMA is warming up
+    pub fn update(&mut self, price: f64) -> bool {
+        if is_nan_or_inf(price) {
+            return false;
+        }
+
+        let today = self.count;
+        self.count += 1;
+        if self.prices.len() == MAX_CYCLE {
+            self.prices.pop_front();
+        }
+        self.prices.push_back(price);
+
+        if today < 3 {
+            self.wma_sub += price;
+            self.wma_sum += price * (today + 1) as f64;
+            return false;
+        }
+
+        let adjusted_period = 0.075 * self.period + 0.54;
+
+        self.wma_sub += price;
+        self.wma_sub -= self.wma_trailing;
+        self.wma_sum += price * 4.0;
+        self.wma_trailing = self.prices[self.prices.len() - 4];
+        let smoothed = self.wma_sum * 0.1;
+        self.wma_sum -= self.wma_sub;
+
+        if today < 3 + self.warmup {
+            return false;
+        }
+
+        if self.smoothed.len() == MAX_CYCLE {
+            self.smoothed.pop_front();
+        }
+        self.smoothed.push_back(smoothed);
+
+        let even = today.is_multiple_of(2);
+        let i1 = if even {
+            self.i1_even_prev3
+        } else {
+            self.i1_odd_prev3
+        };
+        let detrender = self
+            .detrender
+            .update(smoothed, self.idx, even, adjusted_period);
+        let q1 = self
+            .q1_filter
+            .update(detrender, self.idx, even, adjusted_period);
+        let ji = self.ji_filter.update(i1, self.idx, even, adjusted_period);
+        let jq = self.jq_filter.update(q1, self.idx, even, adjusted_period);
+
+        if even {
+            self.idx = (self.idx + 1) % 3;
+            self.i1_odd_prev3 = self.i1_odd_prev2;
+            self.i1_odd_prev2 = detrender;
+        } else {
+            self.i1_even_prev3 = self.i1_even_prev2;
+            self.i1_even_prev2 = detrender;
+        }
+        self.i1 = i1;
+        self.q1 = q1;
+
+        let q2 = (0.2 * (q1 + ji)) + (0.8 * self.prev_q2);
+        let i2 = (0.2 * (i1 - jq)) + (0.8 * self.prev_i2);
+
+        self.re = (0.2 * ((i2 * self.prev_i2) + (q2 * self.prev_q2))) + (0.8 * self.re);
+        self.im = (0.2 * ((i2 * self.prev_q2) - (q2 * self.prev_i2))) + (0.8 * self.im);
+        self.prev_q2 = q2;
+        self.prev_i2 = i2;
+
+        let prev_period = self.period;
+        if self.im != 0.0 && self.re != 0.0 {
+            self.period = 360.0 / ((self.im / self.re).atan() * RAD2DEG);
+        }
+        if self.period > 1.5 * prev_period {
+            self.period = 1.5 * prev_period;
+        }
+        if self.period < 0.67 * prev_period {
+            self.period = 0.67 * prev_period;
+        }
+        self.period = self.period.clamp(6.0, 50.0);
+        self.period = (0.2 * self.period) + (0.8 * prev_period);
+        self.smooth_period = (0.33 * self.period) + (0.67 * self.smooth_period);
+
+        true
+    }
+
+    // in-phase component, the detrended price delayed by 3 bars
+    pub fn inphase(&self) -> f64 {
+        self.i1
+    }
+
+    pub fn quadrature(&self) -> f64 {
+        self.q1
+    }
+
+    // dominant cycle period before and after its final smoothing
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    pub fn smooth_period(&self) -> f64 {
+        self.smooth_period
+    }
+
+    // the smoothed period rounded to whole bars
+    fn cycle(&self) -> usize {
+        ((self.smooth_period + 0.5) as usize).min(MAX_CYCLE)
+    }
+
+    // dominant cycle phase in degrees from the smoothed prices of the last cycle,
+    // `prev` is kept when the cycle has no imaginary part
+    pub fn dc_phase(&self, prev: f64) -> f64 {
+        let cycle = self.cycle();
+        let mut real = 0.0;
+        let mut imag = 0.0;
+        for (i, &smoothed) in self.smoothed.iter().rev().take(cycle).enumerate() {
+            let angle = (i as f64 * 2.0 * PI) / cycle as f64;
+            real += angle.sin() * smoothed;
+            imag += angle.cos() * smoothed;
+        }
+
+        let mut phase = prev;
+        if imag.abs() > 0.0 {
+            phase = (real / imag).atan() * RAD2DEG;
+        } else if real < 0.0 {
+            phase -= 90.0;
+        } else if real > 0.0 {
+            phase += 90.0;
+        }
+        phase += 90.0;
+        // compensate the one bar lag of the price WMA
+        phase += 360.0 / self.smooth_period;
+        if imag < 0.0 {
+            phase += 180.0;
+        }
+        if phase > 315.0 {
+            phase -= 360.0;
+        }
+        phase
+    }
+
+    // mean of the raw prices over the last dominant cycle
+    pub fn cycle_mean(&self) -> f64 {
+        let cycle = self.cycle();
+        let sum: f64 = self
+            .prices
+            .iter()
+            .rev()
+            .take(cycle)
+            .fold(0.0, |acc, v| acc + v);
+        if cycle > 0 {
+            sum / cycle as f64
+        } else {
+            sum
+        }
+    }
+
+    // latest smoothed price
+    pub fn smoothed(&self) -> f64 {
+        self.smoothed.back().copied().unwrap_or(f64::NAN)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.warmup);
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// Trendline is the 4-bar WMA of the cycle means that makes the instantaneous trendline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Trendline {
+    #[serde(with = "crate::utils::float")]
+    trend1: f64,
+    #[serde(with = "crate::utils::float")]
+    trend2: f64,
+    #[serde(with = "crate::utils::float")]
+    trend3: f64,
+}
+
+impl Trendline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, cycle_mean: f64) -> f64 {
+        let trendline =
+            (4.0 * cycle_mean + 3.0 * self.trend1 + 2.0 * self.trend2 + self.trend3) / 10.0;
+        self.trend3 = self.trend2;
+        self.trend2 = self.trend1;
+        self.trend1 = cycle_mean;
+        trendline
+    }
+}
+
+// HT_DCPERIOD - Hilbert Transform - Dominant Cycle Period
+// NOTE: The HT_DCPERIOD function has an unstable period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HTDCPeriod {
+    hilbert: HilbertTransform,
+}
+
+impl HTDCPeriod {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(9),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+        self.value()
+    }
+}
+
+impl Default for HTDCPeriod {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HTDCPeriod {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.is_ready() {
+            self.hilbert.smooth_period()
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.hilbert.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        32
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
+
+// HT_DCPHASE - Hilbert Transform - Dominant Cycle Phase
+// NOTE: The HT_DCPHASE function has an unstable period.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HTDCPhase {
+    hilbert: HilbertTransform,
+    #[serde(with = "crate::utils::float")]
+    phase: f64,
+}
+
+impl HTDCPhase {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(34),
+            phase: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+        self.phase = self.hilbert.dc_phase(self.phase);
+        self.value()
+    }
+}
+
+impl Default for HTDCPhase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HTDCPhase {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.is_ready() {
+            self.phase
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.hilbert.reset();
+        self.phase = 0.0;
+    }
+
+    fn lookback(&self) -> usize {
+        63
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
+
+// HT_PHASOR - Hilbert Transform - Phasor Components
+// NOTE: The HT_PHASOR function has an unstable period.
+// inphase, quadrature = HT_PHASOR(real)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HTPhasor {
+    hilbert: HilbertTransform,
+}
+
+impl HTPhasor {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(9),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> (f64, f64) {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+        self.value()
+    }
+}
+
+impl Default for HTPhasor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HTPhasor {
+    type Input = f64;
+    type Output = (f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        if self.is_ready() {
+            (self.hilbert.inphase(), self.hilbert.quadrature())
+        } else {
+            (f64::NAN, f64::NAN)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.hilbert.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        32
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
+
+// HT_SINE - Hilbert Transform - SineWave
+// NOTE: The HT_SINE function has an unstable period.
+// sine, leadsine = HT_SINE(real)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HTSine {
+    hilbert: HilbertTransform,
+    #[serde(with = "crate::utils::float")]
+    phase: f64,
+}
+
+impl HTSine {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(34),
+            phase: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> (f64, f64) {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+        self.phase = self.hilbert.dc_phase(self.phase);
+        self.value()
+    }
+}
+
+impl Default for HTSine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HTSine {
+    type Input = f64;
+    type Output = (f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        if self.is_ready() {
+            (
+                (self.phase * DEG2RAD).sin(),
+                ((self.phase + 45.0) * DEG2RAD).sin(),
+            )
+        } else {
+            (f64::NAN, f64::NAN)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.hilbert.reset();
+        self.phase = 0.0;
+    }
+
+    fn lookback(&self) -> usize {
+        63
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
+
+// HT_TRENDMODE - Hilbert Transform - Trend vs Cycle Mode
+// NOTE: The HT_TRENDMODE function has an unstable period.
+// 1 in a trend, 0 in a cycle: a crossing of the sine wave lines or a phase advancing at the cycle rate
+// starts a cycle, a price 1.5% away from the trendline forces a trend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HTTrendMode {
+    hilbert: HilbertTransform,
+    trendline: Trendline,
+    #[serde(with = "crate::utils::float")]
+    phase: f64,
+    #[serde(with = "crate::utils::float")]
+    sine: f64,
+    #[serde(with = "crate::utils::float")]
+    lead_sine: f64,
+    days_in_trend: usize,
+    trend: i32,
+}
+
+impl HTTrendMode {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(34),
+            trendline: Trendline::new(),
+            phase: 0.0,
+            sine: 0.0,
+            lead_sine: 0.0,
+            days_in_trend: 0,
+            trend: 0,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> i32 {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+
+        let prev_phase = self.phase;
+        let prev_sine = self.sine;
+        let prev_lead_sine = self.lead_sine;
+        self.phase = self.hilbert.dc_phase(prev_phase);
+        self.sine = (self.phase * DEG2RAD).sin();
+        self.lead_sine = ((self.phase + 45.0) * DEG2RAD).sin();
+        let trendline = self.trendline.update(self.hilbert.cycle_mean());
+        let smooth_period = self.hilbert.smooth_period();
+
+        self.trend = 1;
+        if (self.sine > self.lead_sine && prev_sine <= prev_lead_sine)
+            || (self.sine < self.lead_sine && prev_sine >= prev_lead_sine)
+        {
+            self.days_in_trend = 0;
+            self.trend = 0;
+        }
+        self.days_in_trend += 1;
+        if (self.days_in_trend as f64) < 0.5 * smooth_period {
+            self.trend = 0;
+        }
+
+        let advance = self.phase - prev_phase;
+        if smooth_period != 0.0
+            && advance > 0.67 * 360.0 / smooth_period
+            && advance < 1.5 * 360.0 / smooth_period
+        {
+            self.trend = 0;
+        }
+
+        let smoothed = self.hilbert.smoothed();
+        if trendline != 0.0 && ((smoothed - trendline) / trendline).abs() >= 0.015 {
+            self.trend = 1;
+        }
+
+        self.value()
+    }
+}
+
+impl Default for HTTrendMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HTTrendMode {
+    type Input = f64;
+    type Output = i32;
+
+    fn update(&mut self, input: f64) -> i32 {
+        self.update(input)
+    }
+
+    fn value(&self) -> i32 {
+        if self.is_ready() {
+            self.trend
+        } else {
+            0
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn lookback(&self) -> usize {
+        63
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
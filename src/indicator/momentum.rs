@@ -1,18 +1,16 @@
-use super::overlap::{EMA, SMA};
-use crate::{max, rolling};
-use pyo3::prelude::*;
+use super::overlap::{MAType, EMA, MA, SMA};
+use crate::{max, rolling, utils::is_nan_or_inf, Indicator};
+use serde::{Deserialize, Serialize};
 
 // Awesome Oscillator: SMA5-SMA34
 // compare with 0
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AO {
     fast_smaer: SMA,
     slow_smaer: SMA,
 }
 
-#[pymethods]
 impl AO {
-    #[new]
     pub fn new(fast_period: usize, slow_period: usize) -> Self {
         Self {
             fast_smaer: SMA::new(fast_period),
@@ -26,26 +24,76 @@ impl AO {
     }
 }
 
-// Absolute Price Oscillator: SMA12-SMA26
+impl Indicator for AO {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low): (f64, f64)) -> f64 {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> f64 {
+        self.fast_smaer.value() - self.slow_smaer.value()
+    }
+
+    fn reset(&mut self) {
+        self.fast_smaer.reset();
+        self.slow_smaer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.fast_smaer.lookback().max(self.slow_smaer.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.slow_smaer.count()
+    }
+}
+
+// Absolute Price Oscillator: MA12-MA26 of `matype`, SMA by default in TA-Lib
 // compare with 0
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct APO {
-    fast_smaer: SMA,
-    slow_smaer: SMA,
+    fast_ma: MA,
+    slow_ma: MA,
 }
 
-#[pymethods]
 impl APO {
-    #[new]
-    pub fn new(fast_period: usize, slow_period: usize) -> Self {
+    pub fn new(fast_period: usize, slow_period: usize, matype: MAType) -> Self {
         Self {
-            fast_smaer: SMA::new(fast_period),
-            slow_smaer: SMA::new(slow_period),
+            fast_ma: MA::new(fast_period, matype),
+            slow_ma: MA::new(slow_period, matype),
         }
     }
 
     pub fn update(&mut self, price: f64) -> f64 {
-        self.fast_smaer.update(price) - self.slow_smaer.update(price)
+        self.fast_ma.update(price) - self.slow_ma.update(price)
+    }
+}
+
+impl Indicator for APO {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.fast_ma.value() - self.slow_ma.value()
+    }
+
+    fn reset(&mut self) {
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.fast_ma.lookback().max(self.slow_ma.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.slow_ma.count()
     }
 }
 
@@ -53,7 +101,7 @@ impl APO {
 
 // real = ADX(high, low, close, timeperiod=14)
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ADX {
     high_vec: rolling::container::Container,
     low_vec: rolling::container::Container,
@@ -63,9 +111,7 @@ pub struct ADX {
     dx_meaner: rolling::statis::Meaner,
 }
 
-#[pymethods]
 impl ADX {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             high_vec: rolling::container::Container::new(2),
@@ -86,18 +132,16 @@ impl ADX {
         let high_diff = high - pre_high;
         let low_diff_reverse = pre_low - low;
 
-        let plus_dm: f64;
-        if (high_diff > low_diff_reverse) && (high_diff > 0.0) {
-            plus_dm = high_diff;
+        let plus_dm = if (high_diff > low_diff_reverse) && (high_diff > 0.0) {
+            high_diff
         } else {
-            plus_dm = 0.0;
-        }
-        let minus_dm: f64;
-        if (low_diff_reverse > high_diff) && (low_diff_reverse > 0.0) {
-            minus_dm = low_diff_reverse;
+            0.0
+        };
+        let minus_dm = if (low_diff_reverse > high_diff) && (low_diff_reverse > 0.0) {
+            low_diff_reverse
         } else {
-            minus_dm = 0.0;
-        }
+            0.0
+        };
         let tr = max!(high - low, (high - preclose).abs(), (low - preclose).abs());
 
         let smoothed_plus_dm = self.plus_dm_sumer.update(plus_dm);
@@ -107,23 +151,50 @@ impl ADX {
         let di_plus = 100.0 * smoothed_plus_dm / smoothed_tr;
         let di_minus = 100.0 * smoothed_minus_dm / smoothed_tr;
         let dx = 100.0 * (di_plus - di_minus).abs() / (di_plus + di_minus);
-        let adx = self.dx_meaner.update(dx);
 
-        adx
+        self.dx_meaner.update(dx)
+    }
+}
+
+impl Indicator for ADX {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, preclose): (f64, f64, f64)) -> f64 {
+        self.update(high, low, preclose)
+    }
+
+    fn value(&self) -> f64 {
+        self.dx_meaner.value()
+    }
+
+    fn reset(&mut self) {
+        self.high_vec.reset();
+        self.low_vec.reset();
+        self.plus_dm_sumer.reset();
+        self.minus_dm_sumer.reset();
+        self.tr_sumer.reset();
+        self.dx_meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.tr_sumer.lookback() + self.dx_meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.tr_sumer.count()
     }
 }
 
 // real = ADXR(high, low, close, timeperiod=14)
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ADXR {
     adxer: ADX,
     adx_container: rolling::container::Container,
 }
 
-#[pymethods]
 impl ADXR {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             adxer: ADX::new(timeperiod),
@@ -134,23 +205,48 @@ impl ADXR {
     pub fn update(&mut self, high: f64, low: f64, preclose: f64) -> f64 {
         let adx = self.adxer.update(high, low, preclose);
         self.adx_container.update(adx);
+
+        self.value()
+    }
+}
+
+impl Indicator for ADXR {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, preclose): (f64, f64, f64)) -> f64 {
+        self.update(high, low, preclose)
+    }
+
+    fn value(&self) -> f64 {
         let tail_adx = self.adx_container.tail();
         let head_adx = self.adx_container.head();
 
         (tail_adx + head_adx) / 2.0
     }
+
+    fn reset(&mut self) {
+        self.adxer.reset();
+        self.adx_container.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.adxer.lookback() + self.adx_container.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.adxer.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Aroon {
     high_maxidxer: rolling::minmax::MaxIndexer,
     low_minidxer: rolling::minmax::MinIndexer,
     period: usize,
 }
 
-#[pymethods]
 impl Aroon {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             high_maxidxer: rolling::minmax::MaxIndexer::new(period),
@@ -160,24 +256,60 @@ impl Aroon {
     }
 
     pub fn update(&mut self, high: f64, low: f64) -> (f64, f64) {
-        let (max_high_idx, _) = self.high_maxidxer.update(high);
-        let (min_low_idx, _) = self.low_minidxer.update(low);
+        self.high_maxidxer.update(high);
+        self.low_minidxer.update(low);
+
+        self.value()
+    }
+}
 
-        let aroon_up = max_high_idx as f64 / self.period as f64;
-        let aroon_down = min_low_idx as f64 / self.period as f64;
+impl Indicator for Aroon {
+    type Input = (f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low): (f64, f64)) -> (f64, f64) {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        let (max_high_idx, max_high) = self.high_maxidxer.value();
+        let (min_low_idx, min_low) = self.low_minidxer.value();
+
+        // the indexers return usize::MAX with a NaN value during warmup
+        let aroon_up = if max_high.is_nan() {
+            f64::NAN
+        } else {
+            max_high_idx as f64 / self.period as f64
+        };
+        let aroon_down = if min_low.is_nan() {
+            f64::NAN
+        } else {
+            min_low_idx as f64 / self.period as f64
+        };
 
         (aroon_up, aroon_down)
     }
+
+    fn reset(&mut self) {
+        self.high_maxidxer.reset();
+        self.low_minidxer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.high_maxidxer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.high_maxidxer.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AroonOsc {
     aroon: Aroon,
 }
 
-#[pymethods]
 impl AroonOsc {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             aroon: Aroon::new(period),
@@ -185,40 +317,107 @@ impl AroonOsc {
     }
 
     pub fn update(&mut self, high: f64, low: f64) -> f64 {
-        let (aroon_up, aroon_down) = self.aroon.update(high, low);
+        self.aroon.update(high, low);
+
+        self.value()
+    }
+}
+
+impl Indicator for AroonOsc {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low): (f64, f64)) -> f64 {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> f64 {
+        let (aroon_up, aroon_down) = self.aroon.value();
 
         aroon_up - aroon_down
     }
+
+    fn reset(&mut self) {
+        self.aroon.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.aroon.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.aroon.count()
+    }
 }
 
-#[pyclass]
-pub struct BOP {}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BOP {
+    #[serde(with = "crate::utils::float")]
+    bop: f64,
+    count: usize,
+}
 
-#[pymethods]
 impl BOP {
-    #[new]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            bop: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> f64 {
-        (close - open) / (high - low)
+        self.bop = (close - open) / (high - low);
+        self.count += 1;
+        self.bop
+    }
+}
+
+impl Default for BOP {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for BOP {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.bop
+    }
+
+    fn reset(&mut self) {
+        self.bop = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CCI {
     tp_meaner: rolling::statis::Meaner,
     tp_meandever: rolling::statis::Meaner,
+    #[serde(with = "crate::utils::float")]
+    cci: f64,
 }
 
-#[pymethods]
 impl CCI {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             tp_meaner: rolling::statis::Meaner::new(period),
             tp_meandever: rolling::statis::Meaner::new(period),
+            cci: f64::NAN,
         }
     }
 
@@ -227,20 +426,46 @@ impl CCI {
         let tp_avg = self.tp_meaner.update(tp);
         let tp_meandev = self.tp_meandever.update((tp - tp_avg).abs());
 
-        (tp - tp_avg) / (0.015 * tp_meandev)
+        self.cci = (tp - tp_avg) / (0.015 * tp_meandev);
+        self.cci
+    }
+}
+
+impl Indicator for CCI {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.cci
+    }
+
+    fn reset(&mut self) {
+        self.tp_meaner.reset();
+        self.tp_meandever.reset();
+        self.cci = f64::NAN;
+    }
+
+    fn lookback(&self) -> usize {
+        self.tp_meaner.lookback() + self.tp_meandever.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.tp_meaner.count()
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CMO {
     close_deltaer: rolling::delta::Deltaer,
     gain_sumer: rolling::statis::Sumer,
     loss_sumer: rolling::statis::Sumer,
 }
 
-#[pymethods]
 impl CMO {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             close_deltaer: rolling::delta::Deltaer::new(2),
@@ -251,29 +476,55 @@ impl CMO {
 
     pub fn update(&mut self, close: f64) -> f64 {
         let close_delta = self.close_deltaer.update(close);
-        let up;
-        let down;
         if close_delta > 0.0 {
-            up = self.gain_sumer.update(close_delta);
-            down = self.loss_sumer.update(0.0);
+            self.gain_sumer.update(close_delta);
+            self.loss_sumer.update(0.0);
         } else {
-            up = self.gain_sumer.update(0.0);
-            down = self.loss_sumer.update(close_delta.abs());
+            self.gain_sumer.update(0.0);
+            self.loss_sumer.update(close_delta.abs());
         }
 
+        self.value()
+    }
+}
+
+impl Indicator for CMO {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let up = self.gain_sumer.value();
+        let down = self.loss_sumer.value();
+
         (up - down) / (up + down)
     }
+
+    fn reset(&mut self) {
+        self.close_deltaer.reset();
+        self.gain_sumer.reset();
+        self.loss_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.close_deltaer.lookback() + self.gain_sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.close_deltaer.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DX {
     plus_dier: PlusDI,
     minus_dier: MinusDI,
 }
 
-#[pymethods]
 impl DX {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             plus_dier: PlusDI::new(period),
@@ -282,55 +533,112 @@ impl DX {
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
-        let plus_di = self.plus_dier.update(high, low, close);
-        let minus_di = self.minus_dier.update(high, low, close);
+        self.plus_dier.update(high, low, close);
+        self.minus_dier.update(high, low, close);
+
+        self.value()
+    }
+}
+
+impl Indicator for DX {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        let plus_di = self.plus_dier.value();
+        let minus_di = self.minus_dier.value();
 
         (plus_di - minus_di).abs() / (plus_di + minus_di)
     }
+
+    fn reset(&mut self) {
+        self.plus_dier.reset();
+        self.minus_dier.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.plus_dier.lookback().max(self.minus_dier.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.plus_dier.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KDJ {
     lowest: rolling::minmax::Miner,
     highest: rolling::minmax::Maxer,
     d_liner: rolling::statis::Meaner,
     j_liner: rolling::statis::Meaner,
+    #[serde(with = "crate::utils::float")]
+    k_line: f64,
 }
 
-#[pymethods]
 impl KDJ {
-    #[new]
     pub fn new(view_period: usize, d_period: usize, j_period: usize) -> Self {
         Self {
             lowest: rolling::minmax::Miner::new(view_period),
             highest: rolling::minmax::Maxer::new(view_period),
             d_liner: rolling::statis::Meaner::new(d_period),
             j_liner: rolling::statis::Meaner::new(j_period),
+            k_line: f64::NAN,
         }
     }
 
     pub fn update(&mut self, price: f64, high: f64, low: f64) -> (f64, f64, f64) {
         let lowest_low = self.lowest.update(low);
         let highest_high = self.highest.update(high);
-        let k_line = (price - lowest_low) / (highest_high - lowest_low);
-        let d_line = self.d_liner.update(k_line);
-        let j_line_raw = 3.0 * k_line - 2.0 * d_line;
-        let j_line = self.j_liner.update(j_line_raw);
+        self.k_line = (price - lowest_low) / (highest_high - lowest_low);
+        let d_line = self.d_liner.update(self.k_line);
+        let j_line_raw = 3.0 * self.k_line - 2.0 * d_line;
+        self.j_liner.update(j_line_raw);
+
+        self.value()
+    }
+}
 
-        (k_line, d_line, j_line)
+impl Indicator for KDJ {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, (price, high, low): (f64, f64, f64)) -> (f64, f64, f64) {
+        self.update(price, high, low)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        (self.k_line, self.d_liner.value(), self.j_liner.value())
+    }
+
+    fn reset(&mut self) {
+        self.lowest.reset();
+        self.highest.reset();
+        self.d_liner.reset();
+        self.j_liner.reset();
+        self.k_line = f64::NAN;
+    }
+
+    fn lookback(&self) -> usize {
+        self.highest.lookback() + self.d_liner.lookback() + self.j_liner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.highest.count()
     }
 }
 
 // KST Oscillator: ROC10 + 2*ROC15 + 3*ROC20 + 4*ROC30
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KST {
     ptchanger: rolling::delta::Pctchanger,
     max_period: usize,
 }
 
-#[pymethods]
 impl KST {
-    #[new]
     pub fn new(max_period: usize) -> Self {
         Self {
             ptchanger: rolling::delta::Pctchanger::new(max_period),
@@ -341,6 +649,19 @@ impl KST {
     pub fn update(&mut self, new_val: f64) -> f64 {
         self.ptchanger.update(new_val);
 
+        self.value()
+    }
+}
+
+impl Indicator for KST {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
         let roc10 = self.ptchanger.partial(self.max_period - 9, self.max_period);
         let roc15 = self
             .ptchanger
@@ -354,18 +675,28 @@ impl KST {
 
         roc10 + 2.0 * roc15 + 3.0 * roc20 + 4.0 * roc30
     }
+
+    fn reset(&mut self) {
+        self.ptchanger.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ptchanger.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ptchanger.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MACD {
     fast_ema: EMA,
     slow_ema: EMA,
     signal_ema: EMA,
 }
 
-#[pymethods]
 impl MACD {
-    #[new]
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
         Self {
             fast_ema: EMA::new(fast_period),
@@ -386,16 +717,110 @@ impl MACD {
     }
 }
 
-#[pyclass]
+impl Indicator for MACD {
+    type Input = f64;
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        let macd_line = self.fast_ema.value() - self.slow_ema.value();
+        let signal_line = self.signal_ema.value();
+
+        (macd_line, signal_line, macd_line - signal_line)
+    }
+
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.signal_ema.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.fast_ema.lookback().max(self.slow_ema.lookback()) + self.signal_ema.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.fast_ema.count()
+    }
+}
+
+// MACDEXT - MACD with controllable MA type
+// macd, macdsignal, macdhist = MACDEXT(real, fastperiod=12, fastmatype=0, slowperiod=26, slowmatype=0, signalperiod=9, signalmatype=0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MACDEXT {
+    fast_ma: MA,
+    slow_ma: MA,
+    signal_ma: MA,
+}
+
+impl MACDEXT {
+    pub fn new(
+        fast_period: usize,
+        fast_matype: MAType,
+        slow_period: usize,
+        slow_matype: MAType,
+        signal_period: usize,
+        signal_matype: MAType,
+    ) -> Self {
+        Self {
+            fast_ma: MA::new(fast_period, fast_matype),
+            slow_ma: MA::new(slow_period, slow_matype),
+            signal_ma: MA::new(signal_period, signal_matype),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> (f64, f64, f64) {
+        let macd_line = self.fast_ma.update(new_val) - self.slow_ma.update(new_val);
+        // the signal line starts once the macd line is valid, a skipped NaN bar leaves it as it is
+        if !is_nan_or_inf(new_val) && !is_nan_or_inf(macd_line) {
+            self.signal_ma.update(macd_line);
+        }
+
+        self.value()
+    }
+}
+
+impl Indicator for MACDEXT {
+    type Input = f64;
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        let macd_line = self.fast_ma.value() - self.slow_ma.value();
+        let signal_line = self.signal_ma.value();
+
+        (macd_line, signal_line, macd_line - signal_line)
+    }
+
+    fn reset(&mut self) {
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+        self.signal_ma.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.fast_ma.lookback().max(self.slow_ma.lookback()) + self.signal_ma.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.fast_ma.count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinusDM {
     high_vec: rolling::container::Container,
     low_vec: rolling::container::Container,
     minus_dm_sumer: rolling::statis::Sumer,
 }
 
-#[pymethods]
 impl MinusDM {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             high_vec: rolling::container::Container::new(2),
@@ -411,27 +836,51 @@ impl MinusDM {
         let high_diff = high - pre_high;
         let low_diff_reverse = pre_low - low;
 
-        let minus_dm: f64;
-        if (low_diff_reverse > high_diff) && (low_diff_reverse > 0.0) {
-            minus_dm = low_diff_reverse;
+        let minus_dm = if (low_diff_reverse > high_diff) && (low_diff_reverse > 0.0) {
+            low_diff_reverse
         } else {
-            minus_dm = 0.0;
-        }
+            0.0
+        };
 
         self.minus_dm_sumer.update(minus_dm)
     }
 }
 
-#[pyclass]
+impl Indicator for MinusDM {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low): (f64, f64)) -> f64 {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> f64 {
+        self.minus_dm_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.high_vec.reset();
+        self.low_vec.reset();
+        self.minus_dm_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.minus_dm_sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.low_vec.count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlusDM {
     high_vec: rolling::container::Container,
     low_vec: rolling::container::Container,
     plus_dm_sumer: rolling::statis::Sumer,
 }
 
-#[pymethods]
 impl PlusDM {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             high_vec: rolling::container::Container::new(2),
@@ -447,27 +896,51 @@ impl PlusDM {
         let high_diff = high - pre_high;
         let low_diff_reverse = pre_low - low;
 
-        let plus_dm: f64;
-        if (high_diff > low_diff_reverse) && (high_diff > 0.0) {
-            plus_dm = high_diff;
+        let plus_dm = if (high_diff > low_diff_reverse) && (high_diff > 0.0) {
+            high_diff
         } else {
-            plus_dm = 0.0;
-        }
+            0.0
+        };
 
         self.plus_dm_sumer.update(plus_dm)
     }
 }
 
-#[pyclass]
+impl Indicator for PlusDM {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low): (f64, f64)) -> f64 {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> f64 {
+        self.plus_dm_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.high_vec.reset();
+        self.low_vec.reset();
+        self.plus_dm_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.plus_dm_sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.high_vec.count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlusDI {
     plus_dmer: PlusDM,
     tr_sumer: rolling::statis::Sumer,
     close_container: rolling::container::Container,
 }
 
-#[pymethods]
 impl PlusDI {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             plus_dmer: PlusDM::new(timeperiod),
@@ -478,24 +951,49 @@ impl PlusDI {
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
         let (preclose, _) = self.close_container.update(close);
-        let smoothed_plus_dm = self.plus_dmer.update(high, low);
+        self.plus_dmer.update(high, low);
         let tr = max!(high - low, (high - preclose).abs(), (low - preclose).abs());
-        let smoothed_tr = self.tr_sumer.update(tr);
+        self.tr_sumer.update(tr);
+
+        self.value()
+    }
+}
+
+impl Indicator for PlusDI {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        100.0 * self.plus_dmer.value() / self.tr_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.plus_dmer.reset();
+        self.tr_sumer.reset();
+        self.close_container.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.plus_dmer.lookback().max(self.tr_sumer.lookback())
+    }
 
-        100.0 * smoothed_plus_dm / smoothed_tr
+    fn count(&self) -> usize {
+        self.plus_dmer.count()
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinusDI {
     minus_dmer: MinusDM,
     tr_sumer: rolling::statis::Sumer,
     close_container: rolling::container::Container,
 }
 
-#[pymethods]
 impl MinusDI {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             minus_dmer: MinusDM::new(timeperiod),
@@ -506,24 +1004,49 @@ impl MinusDI {
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
         let (preclose, _) = self.close_container.update(close);
-        let smoothed_minus_dm = self.minus_dmer.update(high, low);
+        self.minus_dmer.update(high, low);
         let tr = max!(high - low, (high - preclose).abs(), (low - preclose).abs());
-        let smoothed_tr = self.tr_sumer.update(tr);
+        self.tr_sumer.update(tr);
+
+        self.value()
+    }
+}
+
+impl Indicator for MinusDI {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        100.0 * self.minus_dmer.value() / self.tr_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.minus_dmer.reset();
+        self.tr_sumer.reset();
+        self.close_container.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.minus_dmer.lookback().max(self.tr_sumer.lookback())
+    }
 
-        100.0 * smoothed_minus_dm / smoothed_tr
+    fn count(&self) -> usize {
+        self.minus_dmer.count()
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MFI {
     pos_mfer: rolling::statis::Sumer,
     neg_mfer: rolling::statis::Sumer,
     tp_deltaer: rolling::delta::Deltaer,
 }
 
-#[pymethods]
 impl MFI {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             pos_mfer: rolling::statis::Sumer::new(timeperiod),
@@ -535,29 +1058,53 @@ impl MFI {
     pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> f64 {
         let typical_price = (high + low + close) / 3.0;
         let tp_diff = self.tp_deltaer.update(typical_price);
-        let pos_mf;
-        let neg_mf;
         if tp_diff > 0.0 {
-            pos_mf = self.pos_mfer.update(typical_price * volume);
-            neg_mf = self.neg_mfer.update(0.0);
+            self.pos_mfer.update(typical_price * volume);
+            self.neg_mfer.update(0.0);
         } else {
-            pos_mf = self.pos_mfer.update(0.0);
-            neg_mf = self.neg_mfer.update(typical_price * volume);
+            self.pos_mfer.update(0.0);
+            self.neg_mfer.update(typical_price * volume);
         }
-        let mfr = pos_mf / neg_mf;
+
+        self.value()
+    }
+}
+
+impl Indicator for MFI {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close, volume): (f64, f64, f64, f64)) -> f64 {
+        self.update(high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        let mfr = self.pos_mfer.value() / self.neg_mfer.value();
 
         mfr / (mfr + 1.0)
     }
+
+    fn reset(&mut self) {
+        self.pos_mfer.reset();
+        self.neg_mfer.reset();
+        self.tp_deltaer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.pos_mfer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.tp_deltaer.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MOM {
     deltaer: rolling::delta::Deltaer,
 }
 
-#[pymethods]
 impl MOM {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             deltaer: rolling::delta::Deltaer::new(timeperiod),
@@ -569,39 +1116,87 @@ impl MOM {
     }
 }
 
-#[pyclass]
+impl Indicator for MOM {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.deltaer.value()
+    }
+
+    fn reset(&mut self) {
+        self.deltaer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.deltaer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.deltaer.count()
+    }
+}
+
+// Percentage Price Oscillator as a ratio: MA12/MA26 - 1 of `matype`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PPO {
-    fast_emaer: EMA,
-    slow_emaer: EMA,
+    fast_ma: MA,
+    slow_ma: MA,
 }
 
-#[pymethods]
 impl PPO {
-    #[new]
-    pub fn new(fast_period: usize, slow_period: usize) -> Self {
+    pub fn new(fast_period: usize, slow_period: usize, matype: MAType) -> Self {
         Self {
-            fast_emaer: EMA::new(fast_period),
-            slow_emaer: EMA::new(slow_period),
+            fast_ma: MA::new(fast_period, matype),
+            slow_ma: MA::new(slow_period, matype),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        self.fast_emaer.update(new_val) / self.slow_emaer.update(new_val) - 1.0
+        self.fast_ma.update(new_val) / self.slow_ma.update(new_val) - 1.0
+    }
+}
+
+impl Indicator for PPO {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.fast_ma.value() / self.slow_ma.value() - 1.0
+    }
+
+    fn reset(&mut self) {
+        self.fast_ma.reset();
+        self.slow_ma.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.fast_ma.lookback().max(self.slow_ma.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.slow_ma.count()
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PPOHist {
     ppo: PPO,
     signal_emaer: EMA,
 }
 
-#[pymethods]
 impl PPOHist {
-    #[new]
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
         Self {
-            ppo: PPO::new(fast_period, slow_period),
+            ppo: PPO::new(fast_period, slow_period, MAType::EMA),
             signal_emaer: EMA::new(signal_period),
         }
     }
@@ -614,14 +1209,38 @@ impl PPOHist {
     }
 }
 
-#[pyclass]
+impl Indicator for PPOHist {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.ppo.value() - self.signal_emaer.value()
+    }
+
+    fn reset(&mut self) {
+        self.ppo.reset();
+        self.signal_emaer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ppo.lookback() + self.signal_emaer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ppo.count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ROC {
     pctchanger: rolling::delta::Pctchanger,
 }
 
-#[pymethods]
 impl ROC {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             pctchanger: rolling::delta::Pctchanger::new(timeperiod),
@@ -633,14 +1252,37 @@ impl ROC {
     }
 }
 
-#[pyclass]
+impl Indicator for ROC {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.pctchanger.value()
+    }
+
+    fn reset(&mut self) {
+        self.pctchanger.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.pctchanger.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.pctchanger.count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ROCR {
     container: rolling::container::Container,
 }
 
-#[pymethods]
 impl ROCR {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             container: rolling::container::Container::new(timeperiod),
@@ -650,11 +1292,36 @@ impl ROCR {
     pub fn update(&mut self, new_val: f64) -> f64 {
         self.container.update(new_val);
 
+        self.value()
+    }
+}
+
+impl Indicator for ROCR {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
         self.container.tail() / self.container.head()
     }
+
+    fn reset(&mut self) {
+        self.container.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.container.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.container.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RSI {
     price_deltaer: rolling::delta::Deltaer,
     up_moves: rolling::statis::Sumer,
@@ -663,9 +1330,7 @@ pub struct RSI {
     down_smoother: EMA,
 }
 
-#[pymethods]
 impl RSI {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             price_deltaer: rolling::delta::Deltaer::new(2),
@@ -678,15 +1343,17 @@ impl RSI {
 
     pub fn update(&mut self, price: f64) -> f64 {
         let delta = self.price_deltaer.update(price);
-        let up_sum;
-        let down_sum;
-        if delta > 0.0 {
-            up_sum = self.up_moves.update(delta.abs());
-            down_sum = self.down_moves.update(0.0);
+        let (up_sum, down_sum) = if delta > 0.0 {
+            (
+                self.up_moves.update(delta.abs()),
+                self.down_moves.update(0.0),
+            )
         } else {
-            up_sum = self.up_moves.update(0.0);
-            down_sum = self.down_moves.update(delta.abs());
-        }
+            (
+                self.up_moves.update(0.0),
+                self.down_moves.update(delta.abs()),
+            )
+        };
 
         let rs = self.up_smoother.update(up_sum) / self.down_smoother.update(down_sum);
 
@@ -694,23 +1361,59 @@ impl RSI {
     }
 }
 
-#[pyclass]
+impl Indicator for RSI {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let rs = self.up_smoother.value() / self.down_smoother.value();
+
+        rs / (1.0 + rs)
+    }
+
+    fn reset(&mut self) {
+        self.price_deltaer.reset();
+        self.up_moves.reset();
+        self.down_moves.reset();
+        self.up_smoother.reset();
+        self.down_smoother.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.price_deltaer.lookback() + self.up_moves.lookback() + self.up_smoother.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.price_deltaer.count()
+    }
+}
+
+// slowk, slowd = STOCH(high, low, close, fastk_period=5, slowk_period=3, slowk_matype=0, slowd_period=3, slowd_matype=0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stoch {
     high_maxer: rolling::minmax::Maxer,
     low_miner: rolling::minmax::Miner,
-    slowk_liner: SMA,
-    slowd_liner: SMA,
+    slowk_liner: MA,
+    slowd_liner: MA,
 }
 
-#[pymethods]
 impl Stoch {
-    #[new]
-    pub fn new(fastk_period: usize, slowk_period: usize, slowd_period: usize) -> Self {
+    pub fn new(
+        fastk_period: usize,
+        slowk_period: usize,
+        slowk_matype: MAType,
+        slowd_period: usize,
+        slowd_matype: MAType,
+    ) -> Self {
         Self {
             high_maxer: rolling::minmax::Maxer::new(fastk_period),
             low_miner: rolling::minmax::Miner::new(fastk_period),
-            slowk_liner: SMA::new(slowk_period),
-            slowd_liner: SMA::new(slowd_period),
+            slowk_liner: MA::new(slowk_period, slowk_matype),
+            slowd_liner: MA::new(slowd_period, slowd_matype),
         }
     }
 
@@ -719,72 +1422,152 @@ impl Stoch {
         let low_min = self.low_miner.update(low);
         let fastk_line = (close - low_min) / (high_max - low_min);
         let slowk_line = self.slowk_liner.update(fastk_line);
-        let slowd_line = self.slowd_liner.update(slowk_line);
+        self.slowd_liner.update(slowk_line);
+
+        self.value()
+    }
+}
+
+impl Indicator for Stoch {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> (f64, f64) {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        (self.slowk_liner.value(), self.slowd_liner.value())
+    }
 
-        (slowk_line, slowd_line)
+    fn reset(&mut self) {
+        self.high_maxer.reset();
+        self.low_miner.reset();
+        self.slowk_liner.reset();
+        self.slowd_liner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.high_maxer.lookback() + self.slowk_liner.lookback() + self.slowd_liner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.high_maxer.count()
     }
 }
 
-#[pyclass]
+// fastk, fastd = STOCHF(high, low, close, fastk_period=5, fastd_period=3, fastd_matype=0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StochCHF {
     high_maxer: rolling::minmax::Maxer,
     low_miner: rolling::minmax::Miner,
-    fastd_liner: SMA,
+    fastd_liner: MA,
+    #[serde(with = "crate::utils::float")]
+    fastk_line: f64,
 }
 
-#[pymethods]
 impl StochCHF {
-    #[new]
-    pub fn new(fastk_period: usize, fastd_period: usize) -> Self {
+    pub fn new(fastk_period: usize, fastd_period: usize, fastd_matype: MAType) -> Self {
         Self {
             high_maxer: rolling::minmax::Maxer::new(fastk_period),
             low_miner: rolling::minmax::Miner::new(fastk_period),
-            fastd_liner: SMA::new(fastd_period),
+            fastd_liner: MA::new(fastd_period, fastd_matype),
+            fastk_line: f64::NAN,
         }
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> (f64, f64) {
         let high_max = self.high_maxer.update(high);
         let low_min = self.low_miner.update(low);
-        let fastk_line = (close - low_min) / (high_max - low_min);
-        let fastd_line = self.fastd_liner.update(fastk_line);
+        self.fastk_line = (close - low_min) / (high_max - low_min);
+        self.fastd_liner.update(self.fastk_line);
+
+        self.value()
+    }
+}
+
+impl Indicator for StochCHF {
+    type Input = (f64, f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> (f64, f64) {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        (self.fastk_line, self.fastd_liner.value())
+    }
 
-        (fastk_line, fastd_line)
+    fn reset(&mut self) {
+        self.high_maxer.reset();
+        self.low_miner.reset();
+        self.fastd_liner.reset();
+        self.fastk_line = f64::NAN;
+    }
+
+    fn lookback(&self) -> usize {
+        self.high_maxer.lookback() + self.fastd_liner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.high_maxer.count()
     }
 }
 
-#[pyclass]
+// fastk, fastd = STOCHRSI(real, timeperiod=14, fastk_period=5, fastd_period=3, fastd_matype=0)
+// STOCHF of the RSI as high, low and close, as TA-Lib computes it
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StochRSI {
     rsier: RSI,
-    rsi_minmaxer: rolling::minmax::MinMaxer,
-    fastk_liner: SMA,
-    fastd_liner: SMA,
+    stocher: StochCHF,
 }
 
-#[pymethods]
 impl StochRSI {
-    #[new]
-    pub fn new(rsi_period: usize, fastk_period: usize, fastd_period: usize) -> Self {
+    pub fn new(
+        rsi_period: usize,
+        fastk_period: usize,
+        fastd_period: usize,
+        fastd_matype: MAType,
+    ) -> Self {
         Self {
             rsier: RSI::new(rsi_period),
-            rsi_minmaxer: rolling::minmax::MinMaxer::new(rsi_period),
-            fastk_liner: SMA::new(fastk_period),
-            fastd_liner: SMA::new(fastd_period),
+            stocher: StochCHF::new(fastk_period, fastd_period, fastd_matype),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> (f64, f64) {
         let rsi = self.rsier.update(new_val);
-        let (rsi_min, rsi_max) = self.rsi_minmaxer.update(rsi);
-        let stoch_rsi = (rsi - rsi_min) / (rsi_max - rsi_min);
-        let fastk = self.fastk_liner.update(stoch_rsi);
-        let fastd = self.fastd_liner.update(stoch_rsi);
+        self.stocher.update(rsi, rsi, rsi)
+    }
+}
+
+impl Indicator for StochRSI {
+    type Input = f64;
+    type Output = (f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        self.stocher.value()
+    }
+
+    fn reset(&mut self) {
+        self.rsier.reset();
+        self.stocher.reset();
+    }
 
-        (fastk, fastd)
+    fn lookback(&self) -> usize {
+        self.rsier.lookback() + self.stocher.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.rsier.count()
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TRIX {
     ema_lv1: EMA,
     ema_lv2: EMA,
@@ -792,9 +1575,7 @@ pub struct TRIX {
     ema_lv3_pctc: rolling::delta::Pctchanger,
 }
 
-#[pymethods]
 impl TRIX {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             ema_lv1: EMA::new(timeperiod),
@@ -813,9 +1594,40 @@ impl TRIX {
     }
 }
 
+impl Indicator for TRIX {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.ema_lv3_pctc.value()
+    }
+
+    fn reset(&mut self) {
+        self.ema_lv1.reset();
+        self.ema_lv2.reset();
+        self.ema_lv3.reset();
+        self.ema_lv3_pctc.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ema_lv1.lookback()
+            + self.ema_lv2.lookback()
+            + self.ema_lv3.lookback()
+            + self.ema_lv3_pctc.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ema_lv3_pctc.count()
+    }
+}
+
 // real = ULTOSC(high, low, close, timeperiod1=7, timeperiod2=14, timeperiod3=28)
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ULTOSC {
     timeperiod1_bp_sumer: rolling::statis::Sumer,
     timeperiod2_bp_sumer: rolling::statis::Sumer,
@@ -825,9 +1637,7 @@ pub struct ULTOSC {
     timeperiod3_tr_sumer: rolling::statis::Sumer,
 }
 
-#[pymethods]
 impl ULTOSC {
-    #[new]
     pub fn new(timeperiod1: usize, timeperiod2: usize, timeperiod3: usize) -> Self {
         Self {
             timeperiod1_bp_sumer: rolling::statis::Sumer::new(timeperiod1),
@@ -843,30 +1653,71 @@ impl ULTOSC {
         let bp = close - low.min(preclose);
         let tr = high.max(preclose) - low.min(preclose);
 
+        self.timeperiod1_bp_sumer.update(bp);
+        self.timeperiod1_tr_sumer.update(tr);
+        self.timeperiod2_bp_sumer.update(bp);
+        self.timeperiod2_tr_sumer.update(tr);
+        self.timeperiod3_bp_sumer.update(bp);
+        self.timeperiod3_tr_sumer.update(tr);
+
+        self.value()
+    }
+}
+
+impl Indicator for ULTOSC {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close, preclose): (f64, f64, f64, f64)) -> f64 {
+        self.update(high, low, close, preclose)
+    }
+
+    fn value(&self) -> f64 {
         let timeperiod1_bp_avg =
-            self.timeperiod1_bp_sumer.update(bp) / self.timeperiod1_tr_sumer.update(tr);
+            self.timeperiod1_bp_sumer.value() / self.timeperiod1_tr_sumer.value();
         let timeperiod2_bp_avg =
-            self.timeperiod2_bp_sumer.update(bp) / self.timeperiod2_tr_sumer.update(tr);
+            self.timeperiod2_bp_sumer.value() / self.timeperiod2_tr_sumer.value();
         let timeperiod3_bp_avg =
-            self.timeperiod3_bp_sumer.update(bp) / self.timeperiod3_tr_sumer.update(tr);
+            self.timeperiod3_bp_sumer.value() / self.timeperiod3_tr_sumer.value();
 
         100.0 * (4.0 * timeperiod1_bp_avg + 2.0 * timeperiod2_bp_avg + timeperiod3_bp_avg) / 7.0
     }
+
+    fn reset(&mut self) {
+        self.timeperiod1_bp_sumer.reset();
+        self.timeperiod2_bp_sumer.reset();
+        self.timeperiod3_bp_sumer.reset();
+        self.timeperiod1_tr_sumer.reset();
+        self.timeperiod2_tr_sumer.reset();
+        self.timeperiod3_tr_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.timeperiod1_tr_sumer
+            .lookback()
+            .max(self.timeperiod2_tr_sumer.lookback())
+            .max(self.timeperiod3_tr_sumer.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.timeperiod1_tr_sumer.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WILLR {
     high_maxer: rolling::minmax::Maxer,
     low_miner: rolling::minmax::Miner,
+    #[serde(with = "crate::utils::float")]
+    willr: f64,
 }
 
-#[pymethods]
 impl WILLR {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             high_maxer: rolling::minmax::Maxer::new(timeperiod),
             low_miner: rolling::minmax::Miner::new(timeperiod),
+            willr: f64::NAN,
         }
     }
 
@@ -874,6 +1725,34 @@ impl WILLR {
         let high_max = self.high_maxer.update(high);
         let low_min = self.low_miner.update(low);
 
-        (high_max - close) / (high_max - low_min)
+        self.willr = (high_max - close) / (high_max - low_min);
+        self.willr
+    }
+}
+
+impl Indicator for WILLR {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.willr
+    }
+
+    fn reset(&mut self) {
+        self.high_maxer.reset();
+        self.low_miner.reset();
+        self.willr = f64::NAN;
+    }
+
+    fn lookback(&self) -> usize {
+        self.high_maxer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.high_maxer.count()
     }
 }
@@ -1,22 +1,24 @@
-use crate::{rolling, utils::is_nan_or_inf};
-use pyo3::prelude::*;
-use std::f64::NAN;
+use super::cycle::{HilbertTransform, Trendline, RAD2DEG};
+use crate::{rolling, utils::is_nan_or_inf, Indicator};
+use serde::{Deserialize, Serialize};
 
 // Bollinger Bands
-#[pyclass]
+// upperband, middleband, lowerband = BBANDS(real, timeperiod=5, nbdevup=2, nbdevdn=2, matype=0)
+// the middle band is the moving average of `matype`, the bands are nbdevup/nbdevdn stds of the period away
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BBands {
-    smaer: rolling::statis::Meaner,
+    ma: MA,
     stder: rolling::statis::Stder,
+    #[serde(with = "crate::utils::float")]
     nbdevup: f64,
+    #[serde(with = "crate::utils::float")]
     nbdevdn: f64,
 }
 
-#[pymethods]
 impl BBands {
-    #[new]
-    pub fn new(period: usize, nbdevup: f64, nbdevdn: f64) -> Self {
+    pub fn new(period: usize, nbdevup: f64, nbdevdn: f64, matype: MAType) -> Self {
         Self {
-            smaer: rolling::statis::Meaner::new(period),
+            ma: MA::new(period, matype),
             stder: rolling::statis::Stder::new(period),
             nbdevup,
             nbdevdn,
@@ -24,29 +26,56 @@ impl BBands {
     }
 
     pub fn update(&mut self, new_val: f64) -> (f64, f64, f64) {
-        let dev = self.stder.update(new_val);
-        let middleband = self.smaer.update(new_val);
+        self.stder.update(new_val);
+        self.ma.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for BBands {
+    type Input = f64;
+    type Output = (f64, f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        let dev = self.stder.value();
+        let middleband = self.ma.value();
 
         let upperband = middleband + self.nbdevup * dev;
         let lowerband = middleband - self.nbdevdn * dev;
 
         (lowerband, middleband, upperband)
     }
+
+    fn reset(&mut self) {
+        self.ma.reset();
+        self.stder.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.stder.lookback().max(self.ma.lookback())
+    }
+
+    fn count(&self) -> usize {
+        self.stder.count()
+    }
 }
 
 // DEMA - Double Exponential Moving Average
 // EMA1 = EMA of price
 // EMA2 = EMA of EMA1
 // DEMA = (2 x EMA1) - EMA2
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DEMA {
     ema_lv1: EMA,
     ema_lv2: EMA,
 }
 
-#[pymethods]
 impl DEMA {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             ema_lv1: EMA::new(period),
@@ -62,6 +91,32 @@ impl DEMA {
     }
 }
 
+impl Indicator for DEMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        2.0 * self.ema_lv1.value() - self.ema_lv2.value()
+    }
+
+    fn reset(&mut self) {
+        self.ema_lv1.reset();
+        self.ema_lv2.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ema_lv1.lookback() + self.ema_lv2.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ema_lv1.count()
+    }
+}
+
 // EMA - Exponential Moving Average
 // NOTE: The EMA function has an unstable period.
 // Cumulative Exponential Moving Average (EMA) over all data points.
@@ -71,51 +126,87 @@ impl DEMA {
 // Also, because it can fluctuate more in response to short-term price movements, leading to potential whipsaws or false signals.
 // EMA = prev_EMA x (1 – SmoothingFactor) + Price x SmoothingFactor
 // SmoothingFactor = 2 / (period + 1)
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EMA {
+    #[serde(with = "crate::utils::float")]
     alpha: f64,
+    #[serde(with = "crate::utils::float")]
     ema: Option<f64>,
+    count: usize,
 }
 
-#[pymethods]
 impl EMA {
-    #[new]
     pub fn new(period: usize) -> Self {
         let alpha = 2.0 / (period as f64 + 1.0);
-        Self { alpha, ema: None }
+        Self {
+            alpha,
+            ema: None,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
         if is_nan_or_inf(new_val) {
-            NAN
+            f64::NAN
         } else {
+            self.count += 1;
             if let Some(prev_ema) = self.ema {
                 self.ema = Some(prev_ema * (1.0 - self.alpha) + new_val * self.alpha);
             } else {
                 self.ema = Some(new_val);
             }
-            self.ema.unwrap()
+            self.value()
         }
     }
 }
 
+impl Indicator for EMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.ema.unwrap_or(f64::NAN)
+    }
+
+    fn reset(&mut self) {
+        self.ema = None;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
 // KAMA - Kaufman Adaptive Moving Average
 // NOTE: The KAMA function has an unstable period.
-#[pyclass]
+// the efficiency ratio is |price - price er_period bars back| over the er_period absolute changes
+// since, as in TA-Lib; the first value starts from the previous price
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KAMA {
     price_vec: rolling::container::Container,
     volatility_sumer: rolling::statis::Sumer,
+    #[serde(with = "crate::utils::float")]
     fast_sc: f64,
+    #[serde(with = "crate::utils::float")]
     slow_sc: f64,
+    #[serde(with = "crate::utils::float")]
     kama: Option<f64>,
 }
 
-#[pymethods]
 impl KAMA {
-    #[new]
     pub fn new(er_period: usize, fast_period: usize, slow_period: usize) -> Self {
         Self {
-            price_vec: rolling::container::Container::new(er_period), // typical 10
+            // the price er_period bars back is the head of er_period + 1 prices
+            price_vec: rolling::container::Container::new(er_period + 1), // typical 10
             volatility_sumer: rolling::statis::Sumer::new(er_period),
             fast_sc: 2.0 / (fast_period as f64 + 1.0), // typical 2
             slow_sc: 2.0 / (slow_period as f64 + 1.0), // typical 30
@@ -125,53 +216,504 @@ impl KAMA {
 
     pub fn update(&mut self, price: f64, preprice: f64) -> f64 {
         if is_nan_or_inf(price) {
-            NAN
+            f64::NAN
         } else {
             self.price_vec.update(price);
             let change = (price - self.price_vec.head()).abs();
             let volatility = self.volatility_sumer.update((price - preprice).abs());
-            let er = change / volatility;
+            // TA-Lib takes a flat window as fully efficient
+            let er = if volatility == 0.0 {
+                1.0
+            } else {
+                change / volatility
+            };
             let sc = (er * (self.fast_sc - self.slow_sc) + self.slow_sc).powi(2);
 
-            if let Some(prev_kama) = self.kama {
-                self.kama = Some(prev_kama + sc * (price - prev_kama));
-            } else {
-                self.kama = Some(price);
+            // the efficiency ratio is NaN until its window is full, seeding earlier would poison kama
+            if is_nan_or_inf(sc) {
+                return self.value();
+            }
+
+            // seeded with the previous price, as TA-Lib does
+            let prev_kama = self.kama.unwrap_or(preprice);
+            self.kama = Some(prev_kama + sc * (price - prev_kama));
+
+            self.value()
+        }
+    }
+}
+
+impl Indicator for KAMA {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (price, preprice): (f64, f64)) -> f64 {
+        self.update(price, preprice)
+    }
+
+    fn value(&self) -> f64 {
+        self.kama.unwrap_or(f64::NAN)
+    }
+
+    fn reset(&mut self) {
+        self.price_vec.reset();
+        self.volatility_sumer.reset();
+        self.kama = None;
+    }
+
+    // timeperiod as in TA-Lib, the first price has no previous one for the volatility
+    fn lookback(&self) -> usize {
+        self.volatility_sumer.lookback() + 1
+    }
+
+    fn count(&self) -> usize {
+        self.price_vec.count()
+    }
+}
+
+// HT_TRENDLINE - Hilbert Transform - Instantaneous Trendline
+// NOTE: The HT_TRENDLINE function has an unstable period.
+// a 4-bar WMA of the price averaged over the dominant cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HTTrendline {
+    hilbert: HilbertTransform,
+    trendline: Trendline,
+    #[serde(with = "crate::utils::float")]
+    value: f64,
+}
+
+impl HTTrendline {
+    pub fn new() -> Self {
+        Self {
+            hilbert: HilbertTransform::new(34),
+            trendline: Trendline::new(),
+            value: f64::NAN,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+        self.value = self.trendline.update(self.hilbert.cycle_mean());
+        self.value()
+    }
+}
+
+impl Default for HTTrendline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HTTrendline {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        if self.is_ready() {
+            self.value
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn lookback(&self) -> usize {
+        63
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
+
+// MAType picks the moving average of the indicators taking a TA-Lib `matype`, with the same codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum MAType {
+    #[default]
+    SMA = 0,
+    EMA = 1,
+    WMA = 2,
+    DEMA = 3,
+    TEMA = 4,
+    TRIMA = 5,
+    KAMA = 6,
+    MAMA = 7,
+    T3 = 8,
+}
+
+impl MAType {
+    pub const ALL: [MAType; 9] = [
+        MAType::SMA,
+        MAType::EMA,
+        MAType::WMA,
+        MAType::DEMA,
+        MAType::TEMA,
+        MAType::TRIMA,
+        MAType::KAMA,
+        MAType::MAMA,
+        MAType::T3,
+    ];
+
+    // the TA-Lib code, 0 for SMA up to 8 for T3
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.get(code as usize).copied()
+    }
+}
+
+// MA - Moving average of a type picked at runtime
+// real = MA(real, timeperiod=30, matype=0)
+// like TA-Lib, KAMA uses the fast/slow periods 2 and 30, MAMA the limits 0.5 and 0.05 and ignores
+// the period, T3 uses a vfactor of 0.7
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MA {
+    SMA(SMA),
+    EMA(EMA),
+    WMA(WMA),
+    DEMA(DEMA),
+    TEMA(TEMA),
+    TRIMA(TRIMA),
+    KAMA {
+        kama: KAMA,
+        #[serde(with = "crate::utils::float")]
+        preprice: f64,
+    },
+    MAMA(Box<MAMA>),
+    T3(T3),
+}
+
+impl MA {
+    pub fn new(timeperiod: usize, matype: MAType) -> Self {
+        match matype {
+            MAType::SMA => MA::SMA(SMA::new(timeperiod)),
+            MAType::EMA => MA::EMA(EMA::new(timeperiod)),
+            MAType::WMA => MA::WMA(WMA::new(timeperiod)),
+            MAType::DEMA => MA::DEMA(DEMA::new(timeperiod)),
+            MAType::TEMA => MA::TEMA(TEMA::new(timeperiod)),
+            MAType::TRIMA => MA::TRIMA(TRIMA::new(timeperiod)),
+            MAType::KAMA => MA::KAMA {
+                kama: KAMA::new(timeperiod, 2, 30),
+                preprice: f64::NAN,
+            },
+            MAType::MAMA => MA::MAMA(Box::default()),
+            MAType::T3 => MA::T3(T3::new(timeperiod, 0.7)),
+        }
+    }
+
+    // the averages that skip a NaN input return NaN for it, MA returns its value like value() does
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        match self {
+            MA::SMA(ma) => {
+                ma.update(new_val);
+            }
+            MA::EMA(ma) => {
+                ma.update(new_val);
+            }
+            MA::WMA(ma) => {
+                ma.update(new_val);
+            }
+            MA::DEMA(ma) => {
+                ma.update(new_val);
             }
+            MA::TEMA(ma) => {
+                ma.update(new_val);
+            }
+            MA::TRIMA(ma) => {
+                ma.update(new_val);
+            }
+            MA::KAMA { kama, preprice } => {
+                kama.update(new_val, *preprice);
+                if !is_nan_or_inf(new_val) {
+                    *preprice = new_val;
+                }
+            }
+            MA::MAMA(ma) => {
+                ma.update(new_val);
+            }
+            MA::T3(ma) => {
+                ma.update(new_val);
+            }
+        }
+
+        self.value()
+    }
+
+    pub fn matype(&self) -> MAType {
+        match self {
+            MA::SMA(_) => MAType::SMA,
+            MA::EMA(_) => MAType::EMA,
+            MA::WMA(_) => MAType::WMA,
+            MA::DEMA(_) => MAType::DEMA,
+            MA::TEMA(_) => MAType::TEMA,
+            MA::TRIMA(_) => MAType::TRIMA,
+            MA::KAMA { .. } => MAType::KAMA,
+            MA::MAMA(_) => MAType::MAMA,
+            MA::T3(_) => MAType::T3,
+        }
+    }
+}
+
+impl Indicator for MA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        match self {
+            MA::SMA(ma) => ma.value(),
+            MA::EMA(ma) => ma.value(),
+            MA::WMA(ma) => ma.value(),
+            MA::DEMA(ma) => ma.value(),
+            MA::TEMA(ma) => ma.value(),
+            MA::TRIMA(ma) => ma.value(),
+            MA::KAMA { kama, .. } => kama.value(),
+            MA::MAMA(ma) => ma.value().0,
+            MA::T3(ma) => ma.value(),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            MA::SMA(ma) => ma.reset(),
+            MA::EMA(ma) => ma.reset(),
+            MA::WMA(ma) => ma.reset(),
+            MA::DEMA(ma) => ma.reset(),
+            MA::TEMA(ma) => ma.reset(),
+            MA::TRIMA(ma) => ma.reset(),
+            MA::KAMA { kama, preprice } => {
+                kama.reset();
+                *preprice = f64::NAN;
+            }
+            MA::MAMA(ma) => ma.reset(),
+            MA::T3(ma) => ma.reset(),
+        }
+    }
+
+    fn lookback(&self) -> usize {
+        match self {
+            MA::SMA(ma) => ma.lookback(),
+            MA::EMA(ma) => ma.lookback(),
+            MA::WMA(ma) => ma.lookback(),
+            MA::DEMA(ma) => ma.lookback(),
+            MA::TEMA(ma) => ma.lookback(),
+            MA::TRIMA(ma) => ma.lookback(),
+            MA::KAMA { kama, .. } => kama.lookback(),
+            MA::MAMA(ma) => ma.lookback(),
+            MA::T3(ma) => ma.lookback(),
+        }
+    }
 
-            self.kama.unwrap()
+    fn count(&self) -> usize {
+        match self {
+            MA::SMA(ma) => ma.count(),
+            MA::EMA(ma) => ma.count(),
+            MA::WMA(ma) => ma.count(),
+            MA::DEMA(ma) => ma.count(),
+            MA::TEMA(ma) => ma.count(),
+            MA::TRIMA(ma) => ma.count(),
+            MA::KAMA { kama, .. } => kama.count(),
+            MA::MAMA(ma) => ma.count(),
+            MA::T3(ma) => ma.count(),
         }
     }
 }
 
-// MAMA - MESA Adaptive Moving Average, [TO DIFFICULT, must use hilbert_transform crate]
+// MAMA - MESA Adaptive Moving Average
 // NOTE: The MAMA function has an unstable period.
-// mama, fama = MAMA(real, fastlimit=0, slowlimit=0)
-#[pyclass]
-pub struct MAMA {}
+// mama, fama = MAMA(real, fastlimit=0.5, slowlimit=0.05)
+// the alpha is fastlimit divided by the change of the Hilbert phase, floored at slowlimit,
+// and fama follows mama with half that alpha
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MAMA {
+    hilbert: HilbertTransform,
+    #[serde(with = "crate::utils::float")]
+    fastlimit: f64,
+    #[serde(with = "crate::utils::float")]
+    slowlimit: f64,
+    #[serde(with = "crate::utils::float")]
+    phase: f64,
+    #[serde(with = "crate::utils::float")]
+    mama: f64,
+    #[serde(with = "crate::utils::float")]
+    fama: f64,
+}
 
-#[pymethods]
 impl MAMA {
-    #[new]
-    pub fn new(timeperiod: usize) -> Self {
-        Self {}
+    pub fn new(fastlimit: f64, slowlimit: f64) -> Self {
+        Self {
+            hilbert: HilbertTransform::new(9),
+            fastlimit,
+            slowlimit,
+            phase: 0.0,
+            mama: 0.0,
+            fama: 0.0,
+        }
     }
 
-    pub fn update(&mut self, new_val: f64) -> f64 {
-        1.0
+    pub fn update(&mut self, new_val: f64) -> (f64, f64) {
+        if !self.hilbert.update(new_val) {
+            return self.value();
+        }
+
+        let inphase = self.hilbert.inphase();
+        let phase = if inphase != 0.0 {
+            (self.hilbert.quadrature() / inphase).atan() * RAD2DEG
+        } else {
+            0.0
+        };
+        let delta_phase = (self.phase - phase).max(1.0);
+        self.phase = phase;
+
+        let alpha = if delta_phase > 1.0 {
+            (self.fastlimit / delta_phase).max(self.slowlimit)
+        } else {
+            self.fastlimit
+        };
+        self.mama = alpha * new_val + (1.0 - alpha) * self.mama;
+        let alpha = alpha * 0.5;
+        self.fama = alpha * self.mama + (1.0 - alpha) * self.fama;
+
+        self.value()
+    }
+}
+
+impl Default for MAMA {
+    fn default() -> Self {
+        Self::new(0.5, 0.05)
+    }
+}
+
+impl Indicator for MAMA {
+    type Input = f64;
+    type Output = (f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        if self.is_ready() {
+            (self.mama, self.fama)
+        } else {
+            (f64::NAN, f64::NAN)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.hilbert.reset();
+        self.phase = 0.0;
+        self.mama = 0.0;
+        self.fama = 0.0;
+    }
+
+    fn lookback(&self) -> usize {
+        32
+    }
+
+    fn count(&self) -> usize {
+        self.hilbert.count()
+    }
+}
+
+// MAVP - Moving average with variable period
+// real = MAVP(real, periods, minperiod=2, maxperiod=30, matype=0)
+// the period of each input is truncated and clamped to [minperiod, maxperiod]. like TA-Lib, which
+// runs a full MA for every distinct period, an MA per period is fed every input, so switching the
+// period picks up MA(period, matype) of the same stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MAVP {
+    minperiod: usize,
+    mas: Vec<MA>,
+    current: Option<usize>,
+}
+
+impl MAVP {
+    pub fn new(minperiod: usize, maxperiod: usize, matype: MAType) -> Self {
+        let minperiod = minperiod.max(1);
+        let maxperiod = maxperiod.max(minperiod);
+        Self {
+            minperiod,
+            mas: (minperiod..=maxperiod)
+                .map(|period| MA::new(period, matype))
+                .collect(),
+            current: None,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64, period: f64) -> f64 {
+        for ma in self.mas.iter_mut() {
+            ma.update(new_val);
+        }
+        self.current = if is_nan_or_inf(period) {
+            None
+        } else {
+            let maxperiod = self.minperiod + self.mas.len() - 1;
+            let period = (period.max(0.0) as usize).clamp(self.minperiod, maxperiod);
+            Some(period - self.minperiod)
+        };
+        self.value()
+    }
+
+    pub fn matype(&self) -> MAType {
+        self.mas[0].matype()
+    }
+}
+
+impl Indicator for MAVP {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, input: (f64, f64)) -> f64 {
+        self.update(input.0, input.1)
+    }
+
+    fn value(&self) -> f64 {
+        match self.current {
+            Some(index) if self.is_ready() => self.mas[index].value(),
+            _ => f64::NAN,
+        }
+    }
+
+    fn reset(&mut self) {
+        for ma in self.mas.iter_mut() {
+            ma.reset();
+        }
+        self.current = None;
+    }
+
+    // the lookback of the longest period
+    fn lookback(&self) -> usize {
+        self.mas[self.mas.len() - 1].lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.mas[0].count()
     }
 }
 
 // MIDPOINT - MidPoint over period
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MIDPOINT {
     maxer: rolling::minmax::Maxer,
     miner: rolling::minmax::Miner,
 }
 
-#[pymethods]
 impl MIDPOINT {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             maxer: rolling::minmax::Maxer::new(timeperiod),
@@ -184,16 +726,40 @@ impl MIDPOINT {
     }
 }
 
+impl Indicator for MIDPOINT {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        (self.maxer.value() + self.miner.value()) / 2.0
+    }
+
+    fn reset(&mut self) {
+        self.maxer.reset();
+        self.miner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.maxer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.maxer.count()
+    }
+}
+
 // MIDPRICE - Midpoint Price over period
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MIDPRICE {
     maxer: rolling::minmax::Maxer,
     miner: rolling::minmax::Miner,
 }
 
-#[pymethods]
 impl MIDPRICE {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             maxer: rolling::minmax::Maxer::new(timeperiod),
@@ -206,16 +772,306 @@ impl MIDPRICE {
     }
 }
 
+impl Indicator for MIDPRICE {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low): (f64, f64)) -> f64 {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> f64 {
+        (self.maxer.value() + self.miner.value()) / 2.0
+    }
+
+    fn reset(&mut self) {
+        self.maxer.reset();
+        self.miner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.maxer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.maxer.count()
+    }
+}
+
+// SAR - Parabolic SAR
+// real = SAR(high, low, acceleration=0.02, maximum=0.2)
+// outputs (stop, direction), direction is 1 in a long trend and -1 in a short one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SAR {
+    sarext: SAREXT,
+}
+
+impl SAR {
+    pub fn new(acceleration: f64, maximum: f64) -> Self {
+        Self {
+            sarext: SAREXT::new(
+                0.0,
+                0.0,
+                acceleration,
+                acceleration,
+                maximum,
+                acceleration,
+                acceleration,
+                maximum,
+            ),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64) -> (f64, f64) {
+        self.sarext.update(high, low)
+    }
+}
+
+impl Default for SAR {
+    fn default() -> Self {
+        Self::new(0.02, 0.2)
+    }
+}
+
+impl Indicator for SAR {
+    type Input = (f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low): (f64, f64)) -> (f64, f64) {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        self.sarext.value()
+    }
+
+    fn reset(&mut self) {
+        self.sarext.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.sarext.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.sarext.count()
+    }
+}
+
+// SAREXT - Parabolic SAR - Extended
+// real = SAREXT(high, low, startvalue=0, offsetonreverse=0, accelerationinitlong=0.02, accelerationlong=0.02,
+//     accelerationmaxlong=0.2, accelerationinitshort=0.02, accelerationshort=0.02, accelerationmaxshort=0.2)
+// startvalue 0 picks the first trend from the directional movement of the first two bars,
+// a positive one starts long at that stop and a negative one starts short at its absolute value.
+// on a reversal the stop is moved away from the price by offsetonreverse times itself.
+// outputs (stop, direction) with a positive stop, TA-Lib's SAREXT is stop * direction.
+// bars with a NaN input are ignored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SAREXT {
+    #[serde(with = "crate::utils::float")]
+    start_value: f64,
+    #[serde(with = "crate::utils::float")]
+    offset_on_reverse: f64,
+    #[serde(with = "crate::utils::float")]
+    af_init_long: f64,
+    #[serde(with = "crate::utils::float")]
+    af_step_long: f64,
+    #[serde(with = "crate::utils::float")]
+    af_max_long: f64,
+    #[serde(with = "crate::utils::float")]
+    af_init_short: f64,
+    #[serde(with = "crate::utils::float")]
+    af_step_short: f64,
+    #[serde(with = "crate::utils::float")]
+    af_max_short: f64,
+    count: usize,
+    #[serde(with = "crate::utils::float")]
+    prev_high: f64,
+    #[serde(with = "crate::utils::float")]
+    prev_low: f64,
+    is_long: bool,
+    #[serde(with = "crate::utils::float")]
+    sar: f64,
+    #[serde(with = "crate::utils::float")]
+    ep: f64,
+    #[serde(with = "crate::utils::float")]
+    af_long: f64,
+    #[serde(with = "crate::utils::float")]
+    af_short: f64,
+    #[serde(with = "crate::utils::float")]
+    stop: f64,
+}
+
+impl SAREXT {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        start_value: f64,
+        offset_on_reverse: f64,
+        af_init_long: f64,
+        af_step_long: f64,
+        af_max_long: f64,
+        af_init_short: f64,
+        af_step_short: f64,
+        af_max_short: f64,
+    ) -> Self {
+        // like TA-Lib, the accelerations are capped by their maximum
+        let af_init_long = af_init_long.min(af_max_long);
+        let af_init_short = af_init_short.min(af_max_short);
+        Self {
+            start_value,
+            offset_on_reverse,
+            af_init_long,
+            af_step_long: af_step_long.min(af_max_long),
+            af_max_long,
+            af_init_short,
+            af_step_short: af_step_short.min(af_max_short),
+            af_max_short,
+            count: 0,
+            prev_high: f64::NAN,
+            prev_low: f64::NAN,
+            is_long: true,
+            sar: f64::NAN,
+            ep: f64::NAN,
+            af_long: af_init_long,
+            af_short: af_init_short,
+            stop: f64::NAN,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64) -> (f64, f64) {
+        if is_nan_or_inf(high) || is_nan_or_inf(low) {
+            return self.value();
+        }
+
+        self.count += 1;
+        let (prev_high, prev_low) = if self.count == 1 {
+            self.prev_high = high;
+            self.prev_low = low;
+            return self.value();
+        } else if self.count == 2 {
+            self.start(high, low);
+            // the first stop is checked against the second bar alone
+            (high, low)
+        } else {
+            (self.prev_high, self.prev_low)
+        };
+        self.prev_high = high;
+        self.prev_low = low;
+
+        if self.is_long {
+            if low <= self.sar {
+                self.is_long = false;
+                self.sar = self.ep.max(prev_high).max(high);
+                if self.offset_on_reverse != 0.0 {
+                    self.sar += self.sar * self.offset_on_reverse;
+                }
+                self.stop = self.sar;
+                self.af_short = self.af_init_short;
+                self.ep = low;
+                self.sar += self.af_short * (self.ep - self.sar);
+                self.sar = self.sar.max(prev_high).max(high);
+            } else {
+                self.stop = self.sar;
+                if high > self.ep {
+                    self.ep = high;
+                    self.af_long = (self.af_long + self.af_step_long).min(self.af_max_long);
+                }
+                self.sar += self.af_long * (self.ep - self.sar);
+                self.sar = self.sar.min(prev_low).min(low);
+            }
+        } else if high >= self.sar {
+            self.is_long = true;
+            self.sar = self.ep.min(prev_low).min(low);
+            if self.offset_on_reverse != 0.0 {
+                self.sar -= self.sar * self.offset_on_reverse;
+            }
+            self.stop = self.sar;
+            self.af_long = self.af_init_long;
+            self.ep = high;
+            self.sar += self.af_long * (self.ep - self.sar);
+            self.sar = self.sar.min(prev_low).min(low);
+        } else {
+            self.stop = self.sar;
+            if low < self.ep {
+                self.ep = low;
+                self.af_short = (self.af_short + self.af_step_short).min(self.af_max_short);
+            }
+            self.sar += self.af_short * (self.ep - self.sar);
+            self.sar = self.sar.max(prev_high).max(high);
+        }
+
+        self.value()
+    }
+
+    // the trend, stop and extreme point before the second bar is processed
+    fn start(&mut self, high: f64, low: f64) {
+        if self.start_value == 0.0 {
+            let minus_dm = self.prev_low - low;
+            self.is_long = !(minus_dm > 0.0 && minus_dm > high - self.prev_high);
+            self.sar = if self.is_long {
+                self.prev_low
+            } else {
+                self.prev_high
+            };
+        } else {
+            self.is_long = self.start_value > 0.0;
+            self.sar = self.start_value.abs();
+        }
+        self.ep = if self.is_long { high } else { low };
+    }
+}
+
+impl Default for SAREXT {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.02, 0.02, 0.2, 0.02, 0.02, 0.2)
+    }
+}
+
+impl Indicator for SAREXT {
+    type Input = (f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low): (f64, f64)) -> (f64, f64) {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        if self.count > 1 {
+            (self.stop, if self.is_long { 1.0 } else { -1.0 })
+        } else {
+            (f64::NAN, f64::NAN)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.count = 0;
+        self.prev_high = f64::NAN;
+        self.prev_low = f64::NAN;
+        self.is_long = true;
+        self.sar = f64::NAN;
+        self.ep = f64::NAN;
+        self.af_long = self.af_init_long;
+        self.af_short = self.af_init_short;
+        self.stop = f64::NAN;
+    }
+
+    fn lookback(&self) -> usize {
+        1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
 // SMA - Simple Moving Average
 // real = SMA(real, timeperiod=30)
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SMA {
     meaner: rolling::statis::Meaner,
 }
 
-#[pymethods]
 impl SMA {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             meaner: rolling::statis::Meaner::new(timeperiod),
@@ -227,9 +1083,34 @@ impl SMA {
     }
 }
 
+impl Indicator for SMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.meaner.value()
+    }
+
+    fn reset(&mut self) {
+        self.meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.meaner.count()
+    }
+}
+
 // T3 - Triple Exponential Moving Average (T3)
 // NOTE: The T3 function has an unstable period, typical vfactor is 0.7
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct T3 {
     ema_lv1: EMA,
     ema_lv2: EMA,
@@ -237,15 +1118,17 @@ pub struct T3 {
     ema_lv4: EMA,
     ema_lv5: EMA,
     ema_lv6: EMA,
+    #[serde(with = "crate::utils::float")]
     c1: f64,
+    #[serde(with = "crate::utils::float")]
     c2: f64,
+    #[serde(with = "crate::utils::float")]
     c3: f64,
+    #[serde(with = "crate::utils::float")]
     c4: f64,
 }
 
-#[pymethods]
 impl T3 {
-    #[new]
     pub fn new(timeperiod: usize, vfactor: f64) -> Self {
         Self {
             ema_lv1: EMA::new(timeperiod),
@@ -273,18 +1156,54 @@ impl T3 {
     }
 }
 
+impl Indicator for T3 {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.c1 * self.ema_lv6.value()
+            + self.c2 * self.ema_lv5.value()
+            + self.c3 * self.ema_lv4.value()
+            + self.c4 * self.ema_lv3.value()
+    }
+
+    fn reset(&mut self) {
+        self.ema_lv1.reset();
+        self.ema_lv2.reset();
+        self.ema_lv3.reset();
+        self.ema_lv4.reset();
+        self.ema_lv5.reset();
+        self.ema_lv6.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ema_lv1.lookback()
+            + self.ema_lv2.lookback()
+            + self.ema_lv3.lookback()
+            + self.ema_lv4.lookback()
+            + self.ema_lv5.lookback()
+            + self.ema_lv6.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ema_lv1.count()
+    }
+}
+
 // TEMA: Triple Exponential Moving Average
 // NOTE: The TEMA function has an unstable period, different from talib.T3
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TEMA {
     ema_lv1: EMA,
     ema_lv2: EMA,
     ema_lv3: EMA,
 }
 
-#[pymethods]
 impl TEMA {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             ema_lv1: EMA::new(timeperiod),
@@ -302,17 +1221,42 @@ impl TEMA {
     }
 }
 
+impl Indicator for TEMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        3.0 * self.ema_lv1.value() - 3.0 * self.ema_lv2.value() + self.ema_lv3.value()
+    }
+
+    fn reset(&mut self) {
+        self.ema_lv1.reset();
+        self.ema_lv2.reset();
+        self.ema_lv3.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ema_lv1.lookback() + self.ema_lv2.lookback() + self.ema_lv3.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ema_lv1.count()
+    }
+}
+
 // TRIMA - Triangular Moving Average
 // SMA(SMA(timeperiod))
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TRIMA {
     sma1: SMA,
     sma2: SMA,
 }
 
-#[pymethods]
 impl TRIMA {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             sma1: SMA::new(timeperiod),
@@ -325,19 +1269,45 @@ impl TRIMA {
     }
 }
 
+impl Indicator for TRIMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.sma2.value()
+    }
+
+    fn reset(&mut self) {
+        self.sma1.reset();
+        self.sma2.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.sma1.lookback() + self.sma2.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.sma1.count()
+    }
+}
+
 // WMA - Weighted Moving Average
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WMA {
     container: rolling::container::Container,
     n: usize,
     nan_count: usize,
+    #[serde(with = "crate::utils::float")]
     sum: f64,
+    #[serde(with = "crate::utils::float")]
     weighted_sum: f64,
 }
 
-#[pymethods]
 impl WMA {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             container: rolling::container::Container::new(n),
@@ -366,10 +1336,38 @@ impl WMA {
             self.sum += new_val;
         }
 
+        self.value()
+    }
+}
+
+impl Indicator for WMA {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
         if self.nan_count > 0 {
-            NAN
+            f64::NAN
         } else {
             self.weighted_sum / (self.n * (self.n + 1)) as f64 * 2.0
         }
     }
+
+    fn reset(&mut self) {
+        self.container.reset();
+        self.nan_count = self.n;
+        self.sum = 0.0;
+        self.weighted_sum = 0.0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.container.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.container.count()
+    }
 }
@@ -1,61 +1,218 @@
-use pyo3::prelude::*;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
-pub struct AvgPrice {}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvgPrice {
+    #[serde(with = "crate::utils::float")]
+    price: f64,
+    count: usize,
+}
 
-#[pymethods]
 impl AvgPrice {
-    #[new]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            price: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> f64 {
-        (open + high + low + close) / 4.0
+        self.price = (open + high + low + close) / 4.0;
+        self.count += 1;
+        self.price
+    }
+}
+
+impl Default for AvgPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for AvgPrice {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.price
+    }
+
+    fn reset(&mut self) {
+        self.price = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
     }
 }
 
-#[pyclass]
-pub struct MedPrice {}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedPrice {
+    #[serde(with = "crate::utils::float")]
+    price: f64,
+    count: usize,
+}
 
-#[pymethods]
 impl MedPrice {
-    #[new]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            price: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, high: f64, low: f64) -> f64 {
-        (high + low) / 2.0
+        self.price = (high + low) / 2.0;
+        self.count += 1;
+        self.price
+    }
+}
+
+impl Default for MedPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for MedPrice {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low): (f64, f64)) -> f64 {
+        self.update(high, low)
+    }
+
+    fn value(&self) -> f64 {
+        self.price
+    }
+
+    fn reset(&mut self) {
+        self.price = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
     }
 }
 
-#[pyclass]
-pub struct TypicalPrice {}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypicalPrice {
+    #[serde(with = "crate::utils::float")]
+    price: f64,
+    count: usize,
+}
 
-#[pymethods]
 impl TypicalPrice {
-    #[new]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            price: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
-        (high + low + close) / 3.0
+        self.price = (high + low + close) / 3.0;
+        self.count += 1;
+        self.price
+    }
+}
+
+impl Default for TypicalPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for TypicalPrice {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.price
+    }
+
+    fn reset(&mut self) {
+        self.price = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
     }
 }
 
-#[pyclass]
-pub struct WeightedClose {}
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedClose {
+    #[serde(with = "crate::utils::float")]
+    price: f64,
+    count: usize,
+}
 
-#[pymethods]
 impl WeightedClose {
-    #[new]
     pub fn new() -> Self {
-        Self {}
+        Self {
+            price: f64::NAN,
+            count: 0,
+        }
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
-        (high + low + 2.0 * close) / 4.0
+        self.price = (high + low + 2.0 * close) / 4.0;
+        self.count += 1;
+        self.price
+    }
+}
+
+impl Default for WeightedClose {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for WeightedClose {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.price
+    }
+
+    fn reset(&mut self) {
+        self.price = f64::NAN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
     }
 }
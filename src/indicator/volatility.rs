@@ -1,43 +1,76 @@
-use crate::rolling;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::{rolling, Indicator};
+use serde::{Deserialize, Serialize};
 
 // True Range
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TR {
     close_vec: rolling::container::Container,
+    #[serde(with = "crate::utils::float")]
+    tr: f64,
 }
 
-#[pymethods]
 impl TR {
-    #[new]
     pub fn new() -> Self {
         Self {
             close_vec: rolling::container::Container::new(2),
+            tr: f64::NAN,
         }
     }
 
     pub fn update(&mut self, high: f64, low: f64, close: f64) -> f64 {
         self.close_vec.update(close);
         let preclose = self.close_vec.head();
-        (high - low)
+        self.tr = (high - low)
             .max((high - preclose).abs())
-            .max((low - preclose).abs())
+            .max((low - preclose).abs());
+        self.tr
+    }
+}
+
+impl Default for TR {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for TR {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.tr
+    }
+
+    fn reset(&mut self) {
+        self.close_vec.reset();
+        self.tr = f64::NAN;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.close_vec.count()
     }
 }
 
 // Average True Range
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ATR {
     n: usize,
+    #[serde(with = "crate::utils::float")]
     atr: Option<f64>,
+    #[serde(with = "crate::utils::float")]
     init_trs: Vec<f64>,
     close_vec: rolling::container::Container,
 }
 
-#[pymethods]
 impl ATR {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             n: period,
@@ -62,22 +95,49 @@ impl ATR {
                 self.atr = Some(self.init_trs.iter().sum::<f64>() / self.n as f64);
             }
         }
-        self.atr.unwrap_or(NAN)
+        self.value()
+    }
+}
+
+impl Indicator for ATR {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        self.atr.unwrap_or(f64::NAN)
+    }
+
+    fn reset(&mut self) {
+        self.atr = None;
+        self.init_trs.clear();
+        self.close_vec.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.n - 1
+    }
+
+    fn count(&self) -> usize {
+        self.close_vec.count()
     }
 }
 
 // NATR - Normalized Average True Range
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NATR {
     n: usize,
+    #[serde(with = "crate::utils::float")]
     atr: Option<f64>,
+    #[serde(with = "crate::utils::float")]
     init_trs: Vec<f64>,
     close_vec: rolling::container::Container,
 }
 
-#[pymethods]
 impl NATR {
-    #[new]
     pub fn new(period: usize) -> Self {
         Self {
             n: period,
@@ -102,6 +162,328 @@ impl NATR {
                 self.atr = Some(self.init_trs.iter().sum::<f64>() / self.n as f64);
             }
         }
-        self.atr.unwrap_or(NAN) / close
+        self.value()
+    }
+}
+
+impl Indicator for NATR {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close): (f64, f64, f64)) -> f64 {
+        self.update(high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        // the close of the current bar is the tail of close_vec
+        self.atr.unwrap_or(f64::NAN) / self.close_vec.tail()
+    }
+
+    fn reset(&mut self) {
+        self.atr = None;
+        self.init_trs.clear();
+        self.close_vec.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.n - 1
+    }
+
+    fn count(&self) -> usize {
+        self.close_vec.count()
+    }
+}
+
+// the range-based estimators below give the volatility of log returns over the last `timeperiod`
+// bars, scaled by sqrt(annualization), e.g. 252 for daily bars or 1.0 for the volatility of one bar
+fn annualized(variance: f64, annualization: f64) -> f64 {
+    // rounding can make a variance slightly negative, NaN stays NaN
+    let variance = if variance < 0.0 { 0.0 } else { variance };
+    (variance * annualization).sqrt()
+}
+
+// Parkinson volatility
+// variance = mean(ln(high / low)^2) / (4 * ln(2)), from the range only
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Parkinson {
+    meaner: rolling::statis::Meaner,
+    #[serde(with = "crate::utils::float")]
+    annualization: f64,
+}
+
+impl Parkinson {
+    pub fn new(timeperiod: usize, annualization: f64) -> Self {
+        Self {
+            meaner: rolling::statis::Meaner::new(timeperiod),
+            annualization,
+        }
+    }
+
+    pub fn update(&mut self, _open: f64, high: f64, low: f64, _close: f64) -> f64 {
+        let range = (high / low).ln();
+        self.meaner.update(range * range);
+
+        self.value()
+    }
+}
+
+impl Indicator for Parkinson {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        annualized(
+            self.meaner.value() / (4.0 * std::f64::consts::LN_2),
+            self.annualization,
+        )
+    }
+
+    fn reset(&mut self) {
+        self.meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.meaner.count()
+    }
+}
+
+// Garman-Klass volatility
+// variance = mean(0.5 * ln(high / low)^2 - (2 * ln(2) - 1) * ln(close / open)^2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GarmanKlass {
+    meaner: rolling::statis::Meaner,
+    #[serde(with = "crate::utils::float")]
+    annualization: f64,
+}
+
+impl GarmanKlass {
+    pub fn new(timeperiod: usize, annualization: f64) -> Self {
+        Self {
+            meaner: rolling::statis::Meaner::new(timeperiod),
+            annualization,
+        }
+    }
+
+    pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> f64 {
+        let range = (high / low).ln();
+        let body = (close / open).ln();
+        self.meaner
+            .update(0.5 * range * range - (2.0 * std::f64::consts::LN_2 - 1.0) * body * body);
+
+        self.value()
+    }
+}
+
+impl Indicator for GarmanKlass {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        annualized(self.meaner.value(), self.annualization)
+    }
+
+    fn reset(&mut self) {
+        self.meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.meaner.count()
+    }
+}
+
+// Rogers-Satchell volatility
+// variance = mean(ln(high / close) * ln(high / open) + ln(low / close) * ln(low / open)),
+// unbiased under a drift
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RogersSatchell {
+    meaner: rolling::statis::Meaner,
+    #[serde(with = "crate::utils::float")]
+    annualization: f64,
+}
+
+impl RogersSatchell {
+    pub fn new(timeperiod: usize, annualization: f64) -> Self {
+        Self {
+            meaner: rolling::statis::Meaner::new(timeperiod),
+            annualization,
+        }
+    }
+
+    pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> f64 {
+        self.meaner.update(rogers_satchell(open, high, low, close));
+
+        self.value()
+    }
+}
+
+fn rogers_satchell(open: f64, high: f64, low: f64, close: f64) -> f64 {
+    (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+}
+
+impl Indicator for RogersSatchell {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        annualized(self.meaner.value(), self.annualization)
+    }
+
+    fn reset(&mut self) {
+        self.meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.meaner.count()
+    }
+}
+
+// Yang-Zhang volatility
+// variance = overnight + k * open-to-close + (1 - k) * Rogers-Satchell, with the sample variances
+// of ln(open / preclose) and ln(close / open) and k = 0.34 / (1.34 + (n + 1) / (n - 1)).
+// the first bar has no previous close
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YangZhang {
+    close_vec: rolling::container::Container,
+    overnight_stder: rolling::statis::Stder,
+    body_stder: rolling::statis::Stder,
+    rs_meaner: rolling::statis::Meaner,
+    #[serde(with = "crate::utils::float")]
+    k: f64,
+    #[serde(with = "crate::utils::float")]
+    annualization: f64,
+}
+
+impl YangZhang {
+    pub fn new(timeperiod: usize, annualization: f64) -> Self {
+        let n = timeperiod as f64;
+        Self {
+            close_vec: rolling::container::Container::new(2),
+            overnight_stder: rolling::statis::Stder::new(timeperiod),
+            body_stder: rolling::statis::Stder::new(timeperiod),
+            rs_meaner: rolling::statis::Meaner::new(timeperiod),
+            k: 0.34 / (1.34 + (n + 1.0) / (n - 1.0)),
+            annualization,
+        }
+    }
+
+    pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> f64 {
+        self.close_vec.update(close);
+        let preclose = self.close_vec.head();
+        self.overnight_stder.update((open / preclose).ln());
+        self.body_stder.update((close / open).ln());
+        self.rs_meaner
+            .update(rogers_satchell(open, high, low, close));
+
+        self.value()
+    }
+}
+
+impl Indicator for YangZhang {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        let overnight = self.overnight_stder.value();
+        let body = self.body_stder.value();
+        let variance =
+            overnight * overnight + self.k * body * body + (1.0 - self.k) * self.rs_meaner.value();
+        annualized(variance, self.annualization)
+    }
+
+    fn reset(&mut self) {
+        self.close_vec.reset();
+        self.overnight_stder.reset();
+        self.body_stder.reset();
+        self.rs_meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        1 + self.overnight_stder.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.close_vec.count()
+    }
+}
+
+// Close-to-close realized volatility
+// the sample std of ln(close / preclose) over the period, the first bar has no previous close
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloseToClose {
+    close_vec: rolling::container::Container,
+    stder: rolling::statis::Stder,
+    #[serde(with = "crate::utils::float")]
+    annualization: f64,
+}
+
+impl CloseToClose {
+    pub fn new(timeperiod: usize, annualization: f64) -> Self {
+        Self {
+            close_vec: rolling::container::Container::new(2),
+            stder: rolling::statis::Stder::new(timeperiod),
+            annualization,
+        }
+    }
+
+    pub fn update(&mut self, _open: f64, _high: f64, _low: f64, close: f64) -> f64 {
+        self.close_vec.update(close);
+        self.stder.update((close / self.close_vec.head()).ln());
+
+        self.value()
+    }
+}
+
+impl Indicator for CloseToClose {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> f64 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> f64 {
+        let std = self.stder.value();
+        annualized(std * std, self.annualization)
+    }
+
+    fn reset(&mut self) {
+        self.close_vec.reset();
+        self.stder.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        1 + self.stder.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.close_vec.count()
     }
 }
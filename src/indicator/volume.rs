@@ -1,41 +1,72 @@
-use super::overlap::EMA;
-use crate::{rolling, utils::sign};
-use pyo3::prelude::*;
+use super::overlap::{EMA, SMA};
+use crate::{
+    cum, rolling,
+    utils::{is_nan_or_inf, sign},
+    Indicator,
+};
+use serde::{Deserialize, Serialize};
 
 // AD - Chaikin A/D Line
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AD {
     sumer: rolling::statis::Sumer,
 }
 
-#[pymethods]
 impl AD {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             sumer: rolling::statis::Sumer::new(timeperiod),
         }
     }
 
-    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: usize) -> f64 {
-        let mfm = (2.0 * close - low - high) / (high - low);
-        let mfv = mfm * volume as f64;
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> f64 {
+        // a flat bar moves no money, as in TA-Lib
+        let range = high - low;
+        let mfm = if range > 0.0 {
+            (2.0 * close - low - high) / range
+        } else {
+            0.0
+        };
+        let mfv = mfm * volume;
 
         self.sumer.update(mfv)
     }
 }
 
+impl Indicator for AD {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close, volume): (f64, f64, f64, f64)) -> f64 {
+        self.update(high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.sumer.count()
+    }
+}
+
 // Chaikin A/D Oscillator
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ADOSC {
     ema_fast: EMA,
     ema_slow: EMA,
     ader: AD,
 }
 
-#[pymethods]
 impl ADOSC {
-    #[new]
     pub fn new(timeperiod: usize, fastperiod: usize, slowperiod: usize) -> Self {
         Self {
             ema_fast: EMA::new(fastperiod),
@@ -44,23 +75,48 @@ impl ADOSC {
         }
     }
 
-    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: usize) -> f64 {
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> f64 {
         let ad_value = self.ader.update(high, low, close, volume);
 
         self.ema_fast.update(ad_value) - self.ema_slow.update(ad_value)
     }
 }
 
+impl Indicator for ADOSC {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close, volume): (f64, f64, f64, f64)) -> f64 {
+        self.update(high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.ema_fast.value() - self.ema_slow.value()
+    }
+
+    fn reset(&mut self) {
+        self.ema_fast.reset();
+        self.ema_slow.reset();
+        self.ader.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.ader.lookback() + self.ema_slow.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.ader.count()
+    }
+}
+
 // OBV - On Balance Volume
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OBV {
     obv_sumer: rolling::statis::Sumer,
     close_deltaer: rolling::delta::Deltaer,
 }
 
-#[pymethods]
 impl OBV {
-    #[new]
     pub fn new(timeperiod: usize) -> Self {
         Self {
             obv_sumer: rolling::statis::Sumer::new(timeperiod),
@@ -68,9 +124,1038 @@ impl OBV {
         }
     }
 
-    pub fn update(&mut self, close: f64, volume: usize) -> f64 {
-        let signed_volume = sign(self.close_deltaer.update(close)) as f64 * volume as f64;
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        let signed_volume = sign(self.close_deltaer.update(close)) as f64 * volume;
 
         self.obv_sumer.update(signed_volume)
     }
 }
+
+impl Indicator for OBV {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (close, volume): (f64, f64)) -> f64 {
+        self.update(close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.obv_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.obv_sumer.reset();
+        self.close_deltaer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.obv_sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.obv_sumer.count()
+    }
+}
+
+// CMF - Chaikin Money Flow
+// sum of the money flow volume over the period divided by the sum of the volume,
+// the money flow multiplier ((close - low) - (high - close)) / (high - low) is 0 for a flat bar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CMF {
+    mfv_sumer: rolling::statis::Sumer,
+    volume_sumer: rolling::statis::Sumer,
+}
+
+impl CMF {
+    pub fn new(timeperiod: usize) -> Self {
+        Self {
+            mfv_sumer: rolling::statis::Sumer::new(timeperiod),
+            volume_sumer: rolling::statis::Sumer::new(timeperiod),
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> f64 {
+        let range = high - low;
+        let mfm = if range > 0.0 {
+            (2.0 * close - low - high) / range
+        } else {
+            0.0
+        };
+        self.mfv_sumer.update(mfm * volume);
+        self.volume_sumer.update(volume);
+
+        self.value()
+    }
+}
+
+impl Indicator for CMF {
+    type Input = (f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, close, volume): (f64, f64, f64, f64)) -> f64 {
+        self.update(high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.mfv_sumer.value() / self.volume_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.mfv_sumer.reset();
+        self.volume_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.mfv_sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.mfv_sumer.count()
+    }
+}
+
+// FI - Force Index
+// EMA of the close change times the volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FI {
+    close_deltaer: rolling::delta::Deltaer,
+    ema: EMA,
+}
+
+impl FI {
+    pub fn new(timeperiod: usize) -> Self {
+        Self {
+            close_deltaer: rolling::delta::Deltaer::new(2),
+            ema: EMA::new(timeperiod),
+        }
+    }
+
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        let force = self.close_deltaer.update(close) * volume;
+        self.ema.update(force);
+
+        self.value()
+    }
+}
+
+impl Indicator for FI {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (close, volume): (f64, f64)) -> f64 {
+        self.update(close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.ema.value()
+    }
+
+    fn reset(&mut self) {
+        self.close_deltaer.reset();
+        self.ema.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.close_deltaer.lookback() + self.ema.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.close_deltaer.count()
+    }
+}
+
+// EMV - Ease of Movement
+// SMA of the midpoint move divided by the box ratio (volume / scale) / (high - low),
+// a scale of 100_000_000 keeps stock volumes readable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EMV {
+    midpoint_deltaer: rolling::delta::Deltaer,
+    sma: SMA,
+    #[serde(with = "crate::utils::float")]
+    scale: f64,
+}
+
+impl EMV {
+    pub fn new(timeperiod: usize, scale: f64) -> Self {
+        Self {
+            midpoint_deltaer: rolling::delta::Deltaer::new(2),
+            sma: SMA::new(timeperiod),
+            scale,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, volume: f64) -> f64 {
+        let distance = self.midpoint_deltaer.update((high + low) / 2.0);
+        // the distance over the box ratio, written so a flat bar moves nothing.
+        // a bar without volume moves nothing either instead of an infinite ease
+        let ease = if volume == 0.0 && !distance.is_nan() {
+            0.0
+        } else {
+            distance * (high - low) * self.scale / volume
+        };
+        self.sma.update(ease)
+    }
+}
+
+impl Indicator for EMV {
+    type Input = (f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (high, low, volume): (f64, f64, f64)) -> f64 {
+        self.update(high, low, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.sma.value()
+    }
+
+    fn reset(&mut self) {
+        self.midpoint_deltaer.reset();
+        self.sma.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.midpoint_deltaer.lookback() + self.sma.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.midpoint_deltaer.count()
+    }
+}
+
+// PVT - Price Volume Trend
+// cumulative sum of the close percentage change times the volume, 0 at the first bar.
+// bars with a NaN close or volume are ignored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PVT {
+    #[serde(with = "crate::utils::float")]
+    preclose: Option<f64>,
+    #[serde(with = "crate::utils::float")]
+    pvt: f64,
+    count: usize,
+}
+
+impl PVT {
+    pub fn new() -> Self {
+        Self {
+            preclose: None,
+            pvt: 0.0,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        if is_nan_or_inf(close) || is_nan_or_inf(volume) {
+            return self.value();
+        }
+        self.count += 1;
+        if let Some(preclose) = self.preclose {
+            self.pvt += (close / preclose - 1.0) * volume;
+        }
+        self.preclose = Some(close);
+
+        self.value()
+    }
+}
+
+impl Default for PVT {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for PVT {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (close, volume): (f64, f64)) -> f64 {
+        self.update(close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        match self.preclose {
+            Some(_) => self.pvt,
+            None => f64::NAN,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.preclose = None;
+        self.pvt = 0.0;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// VolumeIndex is the index behind NVI and PVI: it starts at 1000 and follows the close
+// percentage change on the bars whose volume moved in one direction.
+// bars with a NaN close or volume are ignored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VolumeIndex {
+    #[serde(with = "crate::utils::float")]
+    preclose: Option<f64>,
+    #[serde(with = "crate::utils::float")]
+    prevolume: f64,
+    #[serde(with = "crate::utils::float")]
+    index: f64,
+    count: usize,
+}
+
+impl VolumeIndex {
+    fn new() -> Self {
+        Self {
+            preclose: None,
+            prevolume: 0.0,
+            index: 1000.0,
+            count: 0,
+        }
+    }
+
+    // `sign` is -1 for a volume decrease (NVI), 1 for an increase (PVI)
+    fn update(&mut self, close: f64, volume: f64, sign: f64) -> f64 {
+        if is_nan_or_inf(close) || is_nan_or_inf(volume) {
+            return self.value();
+        }
+        self.count += 1;
+        if let Some(preclose) = self.preclose {
+            if (volume - self.prevolume) * sign > 0.0 {
+                self.index *= close / preclose;
+            }
+        }
+        self.preclose = Some(close);
+        self.prevolume = volume;
+
+        self.value()
+    }
+
+    fn value(&self) -> f64 {
+        match self.preclose {
+            Some(_) => self.index,
+            None => f64::NAN,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+// NVI - Negative Volume Index
+// starts at 1000 and follows the close on the bars where the volume fell
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NVI {
+    index: VolumeIndex,
+}
+
+impl NVI {
+    pub fn new() -> Self {
+        Self {
+            index: VolumeIndex::new(),
+        }
+    }
+
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        self.index.update(close, volume, -1.0)
+    }
+}
+
+impl Default for NVI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for NVI {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (close, volume): (f64, f64)) -> f64 {
+        self.update(close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.index.value()
+    }
+
+    fn reset(&mut self) {
+        self.index.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.index.count
+    }
+}
+
+// PVI - Positive Volume Index
+// starts at 1000 and follows the close on the bars where the volume rose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PVI {
+    index: VolumeIndex,
+}
+
+impl PVI {
+    pub fn new() -> Self {
+        Self {
+            index: VolumeIndex::new(),
+        }
+    }
+
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        self.index.update(close, volume, 1.0)
+    }
+}
+
+impl Default for PVI {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for PVI {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (close, volume): (f64, f64)) -> f64 {
+        self.update(close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.index.value()
+    }
+
+    fn reset(&mut self) {
+        self.index.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.index.count
+    }
+}
+
+// KVO - Klinger Volume Oscillator
+// kvo, signal = KVO(high, low, close, volume, fastperiod=34, slowperiod=55, signalperiod=13)
+// trend is 1 when high + low + close rose and -1 otherwise, dm = high - low and cm adds up dm while
+// the trend holds, restarting from the previous dm when it turns.
+// volume force = volume * |2 * (dm / cm - 1)| * trend * 100, kvo = EMA(vf, fast) - EMA(vf, slow),
+// signal = EMA(kvo, signal). bars with a NaN input are ignored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KVO {
+    ema_fast: EMA,
+    ema_slow: EMA,
+    ema_signal: EMA,
+    #[serde(with = "crate::utils::float")]
+    prehlc: Option<f64>,
+    #[serde(with = "crate::utils::float")]
+    predm: f64,
+    #[serde(with = "crate::utils::float")]
+    cm: f64,
+    trend: i8,
+    count: usize,
+}
+
+impl KVO {
+    pub fn new(fastperiod: usize, slowperiod: usize, signalperiod: usize) -> Self {
+        Self {
+            ema_fast: EMA::new(fastperiod),
+            ema_slow: EMA::new(slowperiod),
+            ema_signal: EMA::new(signalperiod),
+            prehlc: None,
+            predm: 0.0,
+            cm: 0.0,
+            trend: 0,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, high: f64, low: f64, close: f64, volume: f64) -> (f64, f64) {
+        self.count += 1;
+        let hlc = high + low + close;
+        if is_nan_or_inf(hlc) || is_nan_or_inf(volume) {
+            return self.value();
+        }
+
+        let dm = high - low;
+        if let Some(prehlc) = self.prehlc {
+            let trend = if hlc > prehlc { 1 } else { -1 };
+            self.cm = if trend == self.trend {
+                self.cm + dm
+            } else {
+                self.predm + dm
+            };
+            self.trend = trend;
+
+            let ratio = if self.cm > 0.0 { dm / self.cm } else { 0.0 };
+            let force = volume * (2.0 * (ratio - 1.0)).abs() * trend as f64 * 100.0;
+            let kvo = self.ema_fast.update(force) - self.ema_slow.update(force);
+            self.ema_signal.update(kvo);
+        }
+        self.prehlc = Some(hlc);
+        self.predm = dm;
+
+        self.value()
+    }
+}
+
+impl Indicator for KVO {
+    type Input = (f64, f64, f64, f64);
+    type Output = (f64, f64);
+
+    fn update(&mut self, (high, low, close, volume): (f64, f64, f64, f64)) -> (f64, f64) {
+        self.update(high, low, close, volume)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        (
+            self.ema_fast.value() - self.ema_slow.value(),
+            self.ema_signal.value(),
+        )
+    }
+
+    fn reset(&mut self) {
+        self.ema_fast.reset();
+        self.ema_slow.reset();
+        self.ema_signal.reset();
+        self.prehlc = None;
+        self.predm = 0.0;
+        self.cm = 0.0;
+        self.trend = 0;
+        self.count = 0;
+    }
+
+    // the first bar has no trend
+    fn lookback(&self) -> usize {
+        1 + self.ema_slow.lookback() + self.ema_signal.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// VWMA - Volume Weighted Moving Average
+// sum of close * volume over the period divided by the sum of the volume
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VWMA {
+    price_volume_sumer: rolling::statis::Sumer,
+    volume_sumer: rolling::statis::Sumer,
+}
+
+impl VWMA {
+    pub fn new(timeperiod: usize) -> Self {
+        Self {
+            price_volume_sumer: rolling::statis::Sumer::new(timeperiod),
+            volume_sumer: rolling::statis::Sumer::new(timeperiod),
+        }
+    }
+
+    pub fn update(&mut self, close: f64, volume: f64) -> f64 {
+        self.price_volume_sumer.update(close * volume);
+        self.volume_sumer.update(volume);
+
+        self.value()
+    }
+}
+
+impl Indicator for VWMA {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (close, volume): (f64, f64)) -> f64 {
+        self.update(close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.price_volume_sumer.value() / self.volume_sumer.value()
+    }
+
+    fn reset(&mut self) {
+        self.price_volume_sumer.reset();
+        self.volume_sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.volume_sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.volume_sumer.count()
+    }
+}
+
+// VOSC - Volume Oscillator
+// percentage difference of a fast and a slow EMA of the volume, (fast - slow) / slow * 100
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VOSC {
+    ema_fast: EMA,
+    ema_slow: EMA,
+    count: usize,
+}
+
+impl VOSC {
+    pub fn new(fastperiod: usize, slowperiod: usize) -> Self {
+        Self {
+            ema_fast: EMA::new(fastperiod),
+            ema_slow: EMA::new(slowperiod),
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, volume: f64) -> f64 {
+        self.count += 1;
+        self.ema_fast.update(volume);
+        self.ema_slow.update(volume);
+
+        self.value()
+    }
+}
+
+impl Indicator for VOSC {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let slow = self.ema_slow.value();
+        (self.ema_fast.value() - slow) / slow * 100.0
+    }
+
+    fn reset(&mut self) {
+        self.ema_fast.reset();
+        self.ema_slow.reset();
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.ema_slow.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// VWAPSums keeps the volume-weighted sums of the typical price behind the VWAPs around a shift,
+// sum(v), sum(v * (p - shift)) and sum(v * (p - shift)^2), with either cum or rolling sumers.
+// Raw sums of p^2 * v cancel catastrophically for prices like 50000.0, shifted ones do not.
+// The shift is the first valid price after a reset, RollingVWAP moves it with recenter().
+// A bar with a NaN price or volume is fed as NaN to all three, so they stay in step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VWAPSums<S> {
+    #[serde(with = "crate::utils::float")]
+    shift: f64,
+    volume: S,
+    shifted_volume: S,
+    square_volume: S,
+}
+
+impl<S: Indicator<Input = f64, Output = f64>> VWAPSums<S> {
+    fn new(new_sumer: impl Fn() -> S) -> Self {
+        Self {
+            shift: f64::NAN,
+            volume: new_sumer(),
+            shifted_volume: new_sumer(),
+            square_volume: new_sumer(),
+        }
+    }
+
+    fn update(&mut self, price: f64, volume: f64) {
+        let (diff, volume) = if is_nan_or_inf(price) || is_nan_or_inf(volume) {
+            (f64::NAN, f64::NAN)
+        } else {
+            if self.shift.is_nan() {
+                self.shift = price;
+            }
+            (price - self.shift, volume)
+        };
+        self.volume.update(volume);
+        self.shifted_volume.update(diff * volume);
+        self.square_volume.update(diff * diff * volume);
+    }
+
+    fn vwap(&self) -> f64 {
+        self.shift + self.shifted_volume.value() / self.volume.value()
+    }
+
+    // volume-weighted std of the typical price around the vwap
+    fn std(&self) -> f64 {
+        let volume = self.volume.value();
+        let mean = self.shifted_volume.value() / volume;
+        let variance = self.square_volume.value() / volume - mean * mean;
+        // rounding can make the variance slightly negative, NaN stays NaN
+        if variance < 0.0 {
+            0.0
+        } else {
+            variance.sqrt()
+        }
+    }
+
+    // feeds the window again around `shift`
+    fn recenter(&mut self, shift: f64, window: impl Iterator<Item = (f64, f64)>) {
+        self.reset();
+        self.shift = shift;
+        for (price, volume) in window {
+            self.update(price, volume);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.shift = f64::NAN;
+        self.volume.reset();
+        self.shifted_volume.reset();
+        self.square_volume.reset();
+    }
+}
+
+// the price the VWAPs weight
+fn typical_price(high: f64, low: f64, close: f64) -> f64 {
+    (high + low + close) / 3.0
+}
+
+// SessionVWAP - Volume Weighted Average Price of the session
+// the typical price (high + low + close) / 3 weighted by volume since the session start.
+// sessions are the intervals of `session` starting at multiples of it shifted by `offset`, in the
+// unit of the timestamps, e.g. 86_400_000 and 48_600_000 for days starting at 13:30 UTC in
+// milliseconds. A bar of a later session starts over, an older bar is added to the current one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionVWAP {
+    session: i64,
+    offset: i64,
+    current: Option<i64>,
+    sums: VWAPSums<cum::statis::Sumer>,
+    count: usize,
+}
+
+impl SessionVWAP {
+    pub fn new(session: i64, offset: i64) -> Self {
+        Self {
+            session: session.max(1),
+            offset,
+            current: None,
+            sums: VWAPSums::new(cum::statis::Sumer::new),
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, ts: i64, high: f64, low: f64, close: f64, volume: f64) -> f64 {
+        let session = ts.saturating_sub(self.offset).div_euclid(self.session);
+        if self.current.is_none_or(|current| session > current) {
+            self.current = Some(session);
+            self.sums.reset();
+        }
+        self.sums.update(typical_price(high, low, close), volume);
+        self.count += 1;
+
+        self.value()
+    }
+
+    // volume-weighted std of the typical price in the session
+    pub fn std(&self) -> f64 {
+        self.sums.std()
+    }
+}
+
+impl Indicator for SessionVWAP {
+    type Input = (i64, f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (ts, high, low, close, volume): (i64, f64, f64, f64, f64)) -> f64 {
+        self.update(ts, high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.sums.vwap()
+    }
+
+    fn reset(&mut self) {
+        self.current = None;
+        self.sums.reset();
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// AnchoredVWAP - Volume Weighted Average Price from an anchor
+// the VWAP of the bars from the timestamp `anchor` on, e.g. an earnings release or a swing low,
+// bars before it give NaN. anchor_at() moves the anchor and starts over.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchoredVWAP {
+    anchor: i64,
+    sums: VWAPSums<cum::statis::Sumer>,
+    anchored: usize,
+    count: usize,
+}
+
+impl AnchoredVWAP {
+    pub fn new(anchor: i64) -> Self {
+        Self {
+            anchor,
+            sums: VWAPSums::new(cum::statis::Sumer::new),
+            anchored: 0,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, ts: i64, high: f64, low: f64, close: f64, volume: f64) -> f64 {
+        if ts >= self.anchor {
+            self.sums.update(typical_price(high, low, close), volume);
+            self.anchored += 1;
+        }
+        self.count += 1;
+
+        self.value()
+    }
+
+    // restarts the VWAP at the bars from `anchor` on
+    pub fn anchor_at(&mut self, anchor: i64) {
+        self.anchor = anchor;
+        self.sums.reset();
+        self.anchored = 0;
+    }
+
+    // volume-weighted std of the typical price since the anchor
+    pub fn std(&self) -> f64 {
+        if self.anchored > 0 {
+            self.sums.std()
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl Indicator for AnchoredVWAP {
+    type Input = (i64, f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (ts, high, low, close, volume): (i64, f64, f64, f64, f64)) -> f64 {
+        self.update(ts, high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        if self.anchored > 0 {
+            self.sums.vwap()
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.sums.reset();
+        self.anchored = 0;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+// RollingVWAP - Volume Weighted Average Price over period
+// the VWAP of the last `timeperiod` bars, the timestamps only keep the inputs alike.
+// every `timeperiod` bars the sums are rebuilt from the window around the current vwap,
+// so the shift follows the price however far it drifts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RollingVWAP {
+    sums: VWAPSums<rolling::statis::Sumer>,
+    prices: rolling::container::Container,
+    volumes: rolling::container::Container,
+    since_recenter: usize,
+}
+
+impl RollingVWAP {
+    pub fn new(timeperiod: usize) -> Self {
+        Self {
+            sums: VWAPSums::new(|| rolling::statis::Sumer::new(timeperiod)),
+            prices: rolling::container::Container::new(timeperiod),
+            volumes: rolling::container::Container::new(timeperiod),
+            since_recenter: 0,
+        }
+    }
+
+    pub fn update(&mut self, _ts: i64, high: f64, low: f64, close: f64, volume: f64) -> f64 {
+        let price = typical_price(high, low, close);
+        self.prices.update(price);
+        self.volumes.update(volume);
+        self.sums.update(price, volume);
+
+        self.since_recenter += 1;
+        if self.since_recenter >= self.prices.len() {
+            self.since_recenter = 0;
+            let vwap = self.sums.vwap();
+            let window = self
+                .prices
+                .iter()
+                .copied()
+                .zip(self.volumes.iter().copied());
+            self.sums.recenter(vwap, window);
+        }
+
+        self.value()
+    }
+
+    // volume-weighted std of the typical price over the period
+    pub fn std(&self) -> f64 {
+        self.sums.std()
+    }
+}
+
+impl Indicator for RollingVWAP {
+    type Input = (i64, f64, f64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (ts, high, low, close, volume): (i64, f64, f64, f64, f64)) -> f64 {
+        self.update(ts, high, low, close, volume)
+    }
+
+    fn value(&self) -> f64 {
+        self.sums.vwap()
+    }
+
+    fn reset(&mut self) {
+        self.sums.reset();
+        self.prices.reset();
+        self.volumes.reset();
+        self.since_recenter = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.prices.len() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.prices.count()
+    }
+}
+
+// the VWAPs VWAPBands puts its bands around, std() is the volume-weighted std of their prices
+pub trait VolumeWeighted: Indicator<Input = (i64, f64, f64, f64, f64), Output = f64> {
+    fn std(&self) -> f64;
+}
+
+impl VolumeWeighted for SessionVWAP {
+    fn std(&self) -> f64 {
+        SessionVWAP::std(self)
+    }
+}
+
+impl VolumeWeighted for AnchoredVWAP {
+    fn std(&self) -> f64 {
+        AnchoredVWAP::std(self)
+    }
+}
+
+impl VolumeWeighted for RollingVWAP {
+    fn std(&self) -> f64 {
+        RollingVWAP::std(self)
+    }
+}
+
+// VWAPBands - VWAP standard deviation bands
+// lowerband, vwap, upperband of a VWAP in the order of BBands, the bands are nbdevup/nbdevdn
+// volume-weighted stds of the typical price away. new() puts them around the session VWAP,
+// with_vwap() around any of them, AnchoredVWAPBands and RollingVWAPBands name the other two
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VWAPBands<V = SessionVWAP> {
+    vwap: V,
+    #[serde(with = "crate::utils::float")]
+    nbdevup: f64,
+    #[serde(with = "crate::utils::float")]
+    nbdevdn: f64,
+}
+
+pub type AnchoredVWAPBands = VWAPBands<AnchoredVWAP>;
+pub type RollingVWAPBands = VWAPBands<RollingVWAP>;
+
+impl VWAPBands {
+    pub fn new(session: i64, offset: i64, nbdevup: f64, nbdevdn: f64) -> Self {
+        Self::with_vwap(SessionVWAP::new(session, offset), nbdevup, nbdevdn)
+    }
+}
+
+impl VWAPBands<AnchoredVWAP> {
+    // restarts the VWAP at the bars from `anchor` on
+    pub fn anchor_at(&mut self, anchor: i64) {
+        self.vwap.anchor_at(anchor);
+    }
+}
+
+impl<V: VolumeWeighted> VWAPBands<V> {
+    pub fn with_vwap(vwap: V, nbdevup: f64, nbdevdn: f64) -> Self {
+        Self {
+            vwap,
+            nbdevup,
+            nbdevdn,
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        ts: i64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> (f64, f64, f64) {
+        self.vwap.update((ts, high, low, close, volume));
+
+        self.value()
+    }
+}
+
+impl<V: VolumeWeighted> Indicator for VWAPBands<V> {
+    type Input = (i64, f64, f64, f64, f64);
+    type Output = (f64, f64, f64);
+
+    fn update(
+        &mut self,
+        (ts, high, low, close, volume): (i64, f64, f64, f64, f64),
+    ) -> (f64, f64, f64) {
+        self.update(ts, high, low, close, volume)
+    }
+
+    fn value(&self) -> (f64, f64, f64) {
+        let vwap = self.vwap.value();
+        let std = self.vwap.std();
+        (vwap - self.nbdevdn * std, vwap, vwap + self.nbdevup * std)
+    }
+
+    fn reset(&mut self) {
+        self.vwap.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.vwap.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.vwap.count()
+    }
+}
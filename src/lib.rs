@@ -1,14 +1,21 @@
-use pyo3::prelude::*;
-mod cum;
-mod rolling;
-mod indicator;
+// indicator names follow TA-Lib, e.g. EMA, MACD, RSI
+#![allow(clippy::upper_case_acronyms)]
+pub mod amend;
+pub mod bars;
+pub mod batch;
+pub mod cum;
+pub mod ewm;
+pub mod indicator;
+pub mod nan;
+pub mod pattern;
+pub mod rolling;
+pub mod timed;
+mod traits;
 pub mod utils;
 
-/// A Python module implemented in Rust.
-#[pymodule]
-fn ta(m: &Bound<'_, PyModule>) -> PyResult<()> {
-    rolling::register(m)?;
-    cum::register(m)?;
-    indicator::register(m)?;
-    Ok(())
-}
+#[cfg(feature = "python")]
+mod python;
+
+pub use amend::Amend;
+pub use nan::NanPolicy;
+pub use traits::{Indicator, NanHandling};
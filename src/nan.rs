@@ -0,0 +1,97 @@
+use crate::utils::is_nan_or_inf;
+use serde::{Deserialize, Serialize};
+
+// NanPolicy decides what a NaN/inf input does to the output
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NanPolicy {
+    // any NaN/inf in the window makes the output NaN, the default of rolling types
+    Propagate,
+    // NaN/inf inputs are left out, the default of cum types
+    Skip,
+    // NaN/inf inputs are replaced by the last valid input, NaN until there is one
+    FillPrevious,
+    // NaN/inf inputs are replaced by a constant
+    FillValue(#[serde(with = "crate::utils::float")] f64),
+}
+
+// NanGuard applies a NanPolicy and pandas-like min_periods for one indicator.
+// fill() runs on every input, accepts() decides whether the current window has an output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NanGuard {
+    policy: NanPolicy,
+    min_periods: usize,
+    #[serde(with = "crate::utils::float")]
+    last_valid: f64,
+    // NaN/inf inputs a cum type has skipped under Propagate, they never leave its window
+    invalid: usize,
+}
+
+impl NanGuard {
+    pub fn new(policy: NanPolicy, min_periods: usize) -> Self {
+        Self {
+            policy,
+            min_periods: min_periods.max(1),
+            last_valid: f64::NAN,
+            invalid: 0,
+        }
+    }
+
+    pub fn fill(&mut self, x: f64) -> f64 {
+        if is_nan_or_inf(x) {
+            match self.policy {
+                NanPolicy::FillPrevious => self.last_valid,
+                NanPolicy::FillValue(value) => value,
+                NanPolicy::Propagate | NanPolicy::Skip => x,
+            }
+        } else {
+            self.last_valid = x;
+            x
+        }
+    }
+
+    // `valid` finite values and `invalid` NaN/inf values are in the window
+    pub fn accepts(&self, valid: usize, invalid: usize) -> bool {
+        (invalid == 0 || self.policy != NanPolicy::Propagate) && valid >= self.min_periods
+    }
+
+    // skip() records a NaN/inf input that a cum type leaves out
+    pub fn skip(&mut self) {
+        if self.policy == NanPolicy::Propagate {
+            self.invalid += 1;
+        }
+    }
+
+    // accepts() for cum types, over every input since the last reset
+    pub fn accepts_all(&self, valid: usize) -> bool {
+        self.accepts(valid, self.invalid)
+    }
+
+    pub fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: NanPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn min_periods(&self) -> usize {
+        self.min_periods
+    }
+
+    // at least one valid value is needed for any output
+    pub fn set_min_periods(&mut self, min_periods: usize) {
+        self.min_periods = min_periods.max(1);
+    }
+
+    pub fn reset(&mut self) {
+        self.last_valid = f64::NAN;
+        self.invalid = 0;
+    }
+}
+
+// (valid, invalid) counts of a window of `len` slots after `count` inputs with `nan_count` NaN slots,
+// the empty slots of a partial window are NaN but not invalid inputs
+pub fn window_counts(len: usize, count: usize, nan_count: usize) -> (usize, usize) {
+    let empty = len - count.min(len);
+    (len - nan_count, nan_count - empty)
+}
@@ -0,0 +1,245 @@
+use crate::utils::is_nan_or_inf;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Candle {
+    #[serde(with = "crate::utils::float")]
+    pub open: f64,
+    #[serde(with = "crate::utils::float")]
+    pub high: f64,
+    #[serde(with = "crate::utils::float")]
+    pub low: f64,
+    #[serde(with = "crate::utils::float")]
+    pub close: f64,
+}
+
+impl Candle {
+    pub fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    pub fn upper_shadow(&self) -> f64 {
+        self.high - self.body_top()
+    }
+
+    pub fn lower_shadow(&self) -> f64 {
+        self.body_bottom() - self.low
+    }
+
+    pub fn high_low(&self) -> f64 {
+        self.high - self.low
+    }
+
+    pub fn body_top(&self) -> f64 {
+        if self.close >= self.open {
+            self.close
+        } else {
+            self.open
+        }
+    }
+
+    pub fn body_bottom(&self) -> f64 {
+        if self.close >= self.open {
+            self.open
+        } else {
+            self.close
+        }
+    }
+
+    // 1 for white (close >= open), -1 for black
+    pub fn color(&self) -> i32 {
+        if self.close >= self.open {
+            1
+        } else {
+            -1
+        }
+    }
+
+    fn range(&self, range_type: RangeType) -> f64 {
+        match range_type {
+            RangeType::RealBody => self.body(),
+            RangeType::HighLow => self.high_low(),
+            RangeType::Shadows => self.upper_shadow() + self.lower_shadow(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RangeType {
+    RealBody,
+    HighLow,
+    Shadows,
+}
+
+// CandleSetting is a TA-Lib candle setting: what "long body" or "near" means,
+// factor times the average range of the `period` candles before, or of the candle itself when period is 0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CandleSetting {
+    pub range_type: RangeType,
+    pub period: usize,
+    pub factor: f64,
+}
+
+const fn setting(range_type: RangeType, period: usize, factor: f64) -> CandleSetting {
+    CandleSetting {
+        range_type,
+        period,
+        factor,
+    }
+}
+
+// the TA-Lib defaults
+pub const BODY_LONG: CandleSetting = setting(RangeType::RealBody, 10, 1.0);
+pub const BODY_VERY_LONG: CandleSetting = setting(RangeType::RealBody, 10, 3.0);
+pub const BODY_SHORT: CandleSetting = setting(RangeType::RealBody, 10, 1.0);
+pub const BODY_DOJI: CandleSetting = setting(RangeType::HighLow, 10, 0.1);
+pub const SHADOW_LONG: CandleSetting = setting(RangeType::RealBody, 0, 1.0);
+pub const SHADOW_VERY_LONG: CandleSetting = setting(RangeType::RealBody, 0, 2.0);
+pub const SHADOW_SHORT: CandleSetting = setting(RangeType::Shadows, 10, 1.0);
+pub const SHADOW_VERY_SHORT: CandleSetting = setting(RangeType::HighLow, 10, 0.1);
+pub const NEAR: CandleSetting = setting(RangeType::HighLow, 5, 0.2);
+pub const FAR: CandleSetting = setting(RangeType::HighLow, 5, 0.6);
+pub const EQUAL: CandleSetting = setting(RangeType::HighLow, 5, 0.05);
+
+// the longest average period of the settings, lookbacks are built from it like TA-Lib's
+pub fn max_period(settings: &[CandleSetting]) -> usize {
+    settings.iter().map(|s| s.period).max().unwrap_or(0)
+}
+
+// running total of a setting's range over the `period` candles before the candle `offset` back
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Average {
+    setting: CandleSetting,
+    offset: usize,
+    #[serde(with = "crate::utils::float")]
+    total: f64,
+}
+
+// Candles keeps the last candles of a pattern and the averages it compares them with.
+// The totals are summed in the order TA-Lib sums them, so the comparisons agree with it
+// to the last bit: they start `period` candles before the first output and then slide by one candle.
+// Candles with a NaN/inf price are left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candles {
+    candles: VecDeque<Candle>,
+    lookback: usize,
+    averages: Vec<Average>,
+    count: usize,
+}
+
+impl Candles {
+    // lookback is the number of candles before the first output,
+    // averages are the (setting, offset) pairs the pattern compares with
+    pub fn new(lookback: usize, averages: &[(CandleSetting, usize)]) -> Self {
+        Self {
+            candles: VecDeque::with_capacity(lookback + 1),
+            lookback,
+            averages: averages
+                .iter()
+                .map(|&(setting, offset)| Average {
+                    setting,
+                    offset,
+                    total: 0.0,
+                })
+                .collect(),
+            count: 0,
+        }
+    }
+
+    // returns false and ignores the candle when a price is NaN/inf
+    pub fn push(&mut self, open: f64, high: f64, low: f64, close: f64) -> bool {
+        if [open, high, low, close].into_iter().any(is_nan_or_inf) {
+            return false;
+        }
+        if self.candles.len() > self.lookback {
+            self.candles.pop_front();
+        }
+        self.candles.push_back(Candle {
+            open,
+            high,
+            low,
+            close,
+        });
+        self.count += 1;
+        true
+    }
+
+    // slides the averages once the pattern has looked at the latest candle
+    pub fn roll(&mut self) {
+        let n = self.count - 1;
+        let candles = &self.candles;
+        let get = |k: usize| candles[candles.len() - 1 - k];
+        for average in self.averages.iter_mut() {
+            let (setting, k) = (average.setting, average.offset);
+            if setting.period == 0 {
+                continue;
+            }
+            if n >= self.lookback {
+                average.total += get(k).range(setting.range_type)
+                    - get(k + setting.period).range(setting.range_type);
+            } else if n + setting.period >= self.lookback {
+                average.total += get(k).range(setting.range_type);
+            }
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.count > self.lookback
+    }
+
+    pub fn lookback(&self) -> usize {
+        self.lookback
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // the candle k back, 0 is the latest
+    pub fn get(&self, k: usize) -> &Candle {
+        &self.candles[self.candles.len() - 1 - k]
+    }
+
+    // the average of a setting for the candle k back, NaN if the pattern did not ask for it
+    pub fn avg(&self, setting: CandleSetting, k: usize) -> f64 {
+        let range = if setting.period == 0 {
+            self.get(k).range(setting.range_type)
+        } else {
+            self.averages
+                .iter()
+                .find(|a| a.setting == setting && a.offset == k)
+                .map_or(f64::NAN, |a| a.total / setting.period as f64)
+        };
+        let divisor = if setting.range_type == RangeType::Shadows {
+            2.0
+        } else {
+            1.0
+        };
+        setting.factor * range / divisor
+    }
+
+    // the real body of candle k2 gaps up from the one of the older candle k1
+    pub fn body_gap_up(&self, k2: usize, k1: usize) -> bool {
+        self.get(k2).body_bottom() > self.get(k1).body_top()
+    }
+
+    pub fn body_gap_down(&self, k2: usize, k1: usize) -> bool {
+        self.get(k2).body_top() < self.get(k1).body_bottom()
+    }
+
+    // candle k2 gaps up from the older candle k1, shadows included
+    pub fn gap_up(&self, k2: usize, k1: usize) -> bool {
+        self.get(k2).low > self.get(k1).high
+    }
+
+    pub fn gap_down(&self, k2: usize, k1: usize) -> bool {
+        self.get(k2).high < self.get(k1).low
+    }
+
+    pub fn reset(&mut self) {
+        self.candles.clear();
+        self.averages.iter_mut().for_each(|a| a.total = 0.0);
+        self.count = 0;
+    }
+}
@@ -0,0 +1,379 @@
+// two-candle patterns, and one-candle patterns judged against the candle before
+use super::candle::{
+    max_period, BODY_DOJI, BODY_LONG, BODY_SHORT, EQUAL, NEAR, SHADOW_LONG, SHADOW_VERY_SHORT,
+};
+use super::pattern;
+
+// CDLCOUNTERATTACK: two long opposite candles closing at the same price
+pattern!(Counterattack(),
+    lookback: max_period(&[EQUAL, BODY_LONG]) + 1,
+    averages: [(EQUAL, 1), (BODY_LONG, 1), (BODY_LONG, 0)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -c0.color()
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.body() > c.avg(BODY_LONG, 0)
+            && c0.close <= c1.close + c.avg(EQUAL, 1)
+            && c0.close >= c1.close - c.avg(EQUAL, 1)
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLDARKCLOUDCOVER: a black candle opening above a long white high and closing
+// deeper than `penetration` of the white body
+pattern!(DarkCloudCover(penetration = 0.5),
+    lookback: BODY_LONG.period + 1,
+    averages: [(BODY_LONG, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == 1
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.color() == -1
+            && c0.open > c1.high
+            && c0.close > c1.open
+            && c0.close < c1.close - c1.body() * penetration
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLDOJISTAR: a long candle followed by a doji gapping away in its direction
+pattern!(DojiStar(),
+    lookback: max_period(&[BODY_DOJI, BODY_LONG]) + 1,
+    averages: [(BODY_LONG, 1), (BODY_DOJI, 0)],
+    |c| {
+        let c1 = c.get(1);
+        if c1.body() > c.avg(BODY_LONG, 1)
+            && c.get(0).body() <= c.avg(BODY_DOJI, 0)
+            && ((c1.color() == 1 && c.body_gap_up(0, 1))
+                || (c1.color() == -1 && c.body_gap_down(0, 1)))
+        {
+            -c1.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLENGULFING: a body engulfing the opposite body before it,
+// ±80 when one end of the bodies is shared
+pattern!(Engulfing(),
+    lookback: 2,
+    averages: [],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        let white = c0.color() == 1
+            && c1.color() == -1
+            && ((c0.close >= c1.open && c0.open < c1.close)
+                || (c0.close > c1.open && c0.open <= c1.close));
+        let black = c0.color() == -1
+            && c1.color() == 1
+            && ((c0.open >= c1.close && c0.close < c1.open)
+                || (c0.open > c1.close && c0.close <= c1.open));
+        if !(white || black) {
+            0
+        } else if c0.open != c1.close && c0.close != c1.open {
+            c0.color() * 100
+        } else {
+            c0.color() * 80
+        }
+    }
+);
+
+// CDLHAMMER: a small body at the top of a long lower shadow, near the lows of the candle before
+pattern!(Hammer(),
+    lookback: max_period(&[BODY_SHORT, SHADOW_LONG, SHADOW_VERY_SHORT, NEAR]) + 1,
+    averages: [(BODY_SHORT, 0), (SHADOW_LONG, 0), (SHADOW_VERY_SHORT, 0), (NEAR, 1)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.lower_shadow() > c.avg(SHADOW_LONG, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.body_bottom() <= c.get(1).low + c.avg(NEAR, 1)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLHANGINGMAN: the hammer shape near the highs of the candle before
+pattern!(HangingMan(),
+    lookback: max_period(&[BODY_SHORT, SHADOW_LONG, SHADOW_VERY_SHORT, NEAR]) + 1,
+    averages: [(BODY_SHORT, 0), (SHADOW_LONG, 0), (SHADOW_VERY_SHORT, 0), (NEAR, 1)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.lower_shadow() > c.avg(SHADOW_LONG, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.body_bottom() >= c.get(1).high - c.avg(NEAR, 1)
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// inside is the harami test: 100 strictly within the body before, 80 touching one of its ends
+fn inside(body: &super::Candle, mother: &super::Candle) -> i32 {
+    if body.body_top() < mother.body_top() && body.body_bottom() > mother.body_bottom() {
+        100
+    } else if body.body_top() <= mother.body_top() && body.body_bottom() >= mother.body_bottom() {
+        80
+    } else {
+        0
+    }
+}
+
+// CDLHARAMI: a short body within the long body before, against its direction
+pattern!(Harami(),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 1,
+    averages: [(BODY_LONG, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.body() > c.avg(BODY_LONG, 1) && c0.body() <= c.avg(BODY_SHORT, 0) {
+            -c1.color() * inside(c0, c1)
+        } else {
+            0
+        }
+    }
+);
+
+// CDLHARAMICROSS: a harami whose second candle is a doji
+pattern!(HaramiCross(),
+    lookback: max_period(&[BODY_DOJI, BODY_LONG]) + 1,
+    averages: [(BODY_LONG, 1), (BODY_DOJI, 0)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.body() > c.avg(BODY_LONG, 1) && c0.body() <= c.avg(BODY_DOJI, 0) {
+            -c1.color() * inside(c0, c1)
+        } else {
+            0
+        }
+    }
+);
+
+// CDLHOMINGPIGEON: a short black body within the long black body before
+pattern!(HomingPigeon(),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 1,
+    averages: [(BODY_LONG, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -1
+            && c0.color() == -1
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.body() <= c.avg(BODY_SHORT, 0)
+            && c0.open < c1.open
+            && c0.close > c1.close
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLINNECK: a white candle opening below a long black low and closing slightly into its body
+pattern!(InNeck(),
+    lookback: max_period(&[EQUAL, BODY_LONG]) + 1,
+    averages: [(EQUAL, 1), (BODY_LONG, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -1
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.color() == 1
+            && c0.open < c1.low
+            && c0.close <= c1.close + c.avg(EQUAL, 1)
+            && c0.close >= c1.close
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLINVERTEDHAMMER: a small body at the bottom of a long upper shadow, gapping down
+pattern!(InvertedHammer(),
+    lookback: max_period(&[BODY_SHORT, SHADOW_LONG, SHADOW_VERY_SHORT]) + 1,
+    averages: [(BODY_SHORT, 0), (SHADOW_LONG, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.upper_shadow() > c.avg(SHADOW_LONG, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c.body_gap_down(0, 1)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// kicking is the kicking test: two opposite marubozu with a gap between them
+fn kicking(c: &super::Candles) -> bool {
+    let (c1, c0) = (c.get(1), c.get(0));
+    c1.color() == -c0.color()
+        && c1.body() > c.avg(BODY_LONG, 1)
+        && c1.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 1)
+        && c1.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 1)
+        && c0.body() > c.avg(BODY_LONG, 0)
+        && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+        && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+        && ((c1.color() == -1 && c.gap_up(0, 1)) || (c1.color() == 1 && c.gap_down(0, 1)))
+}
+
+// CDLKICKING: a marubozu gapping away from the opposite marubozu, in the direction of the second
+pattern!(Kicking(),
+    lookback: max_period(&[SHADOW_VERY_SHORT, BODY_LONG]) + 1,
+    averages: [(SHADOW_VERY_SHORT, 1), (SHADOW_VERY_SHORT, 0), (BODY_LONG, 1), (BODY_LONG, 0)],
+    |c| if kicking(c) { c.get(0).color() * 100 } else { 0 }
+);
+
+// CDLKICKINGBYLENGTH: kicking in the direction of the longer marubozu
+pattern!(KickingByLength(),
+    lookback: max_period(&[SHADOW_VERY_SHORT, BODY_LONG]) + 1,
+    averages: [(SHADOW_VERY_SHORT, 1), (SHADOW_VERY_SHORT, 0), (BODY_LONG, 1), (BODY_LONG, 0)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if !kicking(c) {
+            0
+        } else if c0.body() > c1.body() {
+            c0.color() * 100
+        } else {
+            c1.color() * 100
+        }
+    }
+);
+
+// CDLMATCHINGLOW: two black candles with the same close
+pattern!(MatchingLow(),
+    lookback: EQUAL.period + 1,
+    averages: [(EQUAL, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -1
+            && c0.color() == -1
+            && c0.close <= c1.close + c.avg(EQUAL, 1)
+            && c0.close >= c1.close - c.avg(EQUAL, 1)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLONNECK: a white candle opening below a long black low and closing at that low
+pattern!(OnNeck(),
+    lookback: max_period(&[EQUAL, BODY_LONG]) + 1,
+    averages: [(EQUAL, 1), (BODY_LONG, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -1
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.color() == 1
+            && c0.open < c1.low
+            && c0.close <= c1.low + c.avg(EQUAL, 1)
+            && c0.close >= c1.low - c.avg(EQUAL, 1)
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLPIERCING: a long white candle opening below a long black low and closing above its midpoint
+pattern!(Piercing(),
+    lookback: BODY_LONG.period + 1,
+    averages: [(BODY_LONG, 1), (BODY_LONG, 0)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -1
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.color() == 1
+            && c0.body() > c.avg(BODY_LONG, 0)
+            && c0.open < c1.low
+            && c0.close < c1.open
+            && c0.close > c1.close + c1.body() * 0.5
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLSEPARATINGLINES: opposite candles with the same open, the second a belt hold
+pattern!(SeparatingLines(),
+    lookback: max_period(&[SHADOW_VERY_SHORT, BODY_LONG, EQUAL]) + 1,
+    averages: [(SHADOW_VERY_SHORT, 0), (BODY_LONG, 0), (EQUAL, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        let belt_side = if c0.color() == 1 {
+            c0.lower_shadow()
+        } else {
+            c0.upper_shadow()
+        };
+        if c1.color() == -c0.color()
+            && c0.open <= c1.open + c.avg(EQUAL, 1)
+            && c0.open >= c1.open - c.avg(EQUAL, 1)
+            && c0.body() > c.avg(BODY_LONG, 0)
+            && belt_side < c.avg(SHADOW_VERY_SHORT, 0)
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLSHOOTINGSTAR: a small body at the bottom of a long upper shadow, gapping up
+pattern!(ShootingStar(),
+    lookback: max_period(&[BODY_SHORT, SHADOW_LONG, SHADOW_VERY_SHORT]) + 1,
+    averages: [(BODY_SHORT, 0), (SHADOW_LONG, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.upper_shadow() > c.avg(SHADOW_LONG, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c.body_gap_up(0, 1)
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLTHRUSTING: a white candle opening below a long black low and closing into its body,
+// under the midpoint
+pattern!(Thrusting(),
+    lookback: max_period(&[EQUAL, BODY_LONG]) + 1,
+    averages: [(EQUAL, 1), (BODY_LONG, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        if c1.color() == -1
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c0.color() == 1
+            && c0.open < c1.low
+            && c0.close > c1.close + c.avg(EQUAL, 1)
+            && c0.close <= c1.close + c1.body() * 0.5
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
@@ -0,0 +1,213 @@
+// Hikkake patterns: an inside bar followed by a false breakout, they keep state since a
+// confirmation can come up to 3 candles after the pattern
+use super::candle::NEAR;
+use super::Candles;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// Confirmation follows the last pattern: a close beyond the inside bar within 3 candles
+// confirms it, with the pattern code ±100 raised to ±200
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Confirmation {
+    result: i32,
+    // candle number of the pattern, 0 when none is pending like TA-Lib's patternIdx
+    index: usize,
+    #[serde(with = "crate::utils::float")]
+    high: f64,
+    #[serde(with = "crate::utils::float")]
+    low: f64,
+}
+
+impl Confirmation {
+    fn new() -> Self {
+        Self {
+            result: 0,
+            index: 0,
+            high: f64::NAN,
+            low: f64::NAN,
+        }
+    }
+
+    // detected is the code of a pattern ending at the latest candle
+    fn step(&mut self, candles: &Candles, detected: i32) -> i32 {
+        let n = candles.count() - 1;
+        if detected != 0 {
+            self.result = detected;
+            self.index = n;
+            self.high = candles.get(1).high;
+            self.low = candles.get(1).low;
+            detected
+        } else if n <= self.index + 3
+            && ((self.result > 0 && candles.get(0).close > self.high)
+                || (self.result < 0 && candles.get(0).close < self.low))
+        {
+            self.index = 0;
+            self.result + self.result.signum() * 100
+        } else {
+            0
+        }
+    }
+}
+
+// CDLHIKKAKE: an inside bar, then a breakout of its high (-100) or low (100),
+// ±200 when a close beyond the other side of the inside bar confirms the false breakout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hikkake {
+    candles: Candles,
+    confirmation: Confirmation,
+    out: i32,
+}
+
+impl Hikkake {
+    pub fn new() -> Self {
+        Self {
+            candles: Candles::new(2, &[]),
+            confirmation: Confirmation::new(),
+            out: 0,
+        }
+    }
+
+    pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> i32 {
+        self.out = 0;
+        if self.candles.push(open, high, low, close) && self.candles.is_ready() {
+            let c = &self.candles;
+            let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+            let detected = if c1.high < c2.high
+                && c1.low > c2.low
+                && ((c0.high < c1.high && c0.low < c1.low)
+                    || (c0.high > c1.high && c0.low > c1.low))
+            {
+                if c0.high < c1.high {
+                    100
+                } else {
+                    -100
+                }
+            } else {
+                0
+            };
+            let out = self.confirmation.step(c, detected);
+            if self.is_ready() {
+                self.out = out;
+            }
+        }
+        self.out
+    }
+}
+
+impl Default for Hikkake {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for Hikkake {
+    type Input = (f64, f64, f64, f64);
+    type Output = i32;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> i32 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> i32 {
+        self.out
+    }
+
+    fn reset(&mut self) {
+        self.candles.reset();
+        self.confirmation = Confirmation::new();
+        self.out = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        5
+    }
+
+    fn count(&self) -> usize {
+        self.candles.count()
+    }
+}
+
+// CDLHIKKAKEMOD: hikkake after two narrowing inside bars, the second closing near its low
+// for the bullish (100) or near its high for the bearish (-100) breakout, ±200 when confirmed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HikkakeMod {
+    candles: Candles,
+    confirmation: Confirmation,
+    out: i32,
+}
+
+impl HikkakeMod {
+    pub fn new() -> Self {
+        Self {
+            // TA-Lib starts the pattern search 3 candles before the first output
+            candles: Candles::new(NEAR.period + 2, &[(NEAR, 2)]),
+            confirmation: Confirmation::new(),
+            out: 0,
+        }
+    }
+
+    pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> i32 {
+        self.out = 0;
+        if self.candles.push(open, high, low, close) {
+            if self.candles.is_ready() {
+                let c = &self.candles;
+                let (c3, c2, c1, c0) = (c.get(3), c.get(2), c.get(1), c.get(0));
+                let near = c.avg(NEAR, 2);
+                let detected = if c2.high < c3.high
+                    && c2.low > c3.low
+                    && c1.high < c2.high
+                    && c1.low > c2.low
+                    && ((c0.high < c1.high && c0.low < c1.low && c2.close <= c2.low + near)
+                        || (c0.high > c1.high && c0.low > c1.low && c2.close >= c2.high - near))
+                {
+                    if c0.high < c1.high {
+                        100
+                    } else {
+                        -100
+                    }
+                } else {
+                    0
+                };
+                let out = self.confirmation.step(c, detected);
+                if self.is_ready() {
+                    self.out = out;
+                }
+            }
+            self.candles.roll();
+        }
+        self.out
+    }
+}
+
+impl Default for HikkakeMod {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Indicator for HikkakeMod {
+    type Input = (f64, f64, f64, f64);
+    type Output = i32;
+
+    fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> i32 {
+        self.update(open, high, low, close)
+    }
+
+    fn value(&self) -> i32 {
+        self.out
+    }
+
+    fn reset(&mut self) {
+        self.candles.reset();
+        self.confirmation = Confirmation::new();
+        self.out = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        NEAR.period.max(1) + 5
+    }
+
+    fn count(&self) -> usize {
+        self.candles.count()
+    }
+}
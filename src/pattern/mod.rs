@@ -0,0 +1,93 @@
+// Candlestick pattern recognition, the TA-Lib CDL* functions as streaming detectors.
+// Every detector takes (open, high, low, close) per update and returns the TA-Lib code:
+// 100 bullish, -100 bearish, 0 nothing, some patterns also give ±80 (weaker) or ±200 (confirmed).
+// "long body", "doji", "near" ... compare with the average of the previous candles like
+// TA-Lib's default candle settings, see candle.rs. Outputs are 0 during the lookback.
+// Candles with a NaN/inf price are ignored and return 0.
+pub mod candle;
+pub mod double;
+pub mod hikkake;
+pub mod multi;
+pub mod single;
+pub mod triple;
+
+pub use candle::{Candle, CandleSetting, Candles};
+
+// pattern! defines a detector: its lookback, the (setting, offset) averages it compares with,
+// and `|c| code` computing the code from the Candles once the lookback has passed.
+// Parameters, e.g. the penetration of the star patterns, are f64 fields usable in the body,
+// Default uses the TA-Lib defaults written after them.
+macro_rules! pattern {
+    (
+        $name:ident($($param:ident = $default:expr),*),
+        lookback: $lookback:expr,
+        averages: [$(($setting:expr, $offset:expr)),* $(,)?],
+        |$c:ident| $detect:expr
+    ) => {
+        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+        pub struct $name {
+            candles: $crate::pattern::Candles,
+            $(
+                #[serde(with = "crate::utils::float")]
+                $param: f64,
+            )*
+            out: i32,
+        }
+
+        impl $name {
+            pub fn new($($param: f64),*) -> Self {
+                Self {
+                    candles: $crate::pattern::Candles::new($lookback, &[$(($setting, $offset)),*]),
+                    $($param,)*
+                    out: 0,
+                }
+            }
+
+            pub fn update(&mut self, open: f64, high: f64, low: f64, close: f64) -> i32 {
+                self.out = 0;
+                if self.candles.push(open, high, low, close) {
+                    if self.candles.is_ready() {
+                        $(let $param = self.$param;)*
+                        let $c = &self.candles;
+                        self.out = $detect;
+                    }
+                    self.candles.roll();
+                }
+                self.out
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new($($default),*)
+            }
+        }
+
+        impl $crate::Indicator for $name {
+            type Input = (f64, f64, f64, f64);
+            type Output = i32;
+
+            fn update(&mut self, (open, high, low, close): (f64, f64, f64, f64)) -> i32 {
+                self.update(open, high, low, close)
+            }
+
+            fn value(&self) -> i32 {
+                self.out
+            }
+
+            fn reset(&mut self) {
+                self.candles.reset();
+                self.out = 0;
+            }
+
+            fn lookback(&self) -> usize {
+                self.candles.lookback()
+            }
+
+            fn count(&self) -> usize {
+                self.candles.count()
+            }
+        }
+    };
+}
+pub(crate) use pattern;
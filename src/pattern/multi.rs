@@ -0,0 +1,226 @@
+// four- and five-candle patterns
+use super::candle::{max_period, BODY_LONG, BODY_SHORT, NEAR, SHADOW_VERY_SHORT};
+use super::pattern;
+
+// CDL3BLACKCROWS: a white candle, then three falling black candles closing near their lows,
+// each opening within the body before
+pattern!(ThreeBlackCrows(),
+    lookback: SHADOW_VERY_SHORT.period + 3,
+    averages: [(SHADOW_VERY_SHORT, 2), (SHADOW_VERY_SHORT, 1), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let (c3, c2, c1, c0) = (c.get(3), c.get(2), c.get(1), c.get(0));
+        if c3.color() == 1
+            && c2.color() == -1
+            && c2.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 2)
+            && c1.color() == -1
+            && c1.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 1)
+            && c0.color() == -1
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c1.open < c2.open
+            && c1.open > c2.close
+            && c0.open < c1.open
+            && c0.open > c1.close
+            && c3.high > c2.close
+            && c2.close > c1.close
+            && c1.close > c0.close
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDL3LINESTRIKE: three candles of one color, each opening within or near the body before,
+// struck by an opposite candle wiping them out
+pattern!(ThreeLineStrike(),
+    lookback: NEAR.period + 3,
+    averages: [(NEAR, 3), (NEAR, 2)],
+    |c| {
+        let (c3, c2, c1, c0) = (c.get(3), c.get(2), c.get(1), c.get(0));
+        if c3.color() == c2.color()
+            && c2.color() == c1.color()
+            && c0.color() == -c1.color()
+            && c2.open >= c3.body_bottom() - c.avg(NEAR, 3)
+            && c2.open <= c3.body_top() + c.avg(NEAR, 3)
+            && c1.open >= c2.body_bottom() - c.avg(NEAR, 2)
+            && c1.open <= c2.body_top() + c.avg(NEAR, 2)
+            && ((c1.color() == 1
+                && c1.close > c2.close
+                && c2.close > c3.close
+                && c0.open > c1.close
+                && c0.close < c3.open)
+                || (c1.color() == -1
+                    && c1.close < c2.close
+                    && c2.close < c3.close
+                    && c0.open < c1.close
+                    && c0.close > c3.open))
+        {
+            c1.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLBREAKAWAY: a long candle, a gap, three candles extending the move,
+// and an opposite candle closing inside the gap
+pattern!(Breakaway(),
+    lookback: BODY_LONG.period + 4,
+    averages: [(BODY_LONG, 4)],
+    |c| {
+        let (c4, c3, c2, c1, c0) = (c.get(4), c.get(3), c.get(2), c.get(1), c.get(0));
+        if c4.body() > c.avg(BODY_LONG, 4)
+            && c4.color() == c3.color()
+            && c3.color() == c1.color()
+            && c1.color() == -c0.color()
+            && ((c4.color() == -1
+                && c.body_gap_down(3, 4)
+                && c2.high < c3.high
+                && c2.low < c3.low
+                && c1.high < c2.high
+                && c1.low < c2.low
+                && c0.close > c3.open
+                && c0.close < c4.close)
+                || (c4.color() == 1
+                    && c.body_gap_up(3, 4)
+                    && c2.high > c3.high
+                    && c2.low > c3.low
+                    && c1.high > c2.high
+                    && c1.low > c2.low
+                    && c0.close < c3.open
+                    && c0.close > c4.close))
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLCONCEALBABYSWALL: two black marubozu, a black candle gapping down with an upper shadow
+// into the body before, and a black candle engulfing it with its shadows
+pattern!(ConcealBabySwallow(),
+    lookback: SHADOW_VERY_SHORT.period + 3,
+    averages: [(SHADOW_VERY_SHORT, 3), (SHADOW_VERY_SHORT, 2), (SHADOW_VERY_SHORT, 1)],
+    |c| {
+        let (c3, c2, c1, c0) = (c.get(3), c.get(2), c.get(1), c.get(0));
+        if c3.color() == -1
+            && c2.color() == -1
+            && c1.color() == -1
+            && c0.color() == -1
+            && c3.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 3)
+            && c3.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 3)
+            && c2.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 2)
+            && c2.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 2)
+            && c.body_gap_down(1, 2)
+            && c1.upper_shadow() > c.avg(SHADOW_VERY_SHORT, 1)
+            && c1.high > c2.close
+            && c0.high > c1.high
+            && c0.low < c1.low
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLLADDERBOTTOM: three falling black candles, a black one with an upper shadow,
+// and a white one opening above its body and closing above its high
+pattern!(LadderBottom(),
+    lookback: SHADOW_VERY_SHORT.period + 4,
+    averages: [(SHADOW_VERY_SHORT, 1)],
+    |c| {
+        let (c4, c3, c2, c1, c0) = (c.get(4), c.get(3), c.get(2), c.get(1), c.get(0));
+        if c4.color() == -1
+            && c3.color() == -1
+            && c2.color() == -1
+            && c4.open > c3.open
+            && c3.open > c2.open
+            && c4.close > c3.close
+            && c3.close > c2.close
+            && c1.color() == -1
+            && c1.upper_shadow() > c.avg(SHADOW_VERY_SHORT, 1)
+            && c0.color() == 1
+            && c0.open > c1.open
+            && c0.close > c1.high
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLMATHOLD: a long white candle, three small falling candles gapping up and holding
+// above `penetration` of its body, and a white candle closing above their highs
+pattern!(MatHold(penetration = 0.5),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 4,
+    averages: [(BODY_LONG, 4), (BODY_SHORT, 3), (BODY_SHORT, 2), (BODY_SHORT, 1)],
+    |c| {
+        let (c4, c3, c2, c1, c0) = (c.get(4), c.get(3), c.get(2), c.get(1), c.get(0));
+        let floor = c4.close - c4.body() * penetration;
+        if c4.body() > c.avg(BODY_LONG, 4)
+            && c3.body() < c.avg(BODY_SHORT, 3)
+            && c2.body() < c.avg(BODY_SHORT, 2)
+            && c1.body() < c.avg(BODY_SHORT, 1)
+            && c4.color() == 1
+            && c3.color() == -1
+            && c0.color() == 1
+            && c.body_gap_up(3, 4)
+            && c2.body_bottom() < c4.close
+            && c1.body_bottom() < c4.close
+            && c2.body_bottom() > floor
+            && c1.body_bottom() > floor
+            && c2.body_top() < c3.open
+            && c1.body_top() < c2.body_top()
+            && c0.open > c1.close
+            && c0.close > c3.high.max(c2.high).max(c1.high)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLRISEFALL3METHODS: a long candle, three small opposite candles held within its range,
+// and a long candle closing beyond the first
+pattern!(RiseFallThreeMethods(),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 4,
+    averages: [
+        (BODY_LONG, 4),
+        (BODY_SHORT, 3),
+        (BODY_SHORT, 2),
+        (BODY_SHORT, 1),
+        (BODY_LONG, 0),
+    ],
+    |c| {
+        let (c4, c3, c2, c1, c0) = (c.get(4), c.get(3), c.get(2), c.get(1), c.get(0));
+        let within = |x: &super::Candle| x.body_bottom() < c4.high && x.body_top() > c4.low;
+        // prices times the first color turn the falling case into the rising one
+        let color = c4.color() as f64;
+        if c4.body() > c.avg(BODY_LONG, 4)
+            && c3.body() < c.avg(BODY_SHORT, 3)
+            && c2.body() < c.avg(BODY_SHORT, 2)
+            && c1.body() < c.avg(BODY_SHORT, 1)
+            && c0.body() > c.avg(BODY_LONG, 0)
+            && c4.color() == -c3.color()
+            && c3.color() == c2.color()
+            && c2.color() == c1.color()
+            && c1.color() == -c0.color()
+            && within(c3)
+            && within(c2)
+            && within(c1)
+            && c2.close * color < c3.close * color
+            && c1.close * color < c2.close * color
+            && c0.open * color > c1.close * color
+            && c0.close * color > c4.close * color
+        {
+            c4.color() * 100
+        } else {
+            0
+        }
+    }
+);
@@ -0,0 +1,224 @@
+// one-candle patterns
+use super::candle::{
+    max_period, BODY_DOJI, BODY_LONG, BODY_SHORT, NEAR, SHADOW_LONG, SHADOW_SHORT,
+    SHADOW_VERY_LONG, SHADOW_VERY_SHORT,
+};
+use super::pattern;
+
+// CDLBELTHOLD: a long candle opening at its low (white) or high (black)
+pattern!(BeltHold(),
+    lookback: max_period(&[BODY_LONG, SHADOW_VERY_SHORT]),
+    averages: [(BODY_LONG, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        let marubozu_side = if c0.color() == 1 {
+            c0.lower_shadow()
+        } else {
+            c0.upper_shadow()
+        };
+        if c0.body() > c.avg(BODY_LONG, 0) && marubozu_side < c.avg(SHADOW_VERY_SHORT, 0) {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLCLOSINGMARUBOZU: a long candle closing at its high (white) or low (black)
+pattern!(ClosingMarubozu(),
+    lookback: max_period(&[BODY_LONG, SHADOW_VERY_SHORT]),
+    averages: [(BODY_LONG, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        let marubozu_side = if c0.color() == 1 {
+            c0.upper_shadow()
+        } else {
+            c0.lower_shadow()
+        };
+        if c0.body() > c.avg(BODY_LONG, 0) && marubozu_side < c.avg(SHADOW_VERY_SHORT, 0) {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLDOJI: open and close about equal
+pattern!(Doji(),
+    lookback: BODY_DOJI.period,
+    averages: [(BODY_DOJI, 0)],
+    |c| if c.get(0).body() <= c.avg(BODY_DOJI, 0) { 100 } else { 0 }
+);
+
+// CDLDRAGONFLYDOJI: a doji with a lower shadow and no upper shadow
+pattern!(DragonflyDoji(),
+    lookback: max_period(&[BODY_DOJI, SHADOW_VERY_SHORT]),
+    averages: [(BODY_DOJI, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() <= c.avg(BODY_DOJI, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.lower_shadow() > c.avg(SHADOW_VERY_SHORT, 0)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLGRAVESTONEDOJI: a doji with an upper shadow and no lower shadow
+pattern!(GravestoneDoji(),
+    lookback: max_period(&[BODY_DOJI, SHADOW_VERY_SHORT]),
+    averages: [(BODY_DOJI, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() <= c.avg(BODY_DOJI, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.upper_shadow() > c.avg(SHADOW_VERY_SHORT, 0)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLHIGHWAVE: a short body with very long shadows on both sides
+pattern!(HighWave(),
+    lookback: max_period(&[BODY_SHORT, SHADOW_VERY_LONG]),
+    averages: [(BODY_SHORT, 0), (SHADOW_VERY_LONG, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.upper_shadow() > c.avg(SHADOW_VERY_LONG, 0)
+            && c0.lower_shadow() > c.avg(SHADOW_VERY_LONG, 0)
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLLONGLEGGEDDOJI: a doji with a long shadow
+pattern!(LongLeggedDoji(),
+    lookback: max_period(&[BODY_DOJI, SHADOW_LONG]),
+    averages: [(BODY_DOJI, 0), (SHADOW_LONG, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() <= c.avg(BODY_DOJI, 0)
+            && (c0.lower_shadow() > c.avg(SHADOW_LONG, 0)
+                || c0.upper_shadow() > c.avg(SHADOW_LONG, 0))
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLLONGLINE: a long body with short shadows
+pattern!(LongLine(),
+    lookback: max_period(&[BODY_LONG, SHADOW_SHORT]),
+    averages: [(BODY_LONG, 0), (SHADOW_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() > c.avg(BODY_LONG, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_SHORT, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_SHORT, 0)
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLMARUBOZU: a long body with almost no shadows
+pattern!(Marubozu(),
+    lookback: max_period(&[BODY_LONG, SHADOW_VERY_SHORT]),
+    averages: [(BODY_LONG, 0), (SHADOW_VERY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() > c.avg(BODY_LONG, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLRICKSHAWMAN: a long-legged doji with its body near the middle of the range
+pattern!(RickshawMan(),
+    lookback: max_period(&[BODY_DOJI, SHADOW_LONG, NEAR]),
+    averages: [(BODY_DOJI, 0), (SHADOW_LONG, 0), (NEAR, 0)],
+    |c| {
+        let c0 = c.get(0);
+        let middle = c0.low + c0.high_low() / 2.0;
+        if c0.body() <= c.avg(BODY_DOJI, 0)
+            && c0.lower_shadow() > c.avg(SHADOW_LONG, 0)
+            && c0.upper_shadow() > c.avg(SHADOW_LONG, 0)
+            && c0.body_bottom() <= middle + c.avg(NEAR, 0)
+            && c0.body_top() >= middle - c.avg(NEAR, 0)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLSHORTLINE: a short body with short shadows
+pattern!(ShortLine(),
+    lookback: max_period(&[BODY_SHORT, SHADOW_SHORT]),
+    averages: [(BODY_SHORT, 0), (SHADOW_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_SHORT, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_SHORT, 0)
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLSPINNINGTOP: a short body with shadows longer than the body
+pattern!(SpinningTop(),
+    lookback: BODY_SHORT.period,
+    averages: [(BODY_SHORT, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.upper_shadow() > c0.body()
+            && c0.lower_shadow() > c0.body()
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLTAKURI: a dragonfly doji with a very long lower shadow
+pattern!(Takuri(),
+    lookback: max_period(&[BODY_DOJI, SHADOW_VERY_SHORT, SHADOW_VERY_LONG]),
+    averages: [(BODY_DOJI, 0), (SHADOW_VERY_SHORT, 0), (SHADOW_VERY_LONG, 0)],
+    |c| {
+        let c0 = c.get(0);
+        if c0.body() <= c.avg(BODY_DOJI, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.lower_shadow() > c.avg(SHADOW_VERY_LONG, 0)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
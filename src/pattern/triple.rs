@@ -0,0 +1,551 @@
+// three-candle patterns
+use super::candle::{
+    max_period, BODY_DOJI, BODY_LONG, BODY_SHORT, EQUAL, FAR, NEAR, SHADOW_LONG, SHADOW_SHORT,
+    SHADOW_VERY_SHORT,
+};
+use super::pattern;
+
+// CDL2CROWS: a long white candle, a black one gapping up, and a black one closing
+// within the white body
+pattern!(TwoCrows(),
+    lookback: BODY_LONG.period + 2,
+    averages: [(BODY_LONG, 2)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == 1
+            && c2.body() > c.avg(BODY_LONG, 2)
+            && c1.color() == -1
+            && c.body_gap_up(1, 2)
+            && c0.color() == -1
+            && c0.open < c1.open
+            && c0.open > c1.close
+            && c0.close > c2.open
+            && c0.close < c2.close
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDL3INSIDE: a harami confirmed by a third candle closing beyond the first open
+pattern!(ThreeInside(),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_SHORT, 1)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c1.body() <= c.avg(BODY_SHORT, 1)
+            && c1.body_top() < c2.body_top()
+            && c1.body_bottom() > c2.body_bottom()
+            && ((c2.color() == 1 && c0.color() == -1 && c0.close < c2.open)
+                || (c2.color() == -1 && c0.color() == 1 && c0.close > c2.open))
+        {
+            -c2.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDL3OUTSIDE: an engulfing confirmed by a third candle closing further in its direction
+pattern!(ThreeOutside(),
+    lookback: 3,
+    averages: [],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if (c1.color() == 1
+            && c2.color() == -1
+            && c1.close > c2.open
+            && c1.open < c2.close
+            && c0.close > c1.close)
+            || (c1.color() == -1
+                && c2.color() == 1
+                && c1.open > c2.close
+                && c1.close < c2.open
+                && c0.close < c1.close)
+        {
+            c1.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDL3STARSINSOUTH: three shrinking black candles, the last a small marubozu
+// within the range of the second
+pattern!(ThreeStarsInSouth(),
+    lookback: max_period(&[SHADOW_VERY_SHORT, SHADOW_LONG, BODY_LONG, BODY_SHORT]) + 2,
+    averages: [
+        (BODY_LONG, 2),
+        (SHADOW_LONG, 2),
+        (SHADOW_VERY_SHORT, 1),
+        (SHADOW_VERY_SHORT, 0),
+        (BODY_SHORT, 0),
+    ],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == -1
+            && c1.color() == -1
+            && c0.color() == -1
+            && c2.body() > c.avg(BODY_LONG, 2)
+            && c2.lower_shadow() > c.avg(SHADOW_LONG, 2)
+            && c1.body() < c2.body()
+            && c1.open > c2.close
+            && c1.open <= c2.high
+            && c1.low < c2.close
+            && c1.low >= c2.low
+            && c1.lower_shadow() > c.avg(SHADOW_VERY_SHORT, 1)
+            && c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.low > c1.low
+            && c0.high < c1.high
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDL3WHITESOLDIERS: three rising white candles closing near their highs,
+// each opening within or near the body before
+pattern!(ThreeWhiteSoldiers(),
+    lookback: max_period(&[SHADOW_VERY_SHORT, BODY_SHORT, FAR, NEAR]) + 2,
+    averages: [
+        (SHADOW_VERY_SHORT, 2),
+        (SHADOW_VERY_SHORT, 1),
+        (SHADOW_VERY_SHORT, 0),
+        (NEAR, 2),
+        (NEAR, 1),
+        (FAR, 2),
+        (FAR, 1),
+        (BODY_SHORT, 0),
+    ],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == 1
+            && c2.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 2)
+            && c1.color() == 1
+            && c1.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 1)
+            && c0.color() == 1
+            && c0.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c0.close > c1.close
+            && c1.close > c2.close
+            && c1.open > c2.open
+            && c1.open <= c2.close + c.avg(NEAR, 2)
+            && c0.open > c1.open
+            && c0.open <= c1.close + c.avg(NEAR, 1)
+            && c1.body() > c2.body() - c.avg(FAR, 2)
+            && c0.body() > c1.body() - c.avg(FAR, 1)
+            && c0.body() > c.avg(BODY_SHORT, 0)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLABANDONEDBABY: a long candle, a doji gapping away with its shadows, and an opposite candle
+// gapping back and closing deeper than `penetration` of the first body
+pattern!(AbandonedBaby(penetration = 0.3),
+    lookback: max_period(&[BODY_DOJI, BODY_LONG, BODY_SHORT]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_DOJI, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c1.body() <= c.avg(BODY_DOJI, 1)
+            && c0.body() > c.avg(BODY_SHORT, 0)
+            && ((c2.color() == 1
+                && c0.color() == -1
+                && c0.close < c2.close - c2.body() * penetration
+                && c.gap_up(1, 2)
+                && c.gap_down(0, 1))
+                || (c2.color() == -1
+                    && c0.color() == 1
+                    && c0.close > c2.close + c2.body() * penetration
+                    && c.gap_down(1, 2)
+                    && c.gap_up(0, 1)))
+        {
+            c0.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLADVANCEBLOCK: three rising white candles losing strength, with shrinking bodies
+// or growing upper shadows
+pattern!(AdvanceBlock(),
+    lookback: max_period(&[SHADOW_LONG, SHADOW_SHORT, FAR, NEAR, BODY_LONG]) + 2,
+    averages: [
+        (SHADOW_SHORT, 2),
+        (SHADOW_SHORT, 1),
+        (SHADOW_SHORT, 0),
+        (SHADOW_LONG, 1),
+        (SHADOW_LONG, 0),
+        (NEAR, 2),
+        (NEAR, 1),
+        (FAR, 2),
+        (FAR, 1),
+        (BODY_LONG, 2),
+    ],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == 1
+            && c1.color() == 1
+            && c0.color() == 1
+            && c0.close > c1.close
+            && c1.close > c2.close
+            && c1.open > c2.open
+            && c1.open <= c2.close + c.avg(NEAR, 2)
+            && c0.open > c1.open
+            && c0.open <= c1.close + c.avg(NEAR, 1)
+            && c2.body() > c.avg(BODY_LONG, 2)
+            && c2.upper_shadow() < c.avg(SHADOW_SHORT, 2)
+            && (
+                // blocked at the 2nd, the 3rd does not carry on
+                (c1.body() < c2.body() - c.avg(FAR, 2) && c0.body() < c1.body() + c.avg(NEAR, 1))
+                // blocked at the 3rd
+                || c0.body() < c1.body() - c.avg(FAR, 1)
+                // shrinking bodies with some upper shadows
+                || (c0.body() < c1.body()
+                    && c1.body() < c2.body()
+                    && (c0.upper_shadow() > c.avg(SHADOW_SHORT, 0)
+                        || c1.upper_shadow() > c.avg(SHADOW_SHORT, 1)))
+                // a smaller 3rd with a long upper shadow
+                || (c0.body() < c1.body() && c0.upper_shadow() > c.avg(SHADOW_LONG, 0))
+            )
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLEVENINGDOJISTAR: a long white candle, a doji gapping up, and a black candle closing
+// deeper than `penetration` of the white body
+pattern!(EveningDojiStar(penetration = 0.3),
+    lookback: max_period(&[BODY_DOJI, BODY_LONG, BODY_SHORT]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_DOJI, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c2.color() == 1
+            && c1.body() <= c.avg(BODY_DOJI, 1)
+            && c.body_gap_up(1, 2)
+            && c0.body() > c.avg(BODY_SHORT, 0)
+            && c0.color() == -1
+            && c0.close < c2.close - c2.body() * penetration
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLEVENINGSTAR: a long white candle, a short body gapping up, and a black candle closing
+// deeper than `penetration` of the white body
+pattern!(EveningStar(penetration = 0.3),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_SHORT, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c2.color() == 1
+            && c1.body() <= c.avg(BODY_SHORT, 1)
+            && c.body_gap_up(1, 2)
+            && c0.body() > c.avg(BODY_SHORT, 0)
+            && c0.color() == -1
+            && c0.close < c2.close - c2.body() * penetration
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLGAPSIDESIDEWHITE: two white candles of the same size and open, both gapping
+// away from the first candle
+pattern!(GapSideSideWhite(),
+    lookback: max_period(&[NEAR, EQUAL]) + 2,
+    averages: [(NEAR, 1), (EQUAL, 1)],
+    |c| {
+        let (c1, c0) = (c.get(1), c.get(0));
+        let gap_up = c.body_gap_up(1, 2) && c.body_gap_up(0, 2);
+        let gap_down = c.body_gap_down(1, 2) && c.body_gap_down(0, 2);
+        if (gap_up || gap_down)
+            && c1.color() == 1
+            && c0.color() == 1
+            && c0.body() >= c1.body() - c.avg(NEAR, 1)
+            && c0.body() <= c1.body() + c.avg(NEAR, 1)
+            && c0.open >= c1.open - c.avg(EQUAL, 1)
+            && c0.open <= c1.open + c.avg(EQUAL, 1)
+        {
+            if c.body_gap_up(1, 2) {
+                100
+            } else {
+                -100
+            }
+        } else {
+            0
+        }
+    }
+);
+
+// CDLIDENTICAL3CROWS: three falling black candles, each opening at the close before
+pattern!(IdenticalThreeCrows(),
+    lookback: max_period(&[SHADOW_VERY_SHORT, EQUAL]) + 2,
+    averages: [
+        (SHADOW_VERY_SHORT, 2),
+        (SHADOW_VERY_SHORT, 1),
+        (SHADOW_VERY_SHORT, 0),
+        (EQUAL, 2),
+        (EQUAL, 1),
+    ],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == -1
+            && c2.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 2)
+            && c1.color() == -1
+            && c1.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 1)
+            && c0.color() == -1
+            && c0.lower_shadow() < c.avg(SHADOW_VERY_SHORT, 0)
+            && c2.close > c1.close
+            && c1.close > c0.close
+            && c1.open <= c2.close + c.avg(EQUAL, 2)
+            && c1.open >= c2.close - c.avg(EQUAL, 2)
+            && c0.open <= c1.close + c.avg(EQUAL, 1)
+            && c0.open >= c1.close - c.avg(EQUAL, 1)
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLMORNINGDOJISTAR: a long black candle, a doji gapping down, and a white candle closing
+// higher than `penetration` of the black body
+pattern!(MorningDojiStar(penetration = 0.3),
+    lookback: max_period(&[BODY_DOJI, BODY_LONG, BODY_SHORT]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_DOJI, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c2.color() == -1
+            && c1.body() <= c.avg(BODY_DOJI, 1)
+            && c.body_gap_down(1, 2)
+            && c0.body() > c.avg(BODY_SHORT, 0)
+            && c0.color() == 1
+            && c0.close > c2.close + c2.body() * penetration
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLMORNINGSTAR: a long black candle, a short body gapping down, and a white candle closing
+// higher than `penetration` of the black body
+pattern!(MorningStar(penetration = 0.3),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_SHORT, 1), (BODY_SHORT, 0)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c2.color() == -1
+            && c1.body() <= c.avg(BODY_SHORT, 1)
+            && c.body_gap_down(1, 2)
+            && c0.body() > c.avg(BODY_SHORT, 0)
+            && c0.color() == 1
+            && c0.close > c2.close + c2.body() * penetration
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLSTALLEDPATTERN: two long rising white candles, then a small one riding on the second's close
+pattern!(StalledPattern(),
+    lookback: max_period(&[BODY_LONG, BODY_SHORT, SHADOW_VERY_SHORT, NEAR]) + 2,
+    averages: [
+        (BODY_LONG, 2),
+        (BODY_LONG, 1),
+        (BODY_SHORT, 0),
+        (SHADOW_VERY_SHORT, 1),
+        (NEAR, 2),
+        (NEAR, 1),
+    ],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == 1
+            && c1.color() == 1
+            && c0.color() == 1
+            && c0.close > c1.close
+            && c1.close > c2.close
+            && c2.body() > c.avg(BODY_LONG, 2)
+            && c1.body() > c.avg(BODY_LONG, 1)
+            && c1.upper_shadow() < c.avg(SHADOW_VERY_SHORT, 1)
+            && c1.open > c2.open
+            && c1.open <= c2.close + c.avg(NEAR, 2)
+            && c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.open >= c1.close - c0.body() - c.avg(NEAR, 1)
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLSTICKSANDWICH: a white candle between two black candles with the same close
+pattern!(StickSandwich(),
+    lookback: EQUAL.period + 2,
+    averages: [(EQUAL, 2)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == -1
+            && c1.color() == 1
+            && c0.color() == -1
+            && c1.low > c2.close
+            && c0.close <= c2.close + c.avg(EQUAL, 2)
+            && c0.close >= c2.close - c.avg(EQUAL, 2)
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLTASUKIGAP: a gap, a candle in its direction, and an opposite candle of about the same size
+// closing inside the gap
+pattern!(TasukiGap(),
+    lookback: NEAR.period + 2,
+    averages: [(NEAR, 1)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        let same_size = (c1.body() - c0.body()).abs() < c.avg(NEAR, 1);
+        if (c.body_gap_up(1, 2)
+            && c1.color() == 1
+            && c0.color() == -1
+            && c0.open < c1.close
+            && c0.open > c1.open
+            && c0.close < c1.open
+            && c0.close > c2.body_top()
+            && same_size)
+            || (c.body_gap_down(1, 2)
+                && c1.color() == -1
+                && c0.color() == 1
+                && c0.open < c1.open
+                && c0.open > c1.close
+                && c0.close > c1.open
+                && c0.close < c2.body_bottom()
+                && same_size)
+        {
+            c1.color() * 100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLTRISTAR: three dojis, the middle one gapping away
+pattern!(Tristar(),
+    lookback: BODY_DOJI.period + 2,
+    averages: [(BODY_DOJI, 2)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        // all three are compared with the average before the first
+        let doji = c.avg(BODY_DOJI, 2);
+        if c2.body() > doji || c1.body() > doji || c0.body() > doji {
+            0
+        } else if c.body_gap_down(1, 2) && c0.body_bottom() > c1.body_bottom() {
+            100
+        } else if c.body_gap_up(1, 2) && c0.body_top() < c1.body_top() {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLUNIQUE3RIVER: a long black candle, a black harami with a lower low, and a small white candle
+pattern!(UniqueThreeRiver(),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_SHORT, 0)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.body() > c.avg(BODY_LONG, 2)
+            && c2.color() == -1
+            && c1.color() == -1
+            && c1.close > c2.close
+            && c1.open <= c2.open
+            && c1.low < c2.low
+            && c0.body() < c.avg(BODY_SHORT, 0)
+            && c0.color() == 1
+            && c0.open > c1.low
+        {
+            100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLUPSIDEGAP2CROWS: a long white candle, a short black one gapping up,
+// and a black one engulfing it but closing above the white
+pattern!(UpsideGapTwoCrows(),
+    lookback: max_period(&[BODY_SHORT, BODY_LONG]) + 2,
+    averages: [(BODY_LONG, 2), (BODY_SHORT, 1)],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == 1
+            && c2.body() > c.avg(BODY_LONG, 2)
+            && c1.color() == -1
+            && c1.body() <= c.avg(BODY_SHORT, 1)
+            && c.body_gap_up(1, 2)
+            && c0.color() == -1
+            && c0.open > c1.open
+            && c0.close < c1.close
+            && c0.close > c2.close
+        {
+            -100
+        } else {
+            0
+        }
+    }
+);
+
+// CDLXSIDEGAP3METHODS: two candles with a gap between them, and an opposite one
+// opening in the second body and closing in the first, filling the gap
+pattern!(XSideGapThreeMethods(),
+    lookback: 2,
+    averages: [],
+    |c| {
+        let (c2, c1, c0) = (c.get(2), c.get(1), c.get(0));
+        if c2.color() == c1.color()
+            && c1.color() == -c0.color()
+            && c0.open < c1.body_top()
+            && c0.open > c1.body_bottom()
+            && c0.close < c2.body_top()
+            && c0.close > c2.body_bottom()
+            && ((c2.color() == 1 && c.body_gap_up(1, 2))
+                || (c2.color() == -1 && c.body_gap_down(1, 2)))
+        {
+            c2.color() * 100
+        } else {
+            0
+        }
+    }
+);
@@ -0,0 +1,65 @@
+use numpy::{Element, PyArray1, PyArray2, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::borrow::Cow;
+
+// borrow the numpy buffer when it is contiguous, copy strided views
+pub fn as_slice<'a, T: Element + Clone>(arr: &'a PyReadonlyArray1<'_, T>) -> Cow<'a, [T]> {
+    match arr.as_slice() {
+        Ok(slice) => Cow::Borrowed(slice),
+        Err(_) => Cow::Owned(arr.as_array().to_vec()),
+    }
+}
+
+// all input arrays of one call must have the same length
+pub fn same_len(lens: &[usize]) -> PyResult<usize> {
+    let len = lens.first().copied().unwrap_or(0);
+    if lens.iter().any(|&l| l != len) {
+        return Err(PyValueError::new_err(
+            "input arrays must have the same length",
+        ));
+    }
+    Ok(len)
+}
+
+// IntoColumns turns per-step outputs into numpy arrays, one array per tuple field
+pub trait IntoColumns: Sized {
+    fn into_columns<'py>(py: Python<'py>, rows: Vec<Self>) -> PyResult<Bound<'py, PyAny>>;
+}
+
+impl IntoColumns for f64 {
+    fn into_columns<'py>(py: Python<'py>, rows: Vec<Self>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyArray1::from_vec(py, rows).into_any())
+    }
+}
+
+impl IntoColumns for i32 {
+    fn into_columns<'py>(py: Python<'py>, rows: Vec<Self>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyArray1::from_vec(py, rows).into_any())
+    }
+}
+
+// a variable number of outputs per step, e.g. several quantiles, becomes one 2-d array
+impl IntoColumns for Vec<f64> {
+    fn into_columns<'py>(py: Python<'py>, rows: Vec<Self>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyArray2::from_vec2(py, &rows)?.into_any())
+    }
+}
+
+macro_rules! impl_columns {
+    ($(($t:ty, $idx:tt)),+) => {
+        impl IntoColumns for ($($t,)+) {
+            fn into_columns<'py>(py: Python<'py>, rows: Vec<Self>) -> PyResult<Bound<'py, PyAny>> {
+                let columns = ($(rows.iter().map(|row| row.$idx).collect::<Vec<$t>>(),)+);
+                Ok(($(PyArray1::from_vec(py, columns.$idx),)+).into_pyobject(py)?.into_any())
+            }
+        }
+    };
+}
+
+impl_columns!((f64, 0), (f64, 1));
+impl_columns!((f64, 0), (f64, 1), (f64, 2));
+impl_columns!((f64, 0), (f64, 1), (f64, 2), (f64, 3));
+impl_columns!((f64, 0), (f64, 1), (f64, 2), (f64, 3), (f64, 4));
+impl_columns!((usize, 0), (f64, 1));
+impl_columns!((usize, 0), (f64, 1), (usize, 2), (f64, 3));
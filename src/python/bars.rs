@@ -0,0 +1,346 @@
+use super::arrays::IntoColumns;
+use super::pywrap;
+use crate::bars::{imbalance, resample, threshold, time, Bar, Measure};
+use numpy::PyArray1;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyString, PyTuple};
+
+// the python side of a completed or forming bar, read-only
+#[pyclass(name = "Bar", frozen, get_all)]
+pub struct PyBar {
+    start: i64,
+    end: i64,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    dollar: f64,
+    trades: usize,
+}
+
+#[pymethods]
+impl PyBar {
+    pub fn vwap(&self) -> f64 {
+        self.dollar / self.volume
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Bar(start={}, end={}, open={}, high={}, low={}, close={}, volume={}, dollar={}, trades={})",
+            self.start, self.end, self.open, self.high, self.low, self.close, self.volume, self.dollar, self.trades
+        )
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Bar {
+    type Target = PyBar;
+    type Output = Bound<'py, PyBar>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyBar>> {
+        Bound::new(
+            py,
+            PyBar {
+                start: self.start,
+                end: self.end,
+                open: self.open,
+                high: self.high,
+                low: self.low,
+                close: self.close,
+                volume: self.volume,
+                dollar: self.dollar,
+                trades: self.trades,
+            },
+        )
+    }
+}
+
+// a measure is given as "tick", "volume" or "dollar"
+impl<'a, 'py> FromPyObject<'a, 'py> for Measure {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        let name: String = obj.extract()?;
+        match name.as_str() {
+            "tick" => Ok(Measure::Tick),
+            "volume" => Ok(Measure::Volume),
+            "dollar" => Ok(Measure::Dollar),
+            _ => Err(PyValueError::new_err(format!(
+                "unknown measure {name:?}, expected one of tick, volume, dollar"
+            ))),
+        }
+    }
+}
+
+impl<'py> IntoPyObject<'py> for Measure {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = std::convert::Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Bound<'py, PyString>, Self::Error> {
+        let name = match self {
+            Measure::Tick => "tick",
+            Measure::Volume => "volume",
+            Measure::Dollar => "dollar",
+        };
+        Ok(PyString::new(py, name))
+    }
+}
+
+// update_many returns the completed bars only, as a dict of arrays ready for the batch functions
+impl IntoColumns for Option<Bar> {
+    fn into_columns<'py>(py: Python<'py>, rows: Vec<Self>) -> PyResult<Bound<'py, PyAny>> {
+        let bars: Vec<Bar> = rows.into_iter().flatten().collect();
+        let columns = PyDict::new(py);
+        columns.set_item(
+            "start",
+            PyArray1::from_iter(py, bars.iter().map(|b| b.start)),
+        )?;
+        columns.set_item("end", PyArray1::from_iter(py, bars.iter().map(|b| b.end)))?;
+        columns.set_item("open", PyArray1::from_iter(py, bars.iter().map(|b| b.open)))?;
+        columns.set_item("high", PyArray1::from_iter(py, bars.iter().map(|b| b.high)))?;
+        columns.set_item("low", PyArray1::from_iter(py, bars.iter().map(|b| b.low)))?;
+        columns.set_item(
+            "close",
+            PyArray1::from_iter(py, bars.iter().map(|b| b.close)),
+        )?;
+        columns.set_item(
+            "volume",
+            PyArray1::from_iter(py, bars.iter().map(|b| b.volume)),
+        )?;
+        columns.set_item(
+            "dollar",
+            PyArray1::from_iter(py, bars.iter().map(|b| b.dollar)),
+        )?;
+        columns.set_item(
+            "trades",
+            PyArray1::from_iter(py, bars.iter().map(|b| b.trades)),
+        )?;
+        Ok(columns.into_any())
+    }
+}
+
+pywrap!(TimeBarer(time::TimeBarer), new(interval: i64), update(timestamp: i64, price: f64, size: f64) -> Option<Bar>, {
+    // completes the forming bar once the clock is past its interval
+    pub fn advance(&mut self, timestamp: i64) -> Option<Bar> {
+        let bar = self.inner.current().filter(|bar| timestamp >= bar.end);
+        self.inner.modify(|inner| {
+            inner.advance(timestamp);
+        });
+        bar
+    }
+
+    pub fn current(&self) -> Option<Bar> {
+        self.inner.current()
+    }
+
+    pub fn flush(&mut self) -> Option<Bar> {
+        let bar = self.inner.current();
+        self.inner.modify(|inner| {
+            inner.flush();
+        });
+        bar
+    }
+});
+pywrap!(TickBarer(threshold::TickBarer), new(ticks: usize), update(timestamp: i64, price: f64, size: f64) -> Option<Bar>, {
+    pub fn current(&self) -> Option<Bar> {
+        self.inner.current()
+    }
+
+    pub fn flush(&mut self) -> Option<Bar> {
+        let bar = self.inner.current();
+        self.inner.modify(|inner| {
+            inner.flush();
+        });
+        bar
+    }
+});
+pywrap!(VolumeBarer(threshold::VolumeBarer), new(volume: f64), update(timestamp: i64, price: f64, size: f64) -> Option<Bar>, {
+    pub fn current(&self) -> Option<Bar> {
+        self.inner.current()
+    }
+
+    pub fn flush(&mut self) -> Option<Bar> {
+        let bar = self.inner.current();
+        self.inner.modify(|inner| {
+            inner.flush();
+        });
+        bar
+    }
+});
+pywrap!(DollarBarer(threshold::DollarBarer), new(dollars: f64), update(timestamp: i64, price: f64, size: f64) -> Option<Bar>, {
+    pub fn current(&self) -> Option<Bar> {
+        self.inner.current()
+    }
+
+    pub fn flush(&mut self) -> Option<Bar> {
+        let bar = self.inner.current();
+        self.inner.modify(|inner| {
+            inner.flush();
+        });
+        bar
+    }
+});
+pywrap!(ImbalanceBarer(imbalance::ImbalanceBarer), new(measure: Measure, initial_ticks: usize, alpha: f64), update(timestamp: i64, price: f64, size: f64) -> Option<Bar>, {
+    pub fn current(&self) -> Option<Bar> {
+        self.inner.current()
+    }
+
+    pub fn flush(&mut self) -> Option<Bar> {
+        let bar = self.inner.current();
+        self.inner.modify(|inner| {
+            inner.flush();
+        });
+        bar
+    }
+
+    // the |imbalance| that completes the forming bar
+    pub fn threshold(&self) -> f64 {
+        self.inner.threshold()
+    }
+
+    // signed imbalance of the forming bar
+    pub fn imbalance(&self) -> f64 {
+        self.inner.imbalance()
+    }
+});
+
+// Resampler runs any indicator on a higher timeframe while it is fed lower timeframe bars.
+// fields names the bar fields passed to the indicator's update, in order, out of
+// open, high, low, close, volume and preclose (the close of the bar before),
+// e.g. ("high", "low", "close") for ATR or ("high", "low", "preclose") for ADX.
+// the timestamps are the open times of the fed bars, as for ta::bars::resample::Resample.
+#[pyclass]
+pub struct Resampler {
+    resample: resample::Resample,
+    indicator: Py<PyAny>,
+    fields: Vec<String>,
+}
+
+impl Resampler {
+    fn args<'py>(
+        &self,
+        py: Python<'py>,
+        bar: &Bar,
+        preclose: f64,
+    ) -> PyResult<Bound<'py, PyTuple>> {
+        let values = self.fields.iter().map(|field| match field.as_str() {
+            "open" => bar.open,
+            "high" => bar.high,
+            "low" => bar.low,
+            "close" => bar.close,
+            "volume" => bar.volume,
+            _ => preclose,
+        });
+        PyTuple::new(py, values)
+    }
+
+    fn feed(&self, py: Python<'_>, completed: Option<(Bar, f64)>) -> PyResult<()> {
+        if let Some((bar, preclose)) = completed {
+            self.indicator
+                .call_method1(py, "update", self.args(py, &bar, preclose)?)?;
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl Resampler {
+    #[new]
+    #[pyo3(signature = (indicator, timeframe, fields = vec![String::from("close")]))]
+    pub fn new(indicator: Py<PyAny>, timeframe: i64, fields: Vec<String>) -> PyResult<Self> {
+        let known = ["open", "high", "low", "close", "volume", "preclose"];
+        if let Some(field) = fields.iter().find(|field| !known.contains(&field.as_str())) {
+            return Err(PyValueError::new_err(format!(
+                "unknown field {field:?}, expected some of open, high, low, close, volume, preclose"
+            )));
+        }
+        Ok(Self {
+            resample: resample::Resample::new(timeframe),
+            indicator,
+            fields,
+        })
+    }
+
+    // returns (closed, provisional): the output as of the last completed bar,
+    // and the output if the forming bar closed now
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        py: Python<'_>,
+        timestamp: i64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> PyResult<(Py<PyAny>, Py<PyAny>)> {
+        let completed = self
+            .resample
+            .update(timestamp, open, high, low, close, volume);
+        self.feed(py, completed)?;
+        self.value(py)
+    }
+
+    // completes the forming bar once the clock is past its period
+    pub fn advance(&mut self, py: Python<'_>, timestamp: i64) -> PyResult<(Py<PyAny>, Py<PyAny>)> {
+        let completed = self.resample.advance(timestamp);
+        self.feed(py, completed)?;
+        self.value(py)
+    }
+
+    pub fn value(&self, py: Python<'_>) -> PyResult<(Py<PyAny>, Py<PyAny>)> {
+        let closed = self.indicator.call_method0(py, "value")?;
+        let provisional = match self.resample.current() {
+            Some((bar, preclose)) => {
+                self.indicator
+                    .call_method1(py, "peek", self.args(py, &bar, preclose)?)?
+            }
+            None => closed.clone_ref(py),
+        };
+        Ok((closed, provisional))
+    }
+
+    // the higher timeframe bar still forming
+    pub fn current(&self) -> Option<Bar> {
+        self.resample.current().map(|(bar, _)| bar)
+    }
+
+    // the indicator as of the last completed bar
+    #[getter]
+    pub fn indicator(&self, py: Python<'_>) -> Py<PyAny> {
+        self.indicator.clone_ref(py)
+    }
+
+    pub fn __getnewargs__(&self, py: Python<'_>) -> (Py<PyAny>, i64, Vec<String>) {
+        (
+            self.indicator.clone_ref(py),
+            self.resample.timeframe(),
+            self.fields.clone(),
+        )
+    }
+
+    pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let state = super::state::to_bytes(&self.resample)?;
+        Ok(PyBytes::new(py, &state))
+    }
+
+    pub fn __setstate__(&mut self, state: &[u8]) -> PyResult<()> {
+        self.resample = super::state::from_bytes(state)?;
+        Ok(())
+    }
+}
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let bars = PyModule::new(parent_module.py(), "bars")?;
+    bars.add_class::<PyBar>()?;
+    bars.add_class::<TimeBarer>()?;
+    bars.add_class::<TickBarer>()?;
+    bars.add_class::<VolumeBarer>()?;
+    bars.add_class::<DollarBarer>()?;
+    bars.add_class::<ImbalanceBarer>()?;
+    bars.add_class::<Resampler>()?;
+    super::add_submodule(parent_module, &bars)
+}
@@ -0,0 +1,870 @@
+use super::arrays::{as_slice, same_len, IntoColumns};
+use crate::batch::{prev, run};
+use crate::indicator::overlap::MAType;
+use crate::indicator::{cycle, momentum, overlap, price, volatility, volume};
+use crate::pattern::{double, hikkake, multi, single, triple};
+use crate::rolling::{corr, minmax, statis};
+use numpy::PyReadonlyArray1;
+use pyo3::prelude::*;
+
+// batch! defines a TA-Lib style function over numpy arrays.
+// the indicator runs with the GIL released, `|i| input` builds the i-th input from the arrays,
+// the optional `=> |out| expr` reorders outputs to the TA-Lib convention.
+macro_rules! batch {
+    (
+        $fn_name:ident, $py_name:literal,
+        ($($arr:ident),+), ($($param:ident: $param_ty:ty = $default:expr),*),
+        $indicator:expr, |$i:ident| $input:expr
+        $(, => |$out:pat_param| $map:expr)?
+    ) => {
+        #[pyfunction]
+        #[pyo3(name = $py_name, signature = ($($arr,)+ $($param = $default),*))]
+        #[allow(clippy::too_many_arguments)]
+        fn $fn_name<'py>(
+            py: Python<'py>,
+            $($arr: PyReadonlyArray1<'py, f64>,)+
+            $($param: $param_ty,)*
+        ) -> PyResult<Bound<'py, PyAny>> {
+            $(let $arr = as_slice(&$arr);)+
+            let len = same_len(&[$($arr.len()),+])?;
+            let rows = py.detach(|| {
+                let rows = run($indicator, (0..len).map(|$i| $input));
+                $(let rows = rows.into_iter().map(|$out| $map).collect::<Vec<_>>();)?
+                rows
+            });
+            IntoColumns::into_columns(py, rows)
+        }
+    };
+}
+
+// overlap
+batch!(bbands, "BBANDS", (real), (timeperiod: usize = 5, nbdevup: f64 = 2.0, nbdevdn: f64 = 2.0, matype: MAType = MAType::SMA),
+    overlap::BBands::new(timeperiod, nbdevup, nbdevdn, matype), |i| real[i],
+    => |(lower, middle, upper)| (upper, middle, lower));
+batch!(dema, "DEMA", (real), (timeperiod: usize = 30), overlap::DEMA::new(timeperiod), |i| real[i]);
+batch!(ema, "EMA", (real), (timeperiod: usize = 30), overlap::EMA::new(timeperiod), |i| real[i]);
+batch!(
+    ht_trendline,
+    "HT_TRENDLINE",
+    (real),
+    (),
+    overlap::HTTrendline::new(),
+    |i| real[i]
+);
+batch!(kama, "KAMA", (real), (timeperiod: usize = 10, fastperiod: usize = 2, slowperiod: usize = 30),
+    overlap::KAMA::new(timeperiod, fastperiod, slowperiod), |i| (real[i], prev(&real, i)));
+batch!(ma, "MA", (real), (timeperiod: usize = 30, matype: MAType = MAType::SMA),
+    overlap::MA::new(timeperiod, matype), |i| real[i]);
+batch!(mama, "MAMA", (real), (fastlimit: f64 = 0.5, slowlimit: f64 = 0.05),
+    overlap::MAMA::new(fastlimit, slowlimit), |i| real[i]);
+batch!(mavp, "MAVP", (real, periods),
+    (minperiod: usize = 2, maxperiod: usize = 30, matype: MAType = MAType::SMA),
+    overlap::MAVP::new(minperiod, maxperiod, matype), |i| (real[i], periods[i]));
+batch!(midpoint, "MIDPOINT", (real), (timeperiod: usize = 14), overlap::MIDPOINT::new(timeperiod), |i| real[i]);
+batch!(midprice, "MIDPRICE", (high, low), (timeperiod: usize = 14),
+    overlap::MIDPRICE::new(timeperiod), |i| (high[i], low[i]));
+batch!(sar, "SAR", (high, low), (acceleration: f64 = 0.02, maximum: f64 = 0.2),
+    overlap::SAR::new(acceleration, maximum), |i| (high[i], low[i]), => |(sar, _)| sar);
+batch!(sarext, "SAREXT", (high, low),
+    (startvalue: f64 = 0.0, offsetonreverse: f64 = 0.0,
+     accelerationinitlong: f64 = 0.02, accelerationlong: f64 = 0.02, accelerationmaxlong: f64 = 0.2,
+     accelerationinitshort: f64 = 0.02, accelerationshort: f64 = 0.02, accelerationmaxshort: f64 = 0.2),
+    overlap::SAREXT::new(startvalue, offsetonreverse, accelerationinitlong, accelerationlong,
+        accelerationmaxlong, accelerationinitshort, accelerationshort, accelerationmaxshort),
+    |i| (high[i], low[i]), => |(sar, direction)| sar * direction);
+batch!(sma, "SMA", (real), (timeperiod: usize = 30), overlap::SMA::new(timeperiod), |i| real[i]);
+batch!(t3, "T3", (real), (timeperiod: usize = 5, vfactor: f64 = 0.7),
+    overlap::T3::new(timeperiod, vfactor), |i| real[i]);
+batch!(tema, "TEMA", (real), (timeperiod: usize = 30), overlap::TEMA::new(timeperiod), |i| real[i]);
+batch!(trima, "TRIMA", (real), (timeperiod: usize = 30), overlap::TRIMA::new(timeperiod), |i| real[i]);
+batch!(wma, "WMA", (real), (timeperiod: usize = 30), overlap::WMA::new(timeperiod), |i| real[i]);
+
+// price
+batch!(
+    avgprice,
+    "AVGPRICE",
+    (open, high, low, close),
+    (),
+    price::AvgPrice::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    medprice,
+    "MEDPRICE",
+    (high, low),
+    (),
+    price::MedPrice::new(),
+    |i| (high[i], low[i])
+);
+batch!(
+    typprice,
+    "TYPPRICE",
+    (high, low, close),
+    (),
+    price::TypicalPrice::new(),
+    |i| (high[i], low[i], close[i])
+);
+batch!(
+    wclprice,
+    "WCLPRICE",
+    (high, low, close),
+    (),
+    price::WeightedClose::new(),
+    |i| (high[i], low[i], close[i])
+);
+
+// volatility
+batch!(
+    trange,
+    "TRANGE",
+    (high, low, close),
+    (),
+    volatility::TR::new(),
+    |i| (high[i], low[i], close[i])
+);
+batch!(atr, "ATR", (high, low, close), (timeperiod: usize = 14),
+    volatility::ATR::new(timeperiod), |i| (high[i], low[i], close[i]));
+batch!(natr, "NATR", (high, low, close), (timeperiod: usize = 14),
+    volatility::NATR::new(timeperiod), |i| (high[i], low[i], close[i]));
+
+// volume, AD, ADOSC and OBV sum over a rolling timeperiod where TA-Lib sums the whole series
+batch!(ad, "AD", (high, low, close, volume), (timeperiod: usize = 14),
+    volume::AD::new(timeperiod), |i| (high[i], low[i], close[i], volume[i]));
+batch!(adosc, "ADOSC", (high, low, close, volume), (fastperiod: usize = 3, slowperiod: usize = 10, timeperiod: usize = 14),
+    volume::ADOSC::new(timeperiod, fastperiod, slowperiod), |i| (high[i], low[i], close[i], volume[i]));
+batch!(obv, "OBV", (close, volume), (timeperiod: usize = 14),
+    volume::OBV::new(timeperiod), |i| (close[i], volume[i]));
+batch!(cmf, "CMF", (high, low, close, volume), (timeperiod: usize = 20),
+    volume::CMF::new(timeperiod), |i| (high[i], low[i], close[i], volume[i]));
+batch!(fi, "FI", (close, volume), (timeperiod: usize = 13),
+    volume::FI::new(timeperiod), |i| (close[i], volume[i]));
+batch!(emv, "EMV", (high, low, volume), (timeperiod: usize = 14, scale: f64 = 100_000_000.0),
+    volume::EMV::new(timeperiod, scale), |i| (high[i], low[i], volume[i]));
+batch!(pvt, "PVT", (close, volume), (), volume::PVT::new(), |i| (
+    close[i], volume[i]
+));
+batch!(nvi, "NVI", (close, volume), (), volume::NVI::new(), |i| (
+    close[i], volume[i]
+));
+batch!(pvi, "PVI", (close, volume), (), volume::PVI::new(), |i| (
+    close[i], volume[i]
+));
+batch!(kvo, "KVO", (high, low, close, volume), (fastperiod: usize = 34, slowperiod: usize = 55, signalperiod: usize = 13),
+    volume::KVO::new(fastperiod, slowperiod, signalperiod), |i| (high[i], low[i], close[i], volume[i]));
+batch!(vwma, "VWMA", (close, volume), (timeperiod: usize = 20),
+    volume::VWMA::new(timeperiod), |i| (close[i], volume[i]));
+batch!(vosc, "VOSC", (volume), (fastperiod: usize = 5, slowperiod: usize = 10),
+    volume::VOSC::new(fastperiod, slowperiod), |i| volume[i]);
+
+// cycle
+batch!(
+    ht_dcperiod,
+    "HT_DCPERIOD",
+    (real),
+    (),
+    cycle::HTDCPeriod::new(),
+    |i| real[i]
+);
+batch!(
+    ht_dcphase,
+    "HT_DCPHASE",
+    (real),
+    (),
+    cycle::HTDCPhase::new(),
+    |i| real[i]
+);
+batch!(
+    ht_phasor,
+    "HT_PHASOR",
+    (real),
+    (),
+    cycle::HTPhasor::new(),
+    |i| real[i]
+);
+batch!(ht_sine, "HT_SINE", (real), (), cycle::HTSine::new(), |i| {
+    real[i]
+});
+batch!(
+    ht_trendmode,
+    "HT_TRENDMODE",
+    (real),
+    (),
+    cycle::HTTrendMode::new(),
+    |i| real[i]
+);
+
+// momentum
+batch!(adx, "ADX", (high, low, close), (timeperiod: usize = 14),
+    momentum::ADX::new(timeperiod), |i| (high[i], low[i], prev(&close, i)));
+batch!(adxr, "ADXR", (high, low, close), (timeperiod: usize = 14),
+    momentum::ADXR::new(timeperiod), |i| (high[i], low[i], prev(&close, i)));
+batch!(apo, "APO", (real), (fastperiod: usize = 12, slowperiod: usize = 26, matype: MAType = MAType::SMA),
+    momentum::APO::new(fastperiod, slowperiod, matype), |i| real[i]);
+batch!(aroon, "AROON", (high, low), (timeperiod: usize = 14),
+    momentum::Aroon::new(timeperiod), |i| (high[i], low[i]),
+    => |(aroon_up, aroon_down)| (aroon_down, aroon_up));
+batch!(aroonosc, "AROONOSC", (high, low), (timeperiod: usize = 14),
+    momentum::AroonOsc::new(timeperiod), |i| (high[i], low[i]));
+batch!(
+    bop,
+    "BOP",
+    (open, high, low, close),
+    (),
+    momentum::BOP::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(cci, "CCI", (high, low, close), (timeperiod: usize = 14),
+    momentum::CCI::new(timeperiod), |i| (high[i], low[i], close[i]));
+batch!(cmo, "CMO", (real), (timeperiod: usize = 14), momentum::CMO::new(timeperiod), |i| real[i]);
+batch!(dx, "DX", (high, low, close), (timeperiod: usize = 14),
+    momentum::DX::new(timeperiod), |i| (high[i], low[i], close[i]));
+batch!(macd, "MACD", (real), (fastperiod: usize = 12, slowperiod: usize = 26, signalperiod: usize = 9),
+    momentum::MACD::new(fastperiod, slowperiod, signalperiod), |i| real[i]);
+batch!(macdext, "MACDEXT", (real),
+    (fastperiod: usize = 12, fastmatype: MAType = MAType::SMA, slowperiod: usize = 26,
+     slowmatype: MAType = MAType::SMA, signalperiod: usize = 9, signalmatype: MAType = MAType::SMA),
+    momentum::MACDEXT::new(fastperiod, fastmatype, slowperiod, slowmatype, signalperiod, signalmatype),
+    |i| real[i]);
+batch!(mfi, "MFI", (high, low, close, volume), (timeperiod: usize = 14),
+    momentum::MFI::new(timeperiod), |i| (high[i], low[i], close[i], volume[i]));
+batch!(minus_di, "MINUS_DI", (high, low, close), (timeperiod: usize = 14),
+    momentum::MinusDI::new(timeperiod), |i| (high[i], low[i], close[i]));
+batch!(minus_dm, "MINUS_DM", (high, low), (timeperiod: usize = 14),
+    momentum::MinusDM::new(timeperiod), |i| (high[i], low[i]));
+batch!(mom, "MOM", (real), (timeperiod: usize = 10), momentum::MOM::new(timeperiod), |i| real[i]);
+batch!(plus_di, "PLUS_DI", (high, low, close), (timeperiod: usize = 14),
+    momentum::PlusDI::new(timeperiod), |i| (high[i], low[i], close[i]));
+batch!(plus_dm, "PLUS_DM", (high, low), (timeperiod: usize = 14),
+    momentum::PlusDM::new(timeperiod), |i| (high[i], low[i]));
+batch!(ppo, "PPO", (real), (fastperiod: usize = 12, slowperiod: usize = 26, matype: MAType = MAType::SMA),
+    momentum::PPO::new(fastperiod, slowperiod, matype), |i| real[i]);
+batch!(roc, "ROC", (real), (timeperiod: usize = 10), momentum::ROC::new(timeperiod), |i| real[i]);
+batch!(rocr, "ROCR", (real), (timeperiod: usize = 10), momentum::ROCR::new(timeperiod), |i| real[i]);
+batch!(rsi, "RSI", (real), (timeperiod: usize = 14), momentum::RSI::new(timeperiod), |i| real[i]);
+batch!(stoch, "STOCH", (high, low, close),
+    (fastk_period: usize = 5, slowk_period: usize = 3, slowk_matype: MAType = MAType::SMA,
+     slowd_period: usize = 3, slowd_matype: MAType = MAType::SMA),
+    momentum::Stoch::new(fastk_period, slowk_period, slowk_matype, slowd_period, slowd_matype),
+    |i| (high[i], low[i], close[i]));
+batch!(stochf, "STOCHF", (high, low, close), (fastk_period: usize = 5, fastd_period: usize = 3, fastd_matype: MAType = MAType::SMA),
+    momentum::StochCHF::new(fastk_period, fastd_period, fastd_matype), |i| (high[i], low[i], close[i]));
+batch!(stochrsi, "STOCHRSI", (real),
+    (timeperiod: usize = 14, fastk_period: usize = 5, fastd_period: usize = 3, fastd_matype: MAType = MAType::SMA),
+    momentum::StochRSI::new(timeperiod, fastk_period, fastd_period, fastd_matype), |i| real[i]);
+batch!(trix, "TRIX", (real), (timeperiod: usize = 30), momentum::TRIX::new(timeperiod), |i| real[i]);
+batch!(ultosc, "ULTOSC", (high, low, close), (timeperiod1: usize = 7, timeperiod2: usize = 14, timeperiod3: usize = 28),
+    momentum::ULTOSC::new(timeperiod1, timeperiod2, timeperiod3), |i| (high[i], low[i], close[i], prev(&close, i)));
+batch!(willr, "WILLR", (high, low, close), (timeperiod: usize = 14),
+    momentum::WILLR::new(timeperiod), |i| (high[i], low[i], close[i]));
+
+// math operator & statistic
+batch!(sum, "SUM", (real), (timeperiod: usize = 30), statis::Sumer::new(timeperiod), |i| real[i]);
+batch!(max, "MAX", (real), (timeperiod: usize = 30), minmax::Maxer::new(timeperiod), |i| real[i]);
+batch!(min, "MIN", (real), (timeperiod: usize = 30), minmax::Miner::new(timeperiod), |i| real[i]);
+batch!(minmax_, "MINMAX", (real), (timeperiod: usize = 30), minmax::MinMaxer::new(timeperiod), |i| real[i]);
+batch!(beta, "BETA", (real0, real1), (timeperiod: usize = 5),
+    corr::Beta::new(timeperiod), |i| (real0[i], real1[i]));
+batch!(correl, "CORREL", (real0, real1), (timeperiod: usize = 30),
+    corr::Correlationer::new(timeperiod), |i| (real0[i], real1[i]));
+batch!(linearreg, "LINEARREG", (real), (timeperiod: usize = 14),
+    corr::LinearReg::new(timeperiod), |i| real[i], => |(_, _, last_reg, _)| last_reg);
+batch!(linearreg_angle, "LINEARREG_ANGLE", (real), (timeperiod: usize = 14),
+    corr::LinearReg::new(timeperiod), |i| real[i], => |(_, _, _, angle)| angle);
+batch!(linearreg_intercept, "LINEARREG_INTERCEPT", (real), (timeperiod: usize = 14),
+    corr::LinearReg::new(timeperiod), |i| real[i], => |(_, intercept, _, _)| intercept);
+batch!(linearreg_slope, "LINEARREG_SLOPE", (real), (timeperiod: usize = 14),
+    corr::LinearReg::new(timeperiod), |i| real[i], => |(slope, _, _, _)| slope);
+batch!(tsf, "TSF", (real), (timeperiod: usize = 14), corr::TSF::new(timeperiod), |i| real[i]);
+
+// pattern recognition
+batch!(
+    cdl2crows,
+    "CDL2CROWS",
+    (open, high, low, close),
+    (),
+    triple::TwoCrows::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdl3blackcrows,
+    "CDL3BLACKCROWS",
+    (open, high, low, close),
+    (),
+    multi::ThreeBlackCrows::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdl3inside,
+    "CDL3INSIDE",
+    (open, high, low, close),
+    (),
+    triple::ThreeInside::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdl3linestrike,
+    "CDL3LINESTRIKE",
+    (open, high, low, close),
+    (),
+    multi::ThreeLineStrike::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdl3outside,
+    "CDL3OUTSIDE",
+    (open, high, low, close),
+    (),
+    triple::ThreeOutside::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdl3starsinsouth,
+    "CDL3STARSINSOUTH",
+    (open, high, low, close),
+    (),
+    triple::ThreeStarsInSouth::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdl3whitesoldiers,
+    "CDL3WHITESOLDIERS",
+    (open, high, low, close),
+    (),
+    triple::ThreeWhiteSoldiers::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(cdlabandonedbaby, "CDLABANDONEDBABY", (open, high, low, close), (penetration: f64 = 0.3),
+    triple::AbandonedBaby::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(
+    cdladvanceblock,
+    "CDLADVANCEBLOCK",
+    (open, high, low, close),
+    (),
+    triple::AdvanceBlock::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlbelthold,
+    "CDLBELTHOLD",
+    (open, high, low, close),
+    (),
+    single::BeltHold::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlbreakaway,
+    "CDLBREAKAWAY",
+    (open, high, low, close),
+    (),
+    multi::Breakaway::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlclosingmarubozu,
+    "CDLCLOSINGMARUBOZU",
+    (open, high, low, close),
+    (),
+    single::ClosingMarubozu::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlconcealbabyswall,
+    "CDLCONCEALBABYSWALL",
+    (open, high, low, close),
+    (),
+    multi::ConcealBabySwallow::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlcounterattack,
+    "CDLCOUNTERATTACK",
+    (open, high, low, close),
+    (),
+    double::Counterattack::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(cdldarkcloudcover, "CDLDARKCLOUDCOVER", (open, high, low, close), (penetration: f64 = 0.5),
+    double::DarkCloudCover::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(
+    cdldoji,
+    "CDLDOJI",
+    (open, high, low, close),
+    (),
+    single::Doji::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdldojistar,
+    "CDLDOJISTAR",
+    (open, high, low, close),
+    (),
+    double::DojiStar::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdldragonflydoji,
+    "CDLDRAGONFLYDOJI",
+    (open, high, low, close),
+    (),
+    single::DragonflyDoji::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlengulfing,
+    "CDLENGULFING",
+    (open, high, low, close),
+    (),
+    double::Engulfing::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(cdleveningdojistar, "CDLEVENINGDOJISTAR", (open, high, low, close), (penetration: f64 = 0.3),
+    triple::EveningDojiStar::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(cdleveningstar, "CDLEVENINGSTAR", (open, high, low, close), (penetration: f64 = 0.3),
+    triple::EveningStar::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(
+    cdlgapsidesidewhite,
+    "CDLGAPSIDESIDEWHITE",
+    (open, high, low, close),
+    (),
+    triple::GapSideSideWhite::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlgravestonedoji,
+    "CDLGRAVESTONEDOJI",
+    (open, high, low, close),
+    (),
+    single::GravestoneDoji::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlhammer,
+    "CDLHAMMER",
+    (open, high, low, close),
+    (),
+    double::Hammer::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlhangingman,
+    "CDLHANGINGMAN",
+    (open, high, low, close),
+    (),
+    double::HangingMan::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlharami,
+    "CDLHARAMI",
+    (open, high, low, close),
+    (),
+    double::Harami::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlharamicross,
+    "CDLHARAMICROSS",
+    (open, high, low, close),
+    (),
+    double::HaramiCross::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlhighwave,
+    "CDLHIGHWAVE",
+    (open, high, low, close),
+    (),
+    single::HighWave::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlhikkake,
+    "CDLHIKKAKE",
+    (open, high, low, close),
+    (),
+    hikkake::Hikkake::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlhikkakemod,
+    "CDLHIKKAKEMOD",
+    (open, high, low, close),
+    (),
+    hikkake::HikkakeMod::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlhomingpigeon,
+    "CDLHOMINGPIGEON",
+    (open, high, low, close),
+    (),
+    double::HomingPigeon::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlidentical3crows,
+    "CDLIDENTICAL3CROWS",
+    (open, high, low, close),
+    (),
+    triple::IdenticalThreeCrows::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlinneck,
+    "CDLINNECK",
+    (open, high, low, close),
+    (),
+    double::InNeck::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlinvertedhammer,
+    "CDLINVERTEDHAMMER",
+    (open, high, low, close),
+    (),
+    double::InvertedHammer::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlkicking,
+    "CDLKICKING",
+    (open, high, low, close),
+    (),
+    double::Kicking::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlkickingbylength,
+    "CDLKICKINGBYLENGTH",
+    (open, high, low, close),
+    (),
+    double::KickingByLength::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlladderbottom,
+    "CDLLADDERBOTTOM",
+    (open, high, low, close),
+    (),
+    multi::LadderBottom::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdllongleggeddoji,
+    "CDLLONGLEGGEDDOJI",
+    (open, high, low, close),
+    (),
+    single::LongLeggedDoji::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdllongline,
+    "CDLLONGLINE",
+    (open, high, low, close),
+    (),
+    single::LongLine::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlmarubozu,
+    "CDLMARUBOZU",
+    (open, high, low, close),
+    (),
+    single::Marubozu::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlmatchinglow,
+    "CDLMATCHINGLOW",
+    (open, high, low, close),
+    (),
+    double::MatchingLow::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(cdlmathold, "CDLMATHOLD", (open, high, low, close), (penetration: f64 = 0.5),
+    multi::MatHold::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(cdlmorningdojistar, "CDLMORNINGDOJISTAR", (open, high, low, close), (penetration: f64 = 0.3),
+    triple::MorningDojiStar::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(cdlmorningstar, "CDLMORNINGSTAR", (open, high, low, close), (penetration: f64 = 0.3),
+    triple::MorningStar::new(penetration), |i| (open[i], high[i], low[i], close[i]));
+batch!(
+    cdlonneck,
+    "CDLONNECK",
+    (open, high, low, close),
+    (),
+    double::OnNeck::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlpiercing,
+    "CDLPIERCING",
+    (open, high, low, close),
+    (),
+    double::Piercing::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlrickshawman,
+    "CDLRICKSHAWMAN",
+    (open, high, low, close),
+    (),
+    single::RickshawMan::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlrisefall3methods,
+    "CDLRISEFALL3METHODS",
+    (open, high, low, close),
+    (),
+    multi::RiseFallThreeMethods::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlseparatinglines,
+    "CDLSEPARATINGLINES",
+    (open, high, low, close),
+    (),
+    double::SeparatingLines::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlshootingstar,
+    "CDLSHOOTINGSTAR",
+    (open, high, low, close),
+    (),
+    double::ShootingStar::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlshortline,
+    "CDLSHORTLINE",
+    (open, high, low, close),
+    (),
+    single::ShortLine::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlspinningtop,
+    "CDLSPINNINGTOP",
+    (open, high, low, close),
+    (),
+    single::SpinningTop::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlstalledpattern,
+    "CDLSTALLEDPATTERN",
+    (open, high, low, close),
+    (),
+    triple::StalledPattern::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlsticksandwich,
+    "CDLSTICKSANDWICH",
+    (open, high, low, close),
+    (),
+    triple::StickSandwich::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdltakuri,
+    "CDLTAKURI",
+    (open, high, low, close),
+    (),
+    single::Takuri::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdltasukigap,
+    "CDLTASUKIGAP",
+    (open, high, low, close),
+    (),
+    triple::TasukiGap::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlthrusting,
+    "CDLTHRUSTING",
+    (open, high, low, close),
+    (),
+    double::Thrusting::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdltristar,
+    "CDLTRISTAR",
+    (open, high, low, close),
+    (),
+    triple::Tristar::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlunique3river,
+    "CDLUNIQUE3RIVER",
+    (open, high, low, close),
+    (),
+    triple::UniqueThreeRiver::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlupsidegap2crows,
+    "CDLUPSIDEGAP2CROWS",
+    (open, high, low, close),
+    (),
+    triple::UpsideGapTwoCrows::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+batch!(
+    cdlxsidegap3methods,
+    "CDLXSIDEGAP3METHODS",
+    (open, high, low, close),
+    (),
+    triple::XSideGapThreeMethods::new(),
+    |i| (open[i], high[i], low[i], close[i])
+);
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let batch = PyModule::new(parent_module.py(), "batch")?;
+    batch.add_function(wrap_pyfunction!(bbands, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(dema, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ema, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ht_trendline, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(kama, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ma, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(mama, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(mavp, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(midpoint, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(midprice, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(sar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(sarext, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(sma, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(t3, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(tema, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(trima, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(wma, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(avgprice, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(medprice, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(typprice, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(wclprice, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(trange, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(atr, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(natr, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ad, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(adosc, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(obv, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cmf, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(fi, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(emv, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(pvt, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(nvi, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(pvi, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(kvo, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(vwma, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(vosc, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ht_dcperiod, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ht_dcphase, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ht_phasor, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ht_sine, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ht_trendmode, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(adx, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(adxr, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(apo, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(aroon, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(aroonosc, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(bop, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cci, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cmo, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(dx, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(macd, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(macdext, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(mfi, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(minus_di, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(minus_dm, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(mom, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(plus_di, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(plus_dm, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ppo, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(roc, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(rocr, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(rsi, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(stoch, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(stochf, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(stochrsi, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(trix, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(ultosc, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(willr, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(sum, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(max, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(min, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(minmax_, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(beta, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(correl, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(linearreg, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(linearreg_angle, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(linearreg_intercept, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(linearreg_slope, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(tsf, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl2crows, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl3blackcrows, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl3inside, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl3linestrike, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl3outside, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl3starsinsouth, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdl3whitesoldiers, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlabandonedbaby, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdladvanceblock, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlbelthold, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlbreakaway, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlclosingmarubozu, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlconcealbabyswall, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlcounterattack, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdldarkcloudcover, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdldoji, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdldojistar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdldragonflydoji, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlengulfing, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdleveningdojistar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdleveningstar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlgapsidesidewhite, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlgravestonedoji, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlhammer, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlhangingman, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlharami, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlharamicross, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlhighwave, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlhikkake, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlhikkakemod, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlhomingpigeon, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlidentical3crows, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlinneck, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlinvertedhammer, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlkicking, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlkickingbylength, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlladderbottom, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdllongleggeddoji, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdllongline, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlmarubozu, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlmatchinglow, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlmathold, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlmorningdojistar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlmorningstar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlonneck, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlpiercing, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlrickshawman, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlrisefall3methods, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlseparatinglines, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlshootingstar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlshortline, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlspinningtop, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlstalledpattern, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlsticksandwich, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdltakuri, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdltasukigap, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlthrusting, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdltristar, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlunique3river, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlupsidegap2crows, &batch)?)?;
+    batch.add_function(wrap_pyfunction!(cdlxsidegap3methods, &batch)?)?;
+    super::add_submodule(parent_module, &batch)
+}
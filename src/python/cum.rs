@@ -0,0 +1,54 @@
+use super::pywrap;
+use crate::cum::{corr, delta, minmax, quantile, statis};
+use pyo3::prelude::*;
+
+pywrap!(Counter(statis::Counter), new(), update(new_val: f64) -> f64);
+pywrap!(nan Sumer(statis::Sumer), new(), update(new_val: f64) -> f64);
+pywrap!(nan Meaner(statis::Meaner), new(), update(new_val: f64) -> f64);
+pywrap!(nan Stder(statis::Stder), new(), update(new_val: f64) -> f64);
+pywrap!(nan Skewer(statis::Skewer), new(), update(new_val: f64) -> f64);
+pywrap!(nan Kurter(statis::Kurter), new(), update(new_val: f64) -> f64);
+pywrap!(nan Maxer(minmax::Maxer), new(), update(new_val: f64) -> f64);
+pywrap!(nan Miner(minmax::Miner), new(), update(new_val: f64) -> f64);
+pywrap!(Correlationer(corr::Correlationer), new(), update(x: f64, y: f64) -> f64);
+pywrap!(Deltaer(delta::Deltaer), new(), update(x: f64) -> f64);
+pywrap!(Pctchanger(delta::Pctchanger), new(), update(x: f64) -> f64);
+pywrap!(nan Quantiler(quantile::Quantiler), new(quantile: f64), update(new_val: f64) -> f64, {
+    pub fn get_quantile(&self) -> f64 {
+        self.inner.get_quantile()
+    }
+});
+pywrap!(nan SketchQuantiler(quantile::SketchQuantiler), new(quantile: f64, compression: f64), update(new_val: f64) -> f64, {
+    pub fn get_quantile(&self, quantile: f64) -> f64 {
+        self.inner.get_quantile(quantile)
+    }
+
+    // fold in the sketch of another stream, e.g. another symbol
+    pub fn merge(&mut self, other: &Self) {
+        let other = (*other.inner).clone();
+        self.inner.modify(|inner| inner.merge(&other));
+    }
+
+    // centroids plus buffered values kept
+    pub fn size(&self) -> usize {
+        self.inner.digest().size()
+    }
+});
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let cum = PyModule::new(parent_module.py(), "cum")?;
+    cum.add_class::<Counter>()?;
+    cum.add_class::<Sumer>()?;
+    cum.add_class::<Meaner>()?;
+    cum.add_class::<Stder>()?;
+    cum.add_class::<Skewer>()?;
+    cum.add_class::<Kurter>()?;
+    cum.add_class::<Maxer>()?;
+    cum.add_class::<Miner>()?;
+    cum.add_class::<Correlationer>()?;
+    cum.add_class::<Deltaer>()?;
+    cum.add_class::<Pctchanger>()?;
+    cum.add_class::<Quantiler>()?;
+    cum.add_class::<SketchQuantiler>()?;
+    super::add_submodule(parent_module, &cum)
+}
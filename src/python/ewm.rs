@@ -0,0 +1,70 @@
+use super::pywrap;
+use crate::ewm::{corr, statis, Decay};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+// the constructors take the args of pandas `ewm`, exactly one of com, span, halflife, alpha
+pywrap!(Meaner(statis::Meaner), new(com: Option<f64> = None, span: Option<f64> = None, halflife: Option<f64> = None, alpha: Option<f64> = None, min_periods: usize = 0, adjust: bool = true, ignore_na: bool = false) => {
+    statis::Meaner::new(decay(com, span, halflife, alpha)?, adjust, ignore_na, min_periods)
+}, update(new_val: f64) -> f64);
+pywrap!(Varer(statis::Varer), new(com: Option<f64> = None, span: Option<f64> = None, halflife: Option<f64> = None, alpha: Option<f64> = None, min_periods: usize = 0, adjust: bool = true, ignore_na: bool = false, bias: bool = false) => {
+    statis::Varer::new(decay(com, span, halflife, alpha)?, adjust, ignore_na, min_periods, bias)
+}, update(new_val: f64) -> f64);
+pywrap!(Stder(statis::Stder), new(com: Option<f64> = None, span: Option<f64> = None, halflife: Option<f64> = None, alpha: Option<f64> = None, min_periods: usize = 0, adjust: bool = true, ignore_na: bool = false, bias: bool = false) => {
+    statis::Stder::new(decay(com, span, halflife, alpha)?, adjust, ignore_na, min_periods, bias)
+}, update(new_val: f64) -> f64);
+pywrap!(Covarer(corr::Covarer), new(com: Option<f64> = None, span: Option<f64> = None, halflife: Option<f64> = None, alpha: Option<f64> = None, min_periods: usize = 0, adjust: bool = true, ignore_na: bool = false, bias: bool = false) => {
+    corr::Covarer::new(decay(com, span, halflife, alpha)?, adjust, ignore_na, min_periods, bias)
+}, update(x: f64, y: f64) -> f64);
+pywrap!(Correlationer(corr::Correlationer), new(com: Option<f64> = None, span: Option<f64> = None, halflife: Option<f64> = None, alpha: Option<f64> = None, min_periods: usize = 0, adjust: bool = true, ignore_na: bool = false) => {
+    corr::Correlationer::new(decay(com, span, halflife, alpha)?, adjust, ignore_na, min_periods)
+}, update(x: f64, y: f64) -> f64);
+
+// exactly one of com, span, halflife, alpha, within range
+fn decay(
+    com: Option<f64>,
+    span: Option<f64>,
+    halflife: Option<f64>,
+    alpha: Option<f64>,
+) -> PyResult<Decay> {
+    let decay = match (com, span, halflife, alpha) {
+        (Some(com), None, None, None) => Decay::Com(com),
+        (None, Some(span), None, None) => Decay::Span(span),
+        (None, None, Some(halflife), None) => Decay::HalfLife(halflife),
+        (None, None, None, Some(alpha)) => Decay::Alpha(alpha),
+        _ => {
+            return Err(PyValueError::new_err(
+                "expected exactly one of com, span, halflife, alpha",
+            ))
+        }
+    };
+    match decay.alpha() {
+        Some(_) => Ok(decay),
+        None => Err(PyValueError::new_err(format!("{decay:?} is out of range"))),
+    }
+}
+
+// the smoothing factor from exactly one of com, span, halflife, alpha, like pandas `ewm`
+#[pyfunction]
+#[pyo3(signature = (com = None, span = None, halflife = None, alpha = None))]
+fn alpha(
+    com: Option<f64>,
+    span: Option<f64>,
+    halflife: Option<f64>,
+    alpha: Option<f64>,
+) -> PyResult<f64> {
+    let decay = decay(com, span, halflife, alpha)?;
+    // decay() has checked the range
+    Ok(decay.alpha().unwrap_or(f64::NAN))
+}
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let ewm = PyModule::new(parent_module.py(), "ewm")?;
+    ewm.add_function(wrap_pyfunction!(alpha, &ewm)?)?;
+    ewm.add_class::<Meaner>()?;
+    ewm.add_class::<Varer>()?;
+    ewm.add_class::<Stder>()?;
+    ewm.add_class::<Covarer>()?;
+    ewm.add_class::<Correlationer>()?;
+    super::add_submodule(parent_module, &ewm)
+}
@@ -0,0 +1,265 @@
+use super::pywrap;
+use crate::indicator::overlap::MAType;
+use crate::indicator::{channel, cycle, momentum, overlap, price, volatility, volume};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyInt;
+
+// a matype is given by its TA-Lib code, 0 for SMA up to 8 for T3, or by name, e.g. "ema"
+impl<'a, 'py> FromPyObject<'a, 'py> for MAType {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        if let Ok(code) = obj.extract::<i64>() {
+            let matype = u8::try_from(code).ok().and_then(MAType::from_code);
+            return matype.ok_or_else(|| {
+                PyValueError::new_err(format!("unknown matype {code}, expected 0 to 8"))
+            });
+        }
+        let name: String = obj.extract()?;
+        MAType::ALL
+            .into_iter()
+            .find(|matype| format!("{matype:?}").eq_ignore_ascii_case(&name))
+            .ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "unknown matype {name:?}, expected one of sma, ema, wma, dema, tema, trima, kama, mama, t3"
+                ))
+            })
+    }
+}
+
+impl<'py> IntoPyObject<'py> for MAType {
+    type Target = PyInt;
+    type Output = Bound<'py, PyInt>;
+    type Error = std::convert::Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Bound<'py, PyInt>, Self::Error> {
+        self.code().into_pyobject(py)
+    }
+}
+
+// overlap
+pywrap!(BBands(overlap::BBands), new(period: usize, nbdevup: f64, nbdevdn: f64, matype: MAType = MAType::SMA), update(new_val: f64) -> (f64, f64, f64));
+pywrap!(DEMA(overlap::DEMA), new(period: usize), update(new_val: f64) -> f64);
+pywrap!(EMA(overlap::EMA), new(period: usize), update(new_val: f64) -> f64);
+pywrap!(HTTrendline(overlap::HTTrendline), new(), update(new_val: f64) -> f64);
+pywrap!(KAMA(overlap::KAMA), new(er_period: usize, fast_period: usize, slow_period: usize), update(price: f64, preprice: f64) -> f64);
+pywrap!(MA(overlap::MA), new(timeperiod: usize, matype: MAType = MAType::SMA), update(new_val: f64) -> f64);
+pywrap!(MAMA(overlap::MAMA), new(fastlimit: f64, slowlimit: f64), update(new_val: f64) -> (f64, f64));
+pywrap!(MAVP(overlap::MAVP), new(minperiod: usize, maxperiod: usize, matype: MAType = MAType::SMA), update(new_val: f64, period: f64) -> f64);
+pywrap!(MIDPOINT(overlap::MIDPOINT), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(MIDPRICE(overlap::MIDPRICE), new(timeperiod: usize), update(high: f64, low: f64) -> f64);
+pywrap!(SAR(overlap::SAR), new(acceleration: f64, maximum: f64), update(high: f64, low: f64) -> (f64, f64));
+pywrap!(SAREXT(overlap::SAREXT), new(startvalue: f64, offsetonreverse: f64, accelerationinitlong: f64, accelerationlong: f64, accelerationmaxlong: f64, accelerationinitshort: f64, accelerationshort: f64, accelerationmaxshort: f64), update(high: f64, low: f64) -> (f64, f64));
+pywrap!(SMA(overlap::SMA), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(T3(overlap::T3), new(timeperiod: usize, vfactor: f64), update(new_val: f64) -> f64);
+pywrap!(TEMA(overlap::TEMA), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(TRIMA(overlap::TRIMA), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(WMA(overlap::WMA), new(n: usize), update(new_val: f64) -> f64);
+
+// price
+pywrap!(AvgPrice(price::AvgPrice), new(), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+pywrap!(MedPrice(price::MedPrice), new(), update(high: f64, low: f64) -> f64);
+pywrap!(TypicalPrice(price::TypicalPrice), new(), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(WeightedClose(price::WeightedClose), new(), update(high: f64, low: f64, close: f64) -> f64);
+
+// volatility
+pywrap!(TR(volatility::TR), new(), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(ATR(volatility::ATR), new(period: usize), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(NATR(volatility::NATR), new(period: usize), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(Parkinson(volatility::Parkinson), new(timeperiod: usize, annualization: f64), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+pywrap!(GarmanKlass(volatility::GarmanKlass), new(timeperiod: usize, annualization: f64), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+pywrap!(RogersSatchell(volatility::RogersSatchell), new(timeperiod: usize, annualization: f64), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+pywrap!(YangZhang(volatility::YangZhang), new(timeperiod: usize, annualization: f64), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+pywrap!(CloseToClose(volatility::CloseToClose), new(timeperiod: usize, annualization: f64), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+
+// channel
+pywrap!(Donchian(channel::Donchian), new(timeperiod: usize), update(high: f64, low: f64) -> (f64, f64, f64));
+pywrap!(Keltner(channel::Keltner), new(timeperiod: usize, atr_period: usize, multiplier: f64), update(high: f64, low: f64, close: f64) -> (f64, f64, f64));
+pywrap!(Ichimoku(channel::Ichimoku), new(tenkan: usize, kijun: usize, senkou: usize, displacement: usize), update(high: f64, low: f64, close: f64) -> (f64, f64, f64, f64, f64), {
+    // (senkou_a, senkou_b) of the bar `displacement` bars ahead
+    pub fn leading(&self) -> (f64, f64) {
+        self.inner.leading()
+    }
+});
+pywrap!(Supertrend(channel::Supertrend), new(timeperiod: usize, multiplier: f64), update(high: f64, low: f64, close: f64) -> (f64, f64));
+pywrap!(Squeeze(channel::Squeeze), new(timeperiod: usize, nbdev: f64, multiplier: f64), update(high: f64, low: f64, close: f64) -> i32);
+
+// volume
+pywrap!(AD(volume::AD), new(timeperiod: usize), update(high: f64, low: f64, close: f64, volume: f64) -> f64);
+pywrap!(ADOSC(volume::ADOSC), new(timeperiod: usize, fastperiod: usize, slowperiod: usize), update(high: f64, low: f64, close: f64, volume: f64) -> f64);
+pywrap!(CMF(volume::CMF), new(timeperiod: usize), update(high: f64, low: f64, close: f64, volume: f64) -> f64);
+pywrap!(EMV(volume::EMV), new(timeperiod: usize, scale: f64), update(high: f64, low: f64, volume: f64) -> f64);
+pywrap!(FI(volume::FI), new(timeperiod: usize), update(close: f64, volume: f64) -> f64);
+pywrap!(KVO(volume::KVO), new(fastperiod: usize, slowperiod: usize, signalperiod: usize), update(high: f64, low: f64, close: f64, volume: f64) -> (f64, f64));
+pywrap!(NVI(volume::NVI), new(), update(close: f64, volume: f64) -> f64);
+pywrap!(OBV(volume::OBV), new(timeperiod: usize), update(close: f64, volume: f64) -> f64);
+pywrap!(PVI(volume::PVI), new(), update(close: f64, volume: f64) -> f64);
+pywrap!(PVT(volume::PVT), new(), update(close: f64, volume: f64) -> f64);
+pywrap!(VOSC(volume::VOSC), new(fastperiod: usize, slowperiod: usize), update(volume: f64) -> f64);
+pywrap!(VWMA(volume::VWMA), new(timeperiod: usize), update(close: f64, volume: f64) -> f64);
+pywrap!(SessionVWAP(volume::SessionVWAP), new(session: i64, offset: i64), update(ts: i64, high: f64, low: f64, close: f64, volume: f64) -> f64, {
+    pub fn std(&self) -> f64 {
+        self.inner.std()
+    }
+});
+pywrap!(AnchoredVWAP(volume::AnchoredVWAP), new(anchor: i64), update(ts: i64, high: f64, low: f64, close: f64, volume: f64) -> f64, {
+    // restarts the VWAP at the bars from `anchor` on
+    pub fn anchor_at(&mut self, anchor: i64) {
+        self.inner.modify(|inner| inner.anchor_at(anchor));
+    }
+
+    pub fn std(&self) -> f64 {
+        self.inner.std()
+    }
+});
+pywrap!(RollingVWAP(volume::RollingVWAP), new(timeperiod: usize), update(ts: i64, high: f64, low: f64, close: f64, volume: f64) -> f64, {
+    pub fn std(&self) -> f64 {
+        self.inner.std()
+    }
+});
+pywrap!(VWAPBands(volume::VWAPBands), new(session: i64, offset: i64, nbdevup: f64, nbdevdn: f64), update(ts: i64, high: f64, low: f64, close: f64, volume: f64) -> (f64, f64, f64));
+pywrap!(AnchoredVWAPBands(volume::AnchoredVWAPBands), new(anchor: i64, nbdevup: f64, nbdevdn: f64) => {
+    volume::VWAPBands::with_vwap(volume::AnchoredVWAP::new(anchor), nbdevup, nbdevdn)
+}, update(ts: i64, high: f64, low: f64, close: f64, volume: f64) -> (f64, f64, f64), {
+    // restarts the VWAP at the bars from `anchor` on
+    pub fn anchor_at(&mut self, anchor: i64) {
+        self.inner.modify(|inner| inner.anchor_at(anchor));
+    }
+});
+pywrap!(RollingVWAPBands(volume::RollingVWAPBands), new(timeperiod: usize, nbdevup: f64, nbdevdn: f64) => {
+    volume::VWAPBands::with_vwap(volume::RollingVWAP::new(timeperiod), nbdevup, nbdevdn)
+}, update(ts: i64, high: f64, low: f64, close: f64, volume: f64) -> (f64, f64, f64));
+
+// cycle
+pywrap!(HTDCPeriod(cycle::HTDCPeriod), new(), update(new_val: f64) -> f64);
+pywrap!(HTDCPhase(cycle::HTDCPhase), new(), update(new_val: f64) -> f64);
+pywrap!(HTPhasor(cycle::HTPhasor), new(), update(new_val: f64) -> (f64, f64));
+pywrap!(HTSine(cycle::HTSine), new(), update(new_val: f64) -> (f64, f64));
+pywrap!(HTTrendMode(cycle::HTTrendMode), new(), update(new_val: f64) -> i32);
+
+// momentum
+pywrap!(AO(momentum::AO), new(fast_period: usize, slow_period: usize), update(high: f64, low: f64) -> f64);
+pywrap!(APO(momentum::APO), new(fast_period: usize, slow_period: usize, matype: MAType = MAType::SMA), update(price: f64) -> f64);
+pywrap!(ADX(momentum::ADX), new(timeperiod: usize), update(high: f64, low: f64, preclose: f64) -> f64);
+pywrap!(ADXR(momentum::ADXR), new(timeperiod: usize), update(high: f64, low: f64, preclose: f64) -> f64);
+pywrap!(Aroon(momentum::Aroon), new(period: usize), update(high: f64, low: f64) -> (f64, f64));
+pywrap!(AroonOsc(momentum::AroonOsc), new(period: usize), update(high: f64, low: f64) -> f64);
+pywrap!(BOP(momentum::BOP), new(), update(open: f64, high: f64, low: f64, close: f64) -> f64);
+pywrap!(CCI(momentum::CCI), new(period: usize), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(CMO(momentum::CMO), new(period: usize), update(close: f64) -> f64);
+pywrap!(DX(momentum::DX), new(period: usize), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(KDJ(momentum::KDJ), new(view_period: usize, d_period: usize, j_period: usize), update(price: f64, high: f64, low: f64) -> (f64, f64, f64));
+pywrap!(KST(momentum::KST), new(max_period: usize), update(new_val: f64) -> f64);
+pywrap!(MACD(momentum::MACD), new(fast_period: usize, slow_period: usize, signal_period: usize), update(new_val: f64) -> (f64, f64, f64));
+pywrap!(MACDEXT(momentum::MACDEXT), new(fast_period: usize, fast_matype: MAType, slow_period: usize, slow_matype: MAType, signal_period: usize, signal_matype: MAType), update(new_val: f64) -> (f64, f64, f64));
+pywrap!(MinusDM(momentum::MinusDM), new(timeperiod: usize), update(high: f64, low: f64) -> f64);
+pywrap!(PlusDM(momentum::PlusDM), new(timeperiod: usize), update(high: f64, low: f64) -> f64);
+pywrap!(PlusDI(momentum::PlusDI), new(timeperiod: usize), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(MinusDI(momentum::MinusDI), new(timeperiod: usize), update(high: f64, low: f64, close: f64) -> f64);
+pywrap!(MFI(momentum::MFI), new(timeperiod: usize), update(high: f64, low: f64, close: f64, volume: f64) -> f64);
+pywrap!(MOM(momentum::MOM), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(PPO(momentum::PPO), new(fast_period: usize, slow_period: usize, matype: MAType = MAType::SMA), update(new_val: f64) -> f64);
+pywrap!(PPOHist(momentum::PPOHist), new(fast_period: usize, slow_period: usize, signal_period: usize), update(new_val: f64) -> f64);
+pywrap!(ROC(momentum::ROC), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(ROCR(momentum::ROCR), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(RSI(momentum::RSI), new(timeperiod: usize), update(price: f64) -> f64);
+pywrap!(Stoch(momentum::Stoch), new(fastk_period: usize, slowk_period: usize, slowk_matype: MAType, slowd_period: usize, slowd_matype: MAType = MAType::SMA), update(high: f64, low: f64, close: f64) -> (f64, f64));
+pywrap!(StochCHF(momentum::StochCHF), new(fastk_period: usize, fastd_period: usize, fastd_matype: MAType = MAType::SMA), update(high: f64, low: f64, close: f64) -> (f64, f64));
+pywrap!(StochRSI(momentum::StochRSI), new(rsi_period: usize, fastk_period: usize, fastd_period: usize, fastd_matype: MAType = MAType::SMA), update(new_val: f64) -> (f64, f64));
+pywrap!(TRIX(momentum::TRIX), new(timeperiod: usize), update(new_val: f64) -> f64);
+pywrap!(ULTOSC(momentum::ULTOSC), new(timeperiod1: usize, timeperiod2: usize, timeperiod3: usize), update(high: f64, low: f64, close: f64, preclose: f64) -> f64);
+pywrap!(WILLR(momentum::WILLR), new(timeperiod: usize), update(high: f64, low: f64, close: f64) -> f64);
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let indicator = PyModule::new(parent_module.py(), "indicator")?;
+    indicator.add_class::<BBands>()?;
+    indicator.add_class::<DEMA>()?;
+    indicator.add_class::<EMA>()?;
+    indicator.add_class::<HTTrendline>()?;
+    indicator.add_class::<KAMA>()?;
+    indicator.add_class::<MA>()?;
+    indicator.add_class::<MAMA>()?;
+    indicator.add_class::<MAVP>()?;
+    indicator.add_class::<MIDPOINT>()?;
+    indicator.add_class::<MIDPRICE>()?;
+    indicator.add_class::<SAR>()?;
+    indicator.add_class::<SAREXT>()?;
+    indicator.add_class::<SMA>()?;
+    indicator.add_class::<T3>()?;
+    indicator.add_class::<TEMA>()?;
+    indicator.add_class::<TRIMA>()?;
+    indicator.add_class::<WMA>()?;
+    indicator.add_class::<AvgPrice>()?;
+    indicator.add_class::<MedPrice>()?;
+    indicator.add_class::<TypicalPrice>()?;
+    indicator.add_class::<WeightedClose>()?;
+    indicator.add_class::<TR>()?;
+    indicator.add_class::<ATR>()?;
+    indicator.add_class::<NATR>()?;
+    indicator.add_class::<Parkinson>()?;
+    indicator.add_class::<GarmanKlass>()?;
+    indicator.add_class::<RogersSatchell>()?;
+    indicator.add_class::<YangZhang>()?;
+    indicator.add_class::<CloseToClose>()?;
+    indicator.add_class::<Donchian>()?;
+    indicator.add_class::<Keltner>()?;
+    indicator.add_class::<Ichimoku>()?;
+    indicator.add_class::<Supertrend>()?;
+    indicator.add_class::<Squeeze>()?;
+    indicator.add_class::<AD>()?;
+    indicator.add_class::<ADOSC>()?;
+    indicator.add_class::<CMF>()?;
+    indicator.add_class::<EMV>()?;
+    indicator.add_class::<FI>()?;
+    indicator.add_class::<KVO>()?;
+    indicator.add_class::<NVI>()?;
+    indicator.add_class::<OBV>()?;
+    indicator.add_class::<PVI>()?;
+    indicator.add_class::<PVT>()?;
+    indicator.add_class::<VOSC>()?;
+    indicator.add_class::<VWMA>()?;
+    indicator.add_class::<SessionVWAP>()?;
+    indicator.add_class::<AnchoredVWAP>()?;
+    indicator.add_class::<RollingVWAP>()?;
+    indicator.add_class::<VWAPBands>()?;
+    indicator.add_class::<AnchoredVWAPBands>()?;
+    indicator.add_class::<RollingVWAPBands>()?;
+    indicator.add_class::<HTDCPeriod>()?;
+    indicator.add_class::<HTDCPhase>()?;
+    indicator.add_class::<HTPhasor>()?;
+    indicator.add_class::<HTSine>()?;
+    indicator.add_class::<HTTrendMode>()?;
+    indicator.add_class::<AO>()?;
+    indicator.add_class::<APO>()?;
+    indicator.add_class::<ADX>()?;
+    indicator.add_class::<ADXR>()?;
+    indicator.add_class::<Aroon>()?;
+    indicator.add_class::<AroonOsc>()?;
+    indicator.add_class::<BOP>()?;
+    indicator.add_class::<CCI>()?;
+    indicator.add_class::<CMO>()?;
+    indicator.add_class::<DX>()?;
+    indicator.add_class::<KDJ>()?;
+    indicator.add_class::<KST>()?;
+    indicator.add_class::<MACD>()?;
+    indicator.add_class::<MACDEXT>()?;
+    indicator.add_class::<MinusDM>()?;
+    indicator.add_class::<PlusDM>()?;
+    indicator.add_class::<PlusDI>()?;
+    indicator.add_class::<MinusDI>()?;
+    indicator.add_class::<MFI>()?;
+    indicator.add_class::<MOM>()?;
+    indicator.add_class::<PPO>()?;
+    indicator.add_class::<PPOHist>()?;
+    indicator.add_class::<ROC>()?;
+    indicator.add_class::<ROCR>()?;
+    indicator.add_class::<RSI>()?;
+    indicator.add_class::<Stoch>()?;
+    indicator.add_class::<StochCHF>()?;
+    indicator.add_class::<StochRSI>()?;
+    indicator.add_class::<TRIX>()?;
+    indicator.add_class::<ULTOSC>()?;
+    indicator.add_class::<WILLR>()?;
+    super::add_submodule(parent_module, &indicator)
+}
@@ -0,0 +1,218 @@
+use pyo3::prelude::*;
+mod arrays;
+mod bars;
+mod batch;
+mod cum;
+mod ewm;
+mod indicator;
+mod pattern;
+mod rolling;
+mod state;
+mod timed;
+
+// pywrap generates a python class holding the rust type and forwarding to it.
+// the rust type is wrapped in Amend, so the last input of a forming bar can be replaced.
+// the constructor args are kept for pickle, which rebuilds the class then restores the state.
+// extra #[pymethods] specific to one class go in the optional trailing block.
+// a leading `nan` adds set_nan_policy/set_min_periods for the types implementing NanHandling.
+// `new(args) => expr` builds the rust type from the args with expr instead of its new(args),
+// expr may use `?` to raise a python error.
+macro_rules! pywrap {
+    (
+        nan $name:ident($inner:ty),
+        new($($arg:ident: $arg_ty:ty $(= $default:expr)?),*),
+        update($($input:ident: $input_ty:ty),*) -> $output:ty
+        $(, { $($extra:tt)* })?
+    ) => {
+        $crate::python::pywrap!($name($inner), new($($arg: $arg_ty $(= $default)?),*), update($($input: $input_ty),*) -> $output, {
+            // policy is one of "propagate", "skip", "fill_previous", "fill_value"
+            #[pyo3(signature = (policy, fill_value = f64::NAN))]
+            pub fn set_nan_policy(&mut self, policy: &str, fill_value: f64) -> PyResult<()> {
+                let policy = $crate::python::nan_policy(policy, fill_value)?;
+                $crate::NanHandling::set_nan_policy(&mut self.inner, policy);
+                Ok(())
+            }
+
+            // valid values needed for an output, like pandas min_periods
+            pub fn set_min_periods(&mut self, min_periods: usize) {
+                $crate::NanHandling::set_min_periods(&mut self.inner, min_periods);
+            }
+
+            $($($extra)*)?
+        });
+    };
+    (
+        $name:ident($inner:ty),
+        new($($arg:ident: $arg_ty:ty $(= $default:expr)?),*),
+        update($($input:ident: $input_ty:ty),*) -> $output:ty
+        $(, { $($extra:tt)* })?
+    ) => {
+        $crate::python::pywrap!($name($inner), new($($arg: $arg_ty $(= $default)?),*) => {
+            // the args are cloned since some are not Copy, e.g. a list of quantiles
+            #[allow(clippy::clone_on_copy)]
+            <$inner>::new($($arg.clone()),*)
+        }, update($($input: $input_ty),*) -> $output $(, { $($extra)* })?);
+    };
+    (
+        $name:ident($inner:ty),
+        new($($arg:ident: $arg_ty:ty $(= $default:expr)?),*) => $ctor:expr,
+        update($($input:ident: $input_ty:ty),*) -> $output:ty
+        $(, { $($extra:tt)* })?
+    ) => {
+        #[pyclass]
+        pub struct $name {
+            inner: $crate::Amend<$inner>,
+            args: ($($arg_ty,)*),
+        }
+
+        #[pymethods]
+        impl $name {
+            #[new]
+            #[pyo3(signature = ($($arg $(= $default)?),*))]
+            // TA-Lib style parameter lists can be long, e.g. SAREXT
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($arg: $arg_ty),*) -> PyResult<Self> {
+                let inner: $inner = $ctor;
+                Ok(Self {
+                    inner: $crate::Amend::new(inner),
+                    args: ($($arg,)*),
+                })
+            }
+
+            // is_final=False feeds a still-forming bar, which the next update replaces
+            #[pyo3(signature = ($($input,)* is_final = true))]
+            pub fn update(&mut self, $($input: $input_ty,)* is_final: bool) -> $output {
+                self.inner.update(($($input),*), is_final)
+            }
+
+            // recompute as if the last input were replaced, without advancing the window,
+            // after an is_final=False update or with keep_last
+            pub fn replace_last(&mut self, $($input: $input_ty),*) -> PyResult<$output> {
+                self.inner.replace_last(($($input),*)).ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(
+                        "the state before the last input was not kept, update with is_final=False or call set_keep_last(True)",
+                    )
+                })
+            }
+
+            // keep the state before every update, not only before is_final=False ones,
+            // so replace_last works after any update at the cost of a copy per update
+            pub fn set_keep_last(&mut self, keep_last: bool) {
+                self.inner.set_keep_last(keep_last);
+            }
+
+            // hypothetical output for a candidate input, the state is left untouched
+            pub fn peek(&self, $($input: $input_ty),*) -> $output {
+                $crate::Indicator::peek(&self.inner, ($($input),*))
+            }
+
+            // feed whole arrays in one call, returns the per-step outputs as arrays
+            pub fn update_many<'py>(
+                &mut self,
+                py: Python<'py>,
+                $($input: numpy::PyReadonlyArray1<'py, $input_ty>),*
+            ) -> PyResult<Bound<'py, PyAny>> {
+                $(let $input = $crate::python::arrays::as_slice(&$input);)*
+                let len = $crate::python::arrays::same_len(&[$($input.len()),*])?;
+                let inner = &mut self.inner;
+                let rows: Vec<$output> = py.detach(|| {
+                    $crate::Indicator::update_many(inner, (0..len).map(|i| ($($input[i]),*)))
+                });
+                $crate::python::arrays::IntoColumns::into_columns(py, rows)
+            }
+
+            #[allow(clippy::clone_on_copy)]
+            pub fn __getnewargs__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyTuple>> {
+                let ($($arg,)*) = self.args.clone();
+                let args: Vec<Bound<'py, PyAny>> = vec![$(pyo3::IntoPyObjectExt::into_bound_py_any($arg, py)?),*];
+                pyo3::types::PyTuple::new(py, args)
+            }
+
+            pub fn __getstate__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+                let state = $crate::python::state::to_bytes(&self.inner)?;
+                Ok(pyo3::types::PyBytes::new(py, &state))
+            }
+
+            pub fn __setstate__(&mut self, state: &[u8]) -> PyResult<()> {
+                self.inner = $crate::python::state::from_bytes(state)?;
+                Ok(())
+            }
+
+            // human-readable snapshot of the state, NaN and inf are written as strings
+            pub fn to_json(&self) -> PyResult<String> {
+                $crate::python::state::to_json(&self.inner)
+            }
+
+            pub fn load_json(&mut self, state: &str) -> PyResult<()> {
+                self.inner = $crate::python::state::from_json(state)?;
+                Ok(())
+            }
+
+            pub fn value(&self) -> $output {
+                $crate::Indicator::value(&self.inner)
+            }
+
+            pub fn reset(&mut self) {
+                $crate::Indicator::reset(&mut self.inner)
+            }
+
+            pub fn is_ready(&self) -> bool {
+                $crate::Indicator::is_ready(&self.inner)
+            }
+
+            // number of inputs before the first valid output
+            pub fn lookback(&self) -> usize {
+                $crate::Indicator::lookback(&self.inner)
+            }
+
+            // number of inputs fed since construction or reset
+            pub fn count(&self) -> usize {
+                $crate::Indicator::count(&self.inner)
+            }
+
+            $($($extra)*)?
+        }
+    };
+}
+pub(crate) use pywrap;
+
+fn nan_policy(policy: &str, fill_value: f64) -> PyResult<crate::NanPolicy> {
+    use crate::NanPolicy;
+    match policy {
+        "propagate" => Ok(NanPolicy::Propagate),
+        "skip" => Ok(NanPolicy::Skip),
+        "fill_previous" => Ok(NanPolicy::FillPrevious),
+        "fill_value" => Ok(NanPolicy::FillValue(fill_value)),
+        _ => Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "unknown nan policy {policy:?}, expected one of propagate, skip, fill_previous, fill_value"
+        ))),
+    }
+}
+
+// add_submodule also registers e.g. `ta.rolling` in sys.modules and sets the __module__ of its classes,
+// so pickle can import them back by name
+fn add_submodule(parent: &Bound<'_, PyModule>, child: &Bound<'_, PyModule>) -> PyResult<()> {
+    let name = format!("{}.{}", parent.name()?, child.name()?);
+    for (_, obj) in child.dict() {
+        if obj.is_instance_of::<pyo3::types::PyType>() {
+            obj.setattr("__module__", &name)?;
+        }
+    }
+    let modules = parent.py().import("sys")?.getattr("modules")?;
+    modules.set_item(&name, child)?;
+    parent.add_submodule(child)
+}
+
+/// A Python module implemented in Rust.
+#[pymodule]
+fn ta(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    rolling::register(m)?;
+    cum::register(m)?;
+    ewm::register(m)?;
+    timed::register(m)?;
+    indicator::register(m)?;
+    pattern::register(m)?;
+    batch::register(m)?;
+    bars::register(m)?;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+use super::pywrap;
+use crate::pattern::{double, hikkake, multi, single, triple};
+use pyo3::prelude::*;
+
+pywrap!(TwoCrows(triple::TwoCrows), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ThreeBlackCrows(multi::ThreeBlackCrows), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ThreeInside(triple::ThreeInside), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ThreeLineStrike(multi::ThreeLineStrike), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ThreeOutside(triple::ThreeOutside), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ThreeStarsInSouth(triple::ThreeStarsInSouth), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ThreeWhiteSoldiers(triple::ThreeWhiteSoldiers), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(AbandonedBaby(triple::AbandonedBaby), new(penetration: f64 = 0.3), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(AdvanceBlock(triple::AdvanceBlock), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(BeltHold(single::BeltHold), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Breakaway(multi::Breakaway), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ClosingMarubozu(single::ClosingMarubozu), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ConcealBabySwallow(multi::ConcealBabySwallow), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Counterattack(double::Counterattack), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(DarkCloudCover(double::DarkCloudCover), new(penetration: f64 = 0.5), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Doji(single::Doji), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(DojiStar(double::DojiStar), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(DragonflyDoji(single::DragonflyDoji), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Engulfing(double::Engulfing), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(EveningDojiStar(triple::EveningDojiStar), new(penetration: f64 = 0.3), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(EveningStar(triple::EveningStar), new(penetration: f64 = 0.3), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(GapSideSideWhite(triple::GapSideSideWhite), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(GravestoneDoji(single::GravestoneDoji), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Hammer(double::Hammer), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(HangingMan(double::HangingMan), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Harami(double::Harami), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(HaramiCross(double::HaramiCross), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(HighWave(single::HighWave), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Hikkake(hikkake::Hikkake), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(HikkakeMod(hikkake::HikkakeMod), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(HomingPigeon(double::HomingPigeon), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(IdenticalThreeCrows(triple::IdenticalThreeCrows), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(InNeck(double::InNeck), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(InvertedHammer(double::InvertedHammer), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Kicking(double::Kicking), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(KickingByLength(double::KickingByLength), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(LadderBottom(multi::LadderBottom), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(LongLeggedDoji(single::LongLeggedDoji), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(LongLine(single::LongLine), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Marubozu(single::Marubozu), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(MatchingLow(double::MatchingLow), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(MatHold(multi::MatHold), new(penetration: f64 = 0.5), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(MorningDojiStar(triple::MorningDojiStar), new(penetration: f64 = 0.3), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(MorningStar(triple::MorningStar), new(penetration: f64 = 0.3), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(OnNeck(double::OnNeck), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Piercing(double::Piercing), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(RickshawMan(single::RickshawMan), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(RiseFallThreeMethods(multi::RiseFallThreeMethods), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(SeparatingLines(double::SeparatingLines), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ShootingStar(double::ShootingStar), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(ShortLine(single::ShortLine), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(SpinningTop(single::SpinningTop), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(StalledPattern(triple::StalledPattern), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(StickSandwich(triple::StickSandwich), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Takuri(single::Takuri), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(TasukiGap(triple::TasukiGap), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Thrusting(double::Thrusting), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(Tristar(triple::Tristar), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(UniqueThreeRiver(triple::UniqueThreeRiver), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(UpsideGapTwoCrows(triple::UpsideGapTwoCrows), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+pywrap!(XSideGapThreeMethods(triple::XSideGapThreeMethods), new(), update(open: f64, high: f64, low: f64, close: f64) -> i32);
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let pattern = PyModule::new(parent_module.py(), "pattern")?;
+    pattern.add_class::<TwoCrows>()?;
+    pattern.add_class::<ThreeBlackCrows>()?;
+    pattern.add_class::<ThreeInside>()?;
+    pattern.add_class::<ThreeLineStrike>()?;
+    pattern.add_class::<ThreeOutside>()?;
+    pattern.add_class::<ThreeStarsInSouth>()?;
+    pattern.add_class::<ThreeWhiteSoldiers>()?;
+    pattern.add_class::<AbandonedBaby>()?;
+    pattern.add_class::<AdvanceBlock>()?;
+    pattern.add_class::<BeltHold>()?;
+    pattern.add_class::<Breakaway>()?;
+    pattern.add_class::<ClosingMarubozu>()?;
+    pattern.add_class::<ConcealBabySwallow>()?;
+    pattern.add_class::<Counterattack>()?;
+    pattern.add_class::<DarkCloudCover>()?;
+    pattern.add_class::<Doji>()?;
+    pattern.add_class::<DojiStar>()?;
+    pattern.add_class::<DragonflyDoji>()?;
+    pattern.add_class::<Engulfing>()?;
+    pattern.add_class::<EveningDojiStar>()?;
+    pattern.add_class::<EveningStar>()?;
+    pattern.add_class::<GapSideSideWhite>()?;
+    pattern.add_class::<GravestoneDoji>()?;
+    pattern.add_class::<Hammer>()?;
+    pattern.add_class::<HangingMan>()?;
+    pattern.add_class::<Harami>()?;
+    pattern.add_class::<HaramiCross>()?;
+    pattern.add_class::<HighWave>()?;
+    pattern.add_class::<Hikkake>()?;
+    pattern.add_class::<HikkakeMod>()?;
+    pattern.add_class::<HomingPigeon>()?;
+    pattern.add_class::<IdenticalThreeCrows>()?;
+    pattern.add_class::<InNeck>()?;
+    pattern.add_class::<InvertedHammer>()?;
+    pattern.add_class::<Kicking>()?;
+    pattern.add_class::<KickingByLength>()?;
+    pattern.add_class::<LadderBottom>()?;
+    pattern.add_class::<LongLeggedDoji>()?;
+    pattern.add_class::<LongLine>()?;
+    pattern.add_class::<Marubozu>()?;
+    pattern.add_class::<MatchingLow>()?;
+    pattern.add_class::<MatHold>()?;
+    pattern.add_class::<MorningDojiStar>()?;
+    pattern.add_class::<MorningStar>()?;
+    pattern.add_class::<OnNeck>()?;
+    pattern.add_class::<Piercing>()?;
+    pattern.add_class::<RickshawMan>()?;
+    pattern.add_class::<RiseFallThreeMethods>()?;
+    pattern.add_class::<SeparatingLines>()?;
+    pattern.add_class::<ShootingStar>()?;
+    pattern.add_class::<ShortLine>()?;
+    pattern.add_class::<SpinningTop>()?;
+    pattern.add_class::<StalledPattern>()?;
+    pattern.add_class::<StickSandwich>()?;
+    pattern.add_class::<Takuri>()?;
+    pattern.add_class::<TasukiGap>()?;
+    pattern.add_class::<Thrusting>()?;
+    pattern.add_class::<Tristar>()?;
+    pattern.add_class::<UniqueThreeRiver>()?;
+    pattern.add_class::<UpsideGapTwoCrows>()?;
+    pattern.add_class::<XSideGapThreeMethods>()?;
+    super::add_submodule(parent_module, &pattern)
+}
@@ -0,0 +1,105 @@
+use super::pywrap;
+use crate::rolling::{container, corr, delta, minmax, quantile, statis};
+use pyo3::prelude::*;
+
+pywrap!(Container(container::Container), new(n: usize), update(new_val: f64) -> (f64, f64), {
+    pub fn get(&self, idx: usize) -> f64 {
+        self.inner.get(idx)
+    }
+
+    pub fn head(&self) -> f64 {
+        self.inner.head()
+    }
+
+    pub fn tail(&self) -> f64 {
+        self.inner.tail()
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+});
+pywrap!(nan Sumer(statis::Sumer), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan Meaner(statis::Meaner), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan Stder(statis::Stder), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan Skewer(statis::Skewer), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan Kurter(statis::Kurter), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan Maxer(minmax::Maxer), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan Miner(minmax::Miner), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan MinMaxer(minmax::MinMaxer), new(n: usize), update(new_val: f64) -> (f64, f64));
+pywrap!(nan MaxIndexer(minmax::MaxIndexer), new(n: usize), update(new_val: f64) -> (usize, f64));
+pywrap!(nan MinIndexer(minmax::MinIndexer), new(n: usize), update(new_val: f64) -> (usize, f64));
+pywrap!(nan MinMaxIndexer(minmax::MinMaxIndexer), new(n: usize), update(new_val: f64) -> (usize, f64, usize, f64));
+pywrap!(Deltaer(delta::Deltaer), new(n: usize), update(new_val: f64) -> f64, {
+    pub fn get(&self, idx: usize) -> f64 {
+        self.inner.get(idx)
+    }
+
+    pub fn head(&self) -> f64 {
+        self.inner.head()
+    }
+
+    pub fn tail(&self) -> f64 {
+        self.inner.tail()
+    }
+
+    pub fn partial(&self, start: usize, end: usize) -> f64 {
+        self.inner.partial(start, end)
+    }
+});
+pywrap!(Pctchanger(delta::Pctchanger), new(n: usize), update(new_val: f64) -> f64, {
+    pub fn get(&self, idx: usize) -> f64 {
+        self.inner.get(idx)
+    }
+
+    pub fn head(&self) -> f64 {
+        self.inner.head()
+    }
+
+    pub fn tail(&self) -> f64 {
+        self.inner.tail()
+    }
+
+    pub fn partial(&self, start: usize, end: usize) -> f64 {
+        self.inner.partial(start, end)
+    }
+});
+pywrap!(nan Quantiler(quantile::Quantiler), new(n: usize, quantile: f64), update(new_val: f64) -> f64);
+pywrap!(nan Medianer(quantile::Medianer), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan PctRanker(quantile::PctRanker), new(n: usize), update(new_val: f64) -> f64);
+pywrap!(nan MultiQuantiler(quantile::MultiQuantiler), new(n: usize, quantiles: Vec<f64>), update(new_val: f64) -> Vec<f64>, {
+    pub fn quantiles(&self) -> Vec<f64> {
+        self.inner.quantiles().to_vec()
+    }
+});
+pywrap!(Correlationer(corr::Correlationer), new(n: usize), update(x: f64, y: f64) -> f64);
+pywrap!(Beta(corr::Beta), new(n: usize), update(x: f64, y: f64) -> f64);
+pywrap!(TSF(corr::TSF), new(n: usize), update(y: f64) -> f64);
+pywrap!(LinearReg(corr::LinearReg), new(n: usize), update(y: f64) -> (f64, f64, f64, f64));
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let rolling = PyModule::new(parent_module.py(), "rolling")?;
+    rolling.add_class::<Container>()?;
+    rolling.add_class::<Sumer>()?;
+    rolling.add_class::<Meaner>()?;
+    rolling.add_class::<Maxer>()?;
+    rolling.add_class::<Miner>()?;
+    rolling.add_class::<MinMaxer>()?;
+    rolling.add_class::<MaxIndexer>()?;
+    rolling.add_class::<MinIndexer>()?;
+    rolling.add_class::<MinMaxIndexer>()?;
+    rolling.add_class::<Deltaer>()?;
+    rolling.add_class::<Pctchanger>()?;
+    rolling.add_class::<Stder>()?;
+    rolling.add_class::<Skewer>()?;
+    rolling.add_class::<Kurter>()?;
+    rolling.add_class::<Correlationer>()?;
+    rolling.add_class::<Beta>()?;
+    rolling.add_class::<TSF>()?;
+    rolling.add_class::<LinearReg>()?;
+    rolling.add_class::<Quantiler>()?;
+    rolling.add_class::<Medianer>()?;
+    rolling.add_class::<PctRanker>()?;
+    rolling.add_class::<MultiQuantiler>()?;
+    super::add_submodule(parent_module, &rolling)
+}
@@ -0,0 +1,21 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+// pickle state is the compact bincode encoding of the rust struct
+pub fn to_bytes<T: Serialize>(inner: &T) -> PyResult<Vec<u8>> {
+    bincode::serialize(inner).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+pub fn from_bytes<T: DeserializeOwned>(state: &[u8]) -> PyResult<T> {
+    bincode::deserialize(state).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+pub fn to_json<T: Serialize>(inner: &T) -> PyResult<String> {
+    serde_json::to_string(inner).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+pub fn from_json<T: DeserializeOwned>(state: &str) -> PyResult<T> {
+    serde_json::from_str(state).map_err(|e| PyValueError::new_err(e.to_string()))
+}
@@ -0,0 +1,29 @@
+use super::pywrap;
+use crate::timed::{corr, minmax, quantile, statis};
+use pyo3::prelude::*;
+
+pywrap!(nan Sumer(statis::Sumer), new(duration: i64), update(timestamp: i64, new_val: f64) -> f64);
+pywrap!(nan Meaner(statis::Meaner), new(duration: i64), update(timestamp: i64, new_val: f64) -> f64);
+pywrap!(nan Stder(statis::Stder), new(duration: i64), update(timestamp: i64, new_val: f64) -> f64);
+pywrap!(nan Maxer(minmax::Maxer), new(duration: i64), update(timestamp: i64, new_val: f64) -> f64);
+pywrap!(nan Miner(minmax::Miner), new(duration: i64), update(timestamp: i64, new_val: f64) -> f64);
+pywrap!(nan Quantiler(quantile::Quantiler), new(duration: i64, quantile: f64), update(timestamp: i64, new_val: f64) -> f64, {
+    pub fn get_quantile(&self, quantile: f64) -> f64 {
+        self.inner.get_quantile(quantile)
+    }
+});
+pywrap!(Correlationer(corr::Correlationer), new(duration: i64), update(timestamp: i64, x: f64, y: f64) -> f64);
+pywrap!(DecayMeaner(statis::DecayMeaner), new(halflife: f64), update(timestamp: i64, new_val: f64) -> f64);
+
+pub fn register(parent_module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let timed = PyModule::new(parent_module.py(), "timed")?;
+    timed.add_class::<Sumer>()?;
+    timed.add_class::<Meaner>()?;
+    timed.add_class::<Stder>()?;
+    timed.add_class::<Maxer>()?;
+    timed.add_class::<Miner>()?;
+    timed.add_class::<Quantiler>()?;
+    timed.add_class::<Correlationer>()?;
+    timed.add_class::<DecayMeaner>()?;
+    super::add_submodule(parent_module, &timed)
+}
@@ -1,22 +1,23 @@
+use crate::Indicator;
 use core::fmt;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Container {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     head_idx: usize,
     tail_idx: usize,
+    count: usize,
 }
 
-#[pymethods]
 impl Container {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             head_idx: 0,
             tail_idx: 0,
+            count: 0,
         }
     }
 
@@ -24,6 +25,7 @@ impl Container {
         self.tail_idx = self.head_idx;
         self.buf[self.tail_idx] = new_val;
         self.head_idx = (self.head_idx + 1) % self.buf.len();
+        self.count += 1;
 
         (self.buf[self.head_idx], self.buf[self.tail_idx])
     }
@@ -44,10 +46,11 @@ impl Container {
     pub fn len(&self) -> usize {
         self.buf.len()
     }
-}
 
-// just use in rust not in python
-impl Container {
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &f64> {
         (0..self.buf.len()).map(move |i| {
             let idx = (self.head_idx + i) % self.buf.len();
@@ -56,6 +59,34 @@ impl Container {
     }
 }
 
+impl Indicator for Container {
+    type Input = f64;
+    type Output = (f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        (self.head(), self.tail())
+    }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.head_idx = 0;
+        self.tail_idx = 0;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        self.buf.len().saturating_sub(1)
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
 impl fmt::Display for Container {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Collect the elements in logical order into a vector of strings
@@ -1,7 +1,8 @@
 use super::statis::Meaner;
-use pyo3::prelude::*;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Correlationer {
     x_meaner: Meaner,
     x_sq_meaner: Meaner,
@@ -10,9 +11,7 @@ pub struct Correlationer {
     xy_meaner: Meaner,
 }
 
-#[pymethods]
 impl Correlationer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             x_meaner: Meaner::new(n),
@@ -24,20 +23,55 @@ impl Correlationer {
     }
 
     pub fn update(&mut self, x: f64, y: f64) -> f64 {
-        let mean_x = self.x_meaner.update(x);
-        let mean_x_sq = self.x_sq_meaner.update(x * x);
-        let mean_y = self.y_meaner.update(y);
-        let mean_y_sq = self.y_sq_meaner.update(y * y);
-        let mean_xy = self.xy_meaner.update(x * y);
+        self.x_meaner.update(x);
+        self.x_sq_meaner.update(x * x);
+        self.y_meaner.update(y);
+        self.y_sq_meaner.update(y * y);
+        self.xy_meaner.update(x * y);
+
+        self.value()
+    }
+}
+
+impl Indicator for Correlationer {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (x, y): (f64, f64)) -> f64 {
+        self.update(x, y)
+    }
+
+    fn value(&self) -> f64 {
+        let mean_x = self.x_meaner.value();
+        let mean_x_sq = self.x_sq_meaner.value();
+        let mean_y = self.y_meaner.value();
+        let mean_y_sq = self.y_sq_meaner.value();
+        let mean_xy = self.xy_meaner.value();
 
         let sigmax_sq = (mean_x_sq - mean_x.powi(2)).sqrt();
         let sigmay_sq = (mean_y_sq - mean_y.powi(2)).sqrt();
 
         (mean_xy - mean_x * mean_y) / (sigmax_sq * sigmay_sq)
     }
+
+    fn reset(&mut self) {
+        self.x_meaner.reset();
+        self.x_sq_meaner.reset();
+        self.y_meaner.reset();
+        self.y_sq_meaner.reset();
+        self.xy_meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.x_meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.x_meaner.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Beta {
     x_meaner: Meaner,
     x_sq_meaner: Meaner,
@@ -45,9 +79,7 @@ pub struct Beta {
     xy_meaner: Meaner,
 }
 
-#[pymethods]
 impl Beta {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             x_meaner: Meaner::new(n),
@@ -58,26 +90,59 @@ impl Beta {
     }
 
     pub fn update(&mut self, x: f64, y: f64) -> f64 {
-        let mean_x = self.x_meaner.update(x);
-        let mean_x_sq = self.x_sq_meaner.update(x * x);
-        let mean_y = self.y_meaner.update(y);
-        let mean_xy = self.xy_meaner.update(x * y);
+        self.x_meaner.update(x);
+        self.x_sq_meaner.update(x * x);
+        self.y_meaner.update(y);
+        self.xy_meaner.update(x * y);
+
+        self.value()
+    }
+}
+
+impl Indicator for Beta {
+    type Input = (f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (x, y): (f64, f64)) -> f64 {
+        self.update(x, y)
+    }
+
+    fn value(&self) -> f64 {
+        let mean_x = self.x_meaner.value();
+        let mean_x_sq = self.x_sq_meaner.value();
+        let mean_y = self.y_meaner.value();
+        let mean_xy = self.xy_meaner.value();
 
         (mean_xy - mean_x * mean_y) / (mean_x_sq - mean_x.powi(2))
     }
+
+    fn reset(&mut self) {
+        self.x_meaner.reset();
+        self.x_sq_meaner.reset();
+        self.y_meaner.reset();
+        self.xy_meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.x_meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.x_meaner.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TSF {
     n: usize,
     y_meaner: Meaner,
+    #[serde(with = "crate::utils::float")]
     x_sq_meaner: f64,
+    #[serde(with = "crate::utils::float")]
     mean_x: f64,
 }
 
-#[pymethods]
 impl TSF {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             n,
@@ -88,7 +153,22 @@ impl TSF {
     }
 
     pub fn update(&mut self, y: f64) -> f64 {
-        let mean_y = self.y_meaner.update(y);
+        self.y_meaner.update(y);
+
+        self.value()
+    }
+}
+
+impl Indicator for TSF {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let mean_y = self.y_meaner.value();
         let sum_xy: f64 = self
             .y_meaner
             .iter()
@@ -101,19 +181,31 @@ impl TSF {
 
         slope * (self.n + 1) as f64 + intercept // forecast n+1
     }
+
+    fn reset(&mut self) {
+        self.y_meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.y_meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.y_meaner.count()
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LinearReg {
     n: usize,
     y_meaner: Meaner,
+    #[serde(with = "crate::utils::float")]
     x_sq_meaner: f64,
+    #[serde(with = "crate::utils::float")]
     mean_x: f64,
 }
 
-#[pymethods]
 impl LinearReg {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             n,
@@ -124,7 +216,22 @@ impl LinearReg {
     }
 
     pub fn update(&mut self, y: f64) -> (f64, f64, f64, f64) {
-        let mean_y = self.y_meaner.update(y);
+        self.y_meaner.update(y);
+
+        self.value()
+    }
+}
+
+impl Indicator for LinearReg {
+    type Input = f64;
+    type Output = (f64, f64, f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64, f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64, f64, f64) {
+        let mean_y = self.y_meaner.value();
         let sum_xy: f64 = self
             .y_meaner
             .iter()
@@ -140,4 +247,16 @@ impl LinearReg {
 
         (slope, intercept, last_reg, angle)
     }
+
+    fn reset(&mut self) {
+        self.y_meaner.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.y_meaner.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.y_meaner.count()
+    }
 }
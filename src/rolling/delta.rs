@@ -1,14 +1,13 @@
 use super::container::Container;
-use pyo3::prelude::*;
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Deltaer {
     container: Container,
 }
 
-#[pymethods]
 impl Deltaer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             container: Container::new(n),
@@ -38,14 +37,37 @@ impl Deltaer {
     }
 }
 
-#[pyclass]
+impl Indicator for Deltaer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.container.tail() - self.container.head()
+    }
+
+    fn reset(&mut self) {
+        self.container.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.container.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.container.count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pctchanger {
     container: Container,
 }
 
-#[pymethods]
 impl Pctchanger {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             container: Container::new(n),
@@ -74,3 +96,28 @@ impl Pctchanger {
         self.get(end) / self.get(start) - 1.0
     }
 }
+
+impl Indicator for Pctchanger {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.container.tail() / self.container.head() - 1.0
+    }
+
+    fn reset(&mut self) {
+        self.container.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.container.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.container.count()
+    }
+}
@@ -1,28 +1,103 @@
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::nan::{window_counts, NanGuard};
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Extreme tracks the max or the min of the valid values of a rolling buffer,
+// with a monotonic deque of input times whose front is the extreme, O(1) amortized per update.
+// Equal values are all kept, so ties resolve to the lowest buffer slot like a scan of the buffer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Extreme {
+    times: VecDeque<usize>,
+    is_max: bool,
+}
+
+impl Extreme {
+    fn new(is_max: bool) -> Self {
+        Self {
+            times: VecDeque::new(),
+            is_max,
+        }
+    }
+
+    // t is the time of the input just written to buf[t % n]
+    fn push(&mut self, buf: &[f64], t: usize) {
+        let n = buf.len();
+        while self.times.front().is_some_and(|&front| front + n <= t) {
+            self.times.pop_front();
+        }
+
+        let x = buf[t % n];
+        if x.is_nan() {
+            return;
+        }
+        while let Some(&back) = self.times.back() {
+            let y = buf[back % n];
+            if (self.is_max && y < x) || (!self.is_max && y > x) {
+                self.times.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.times.push_back(t);
+    }
+
+    // (slot, value) of the extreme as of the input at time `newest`
+    fn get(&self, buf: &[f64], newest: usize) -> (usize, f64) {
+        let n = buf.len();
+        let Some(&front) = self.times.front() else {
+            return (usize::MAX, f64::NAN);
+        };
+        let x = buf[front % n];
+
+        // ties written since the buffer last wrapped sit in the lowest slots,
+        // the value is read from the chosen slot as 0.0 and -0.0 tie
+        let lap = newest - newest % n;
+        let ties = self.times.partition_point(|&t| buf[t % n] == x);
+        let wrapped = self.times.partition_point(|&t| t < lap && buf[t % n] == x);
+        let t = if wrapped < ties {
+            self.times[wrapped]
+        } else {
+            front
+        };
+        (t % n, buf[t % n])
+    }
 
-#[pyclass]
+    fn reset(&mut self) {
+        self.times.clear();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Maxer {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     cur_idx: usize,
     nan_count: usize,
+    count: usize,
+    maxes: Extreme,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Maxer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             cur_idx: 0,
             nan_count: n,
+            count: 0,
+            maxes: Extreme::new(true),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.buf[self.cur_idx];
         self.buf[self.cur_idx] = new_val;
         self.cur_idx = (self.cur_idx + 1) % self.buf.len();
+        self.count += 1;
+        self.maxes.push(&self.buf, self.count - 1);
 
         if new_val.is_nan() {
             self.nan_count += 1;
@@ -32,38 +107,85 @@ impl Maxer {
             self.nan_count -= 1;
         }
 
-        if self.nan_count > 0 {
-            NAN
+        self.value()
+    }
+}
+
+impl Indicator for Maxer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = window_counts(self.buf.len(), self.count, self.nan_count);
+        if !self.guard.accepts(valid, invalid) {
+            f64::NAN
         } else {
-            self.buf
-                .iter()
-                .fold(NAN, |cur_max, x| if *x <= cur_max { cur_max } else { *x })
+            self.maxes.get(&self.buf, self.count - 1).1
         }
     }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.cur_idx = 0;
+        self.nan_count = self.buf.len();
+        self.count = 0;
+        self.maxes.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
 }
 
-#[pyclass]
+impl NanHandling for Maxer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.min(self.buf.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Miner {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     cur_idx: usize,
     nan_count: usize,
+    count: usize,
+    mins: Extreme,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Miner {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             cur_idx: 0,
             nan_count: n,
+            count: 0,
+            mins: Extreme::new(false),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.buf[self.cur_idx];
         self.buf[self.cur_idx] = new_val;
         self.cur_idx = (self.cur_idx + 1) % self.buf.len();
+        self.count += 1;
+        self.mins.push(&self.buf, self.count - 1);
 
         if new_val.is_nan() {
             self.nan_count += 1;
@@ -73,38 +195,88 @@ impl Miner {
             self.nan_count -= 1;
         }
 
-        if self.nan_count > 0 {
-            NAN
+        self.value()
+    }
+}
+
+impl Indicator for Miner {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = window_counts(self.buf.len(), self.count, self.nan_count);
+        if !self.guard.accepts(valid, invalid) {
+            f64::NAN
         } else {
-            self.buf
-                .iter()
-                .fold(NAN, |cur_min, x| if *x >= cur_min { cur_min } else { *x })
+            self.mins.get(&self.buf, self.count - 1).1
         }
     }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.cur_idx = 0;
+        self.nan_count = self.buf.len();
+        self.count = 0;
+        self.mins.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
 }
 
-#[pyclass]
+impl NanHandling for Miner {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.min(self.buf.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinMaxer {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     cur_idx: usize,
     nan_count: usize,
+    count: usize,
+    mins: Extreme,
+    maxes: Extreme,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl MinMaxer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             cur_idx: 0,
             nan_count: n,
+            count: 0,
+            mins: Extreme::new(false),
+            maxes: Extreme::new(true),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> (f64, f64) {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.buf[self.cur_idx];
         self.buf[self.cur_idx] = new_val;
         self.cur_idx = (self.cur_idx + 1) % self.buf.len();
+        self.count += 1;
+        self.mins.push(&self.buf, self.count - 1);
+        self.maxes.push(&self.buf, self.count - 1);
 
         if new_val.is_nan() {
             self.nan_count += 1;
@@ -114,52 +286,91 @@ impl MinMaxer {
             self.nan_count -= 1;
         }
 
-        if self.nan_count > 0 {
+        self.value()
+    }
+}
+
+impl Indicator for MinMaxer {
+    type Input = f64;
+    type Output = (f64, f64);
+
+    fn update(&mut self, input: f64) -> (f64, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (f64, f64) {
+        let (valid, invalid) = window_counts(self.buf.len(), self.count, self.nan_count);
+        if !self.guard.accepts(valid, invalid) {
             // (min_val, max_val)
-            (NAN, NAN)
+            (f64::NAN, f64::NAN)
         } else {
-            self.buf.iter().fold((NAN, NAN), |(cur_min, cur_max), x| {
-                let temp_min;
-                let temp_max;
-                if *x >= cur_min {
-                    temp_min = cur_min;
-                } else {
-                    temp_min = *x;
-                }
-
-                if *x <= cur_max {
-                    temp_max = cur_max;
-                } else {
-                    temp_max = *x;
-                }
-                (temp_min, temp_max)
-            })
+            let newest = self.count - 1;
+            (
+                self.mins.get(&self.buf, newest).1,
+                self.maxes.get(&self.buf, newest).1,
+            )
         }
     }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.cur_idx = 0;
+        self.nan_count = self.buf.len();
+        self.count = 0;
+        self.mins.reset();
+        self.maxes.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl NanHandling for MinMaxer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.min(self.buf.len()));
+    }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MaxIndexer {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     cur_idx: usize,
     nan_count: usize,
+    count: usize,
+    maxes: Extreme,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl MaxIndexer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             cur_idx: 0,
             nan_count: n,
+            count: 0,
+            maxes: Extreme::new(true),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> (usize, f64) {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.buf[self.cur_idx];
         self.buf[self.cur_idx] = new_val;
         self.cur_idx = (self.cur_idx + 1) % self.buf.len();
+        self.count += 1;
+        self.maxes.push(&self.buf, self.count - 1);
 
         if new_val.is_nan() {
             self.nan_count += 1;
@@ -169,45 +380,85 @@ impl MaxIndexer {
             self.nan_count -= 1;
         }
 
-        if self.nan_count > 0 {
-            (usize::MAX, NAN)
+        self.value()
+    }
+}
+
+impl Indicator for MaxIndexer {
+    type Input = f64;
+    type Output = (usize, f64);
+
+    fn update(&mut self, input: f64) -> (usize, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (usize, f64) {
+        let (valid, invalid) = window_counts(self.buf.len(), self.count, self.nan_count);
+        if !self.guard.accepts(valid, invalid) {
+            (usize::MAX, f64::NAN)
         } else {
-            self.buf
-                .iter()
-                .enumerate()
-                .fold((usize::MAX, NAN), |(cur_idx, cur_max), (idx, x)| {
-                    if *x <= cur_max {
-                        (cur_idx, cur_max)
-                    } else {
-                        (idx, *x)
-                    }
-                })
+            self.maxes.get(&self.buf, self.count - 1)
         }
     }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.cur_idx = 0;
+        self.nan_count = self.buf.len();
+        self.count = 0;
+        self.maxes.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
 }
 
-#[pyclass]
+impl NanHandling for MaxIndexer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.min(self.buf.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinIndexer {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     cur_idx: usize,
     nan_count: usize,
+    count: usize,
+    mins: Extreme,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl MinIndexer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             cur_idx: 0,
             nan_count: n,
+            count: 0,
+            mins: Extreme::new(false),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> (usize, f64) {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.buf[self.cur_idx];
         self.buf[self.cur_idx] = new_val;
         self.cur_idx = (self.cur_idx + 1) % self.buf.len();
+        self.count += 1;
+        self.mins.push(&self.buf, self.count - 1);
 
         if new_val.is_nan() {
             self.nan_count += 1;
@@ -217,45 +468,88 @@ impl MinIndexer {
             self.nan_count -= 1;
         }
 
-        if self.nan_count > 0 {
-            (usize::MAX, NAN)
+        self.value()
+    }
+}
+
+impl Indicator for MinIndexer {
+    type Input = f64;
+    type Output = (usize, f64);
+
+    fn update(&mut self, input: f64) -> (usize, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (usize, f64) {
+        let (valid, invalid) = window_counts(self.buf.len(), self.count, self.nan_count);
+        if !self.guard.accepts(valid, invalid) {
+            (usize::MAX, f64::NAN)
         } else {
-            self.buf
-                .iter()
-                .enumerate()
-                .fold((usize::MAX, NAN), |(cur_idx, cur_min), (idx, x)| {
-                    if *x >= cur_min {
-                        (cur_idx, cur_min)
-                    } else {
-                        (idx, *x)
-                    }
-                })
+            self.mins.get(&self.buf, self.count - 1)
         }
     }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.cur_idx = 0;
+        self.nan_count = self.buf.len();
+        self.count = 0;
+        self.mins.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
 }
 
-#[pyclass]
+impl NanHandling for MinIndexer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.min(self.buf.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinMaxIndexer {
+    #[serde(with = "crate::utils::float")]
     buf: Vec<f64>,
     cur_idx: usize,
     nan_count: usize,
+    count: usize,
+    mins: Extreme,
+    maxes: Extreme,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl MinMaxIndexer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            buf: vec![NAN; n],
+            buf: vec![f64::NAN; n],
             cur_idx: 0,
             nan_count: n,
+            count: 0,
+            mins: Extreme::new(false),
+            maxes: Extreme::new(true),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> (usize, f64, usize, f64) {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.buf[self.cur_idx];
         self.buf[self.cur_idx] = new_val;
         self.cur_idx = (self.cur_idx + 1) % self.buf.len();
+        self.count += 1;
+        self.mins.push(&self.buf, self.count - 1);
+        self.maxes.push(&self.buf, self.count - 1);
 
         if new_val.is_nan() {
             self.nan_count += 1;
@@ -265,30 +559,56 @@ impl MinMaxIndexer {
             self.nan_count -= 1;
         }
 
-        if self.nan_count > 0 {
+        self.value()
+    }
+}
+
+impl Indicator for MinMaxIndexer {
+    type Input = f64;
+    type Output = (usize, f64, usize, f64);
+
+    fn update(&mut self, input: f64) -> (usize, f64, usize, f64) {
+        self.update(input)
+    }
+
+    fn value(&self) -> (usize, f64, usize, f64) {
+        let (valid, invalid) = window_counts(self.buf.len(), self.count, self.nan_count);
+        if !self.guard.accepts(valid, invalid) {
             // (min_idx, min_val, max_indx, max_val)
-            (usize::MAX, NAN, usize::MAX, NAN)
+            (usize::MAX, f64::NAN, usize::MAX, f64::NAN)
         } else {
-            self.buf.iter().enumerate().fold(
-                (usize::MAX, NAN, usize::MAX, NAN),
-                |(cur_mini, cur_min, cur_maxi, cur_max), (idx, x)| {
-                    let temp_min;
-                    let temp_max;
-                    if *x >= cur_min {
-                        temp_min = (cur_mini, cur_min);
-                    } else {
-                        temp_min = (idx, *x);
-                    }
-
-                    if *x <= cur_max {
-                        temp_max = (cur_maxi, cur_max);
-                    } else {
-                        temp_max = (idx, *x);
-                    }
-
-                    (temp_min.0, temp_min.1, temp_max.0, temp_max.1)
-                },
-            )
+            let newest = self.count - 1;
+            let (min_idx, min_val) = self.mins.get(&self.buf, newest);
+            let (max_idx, max_val) = self.maxes.get(&self.buf, newest);
+            (min_idx, min_val, max_idx, max_val)
         }
     }
+
+    fn reset(&mut self) {
+        self.buf.fill(f64::NAN);
+        self.cur_idx = 0;
+        self.nan_count = self.buf.len();
+        self.count = 0;
+        self.mins.reset();
+        self.maxes.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl NanHandling for MinMaxIndexer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.min(self.buf.len()));
+    }
 }
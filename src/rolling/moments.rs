@@ -0,0 +1,148 @@
+use super::container::Container;
+use crate::nan::window_counts;
+use crate::utils::{is_nan_or_inf, CompensatedSum};
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// Moments keeps the power sums of a rolling window around a shift close to the window mean.
+// Raw power sums of prices like 50000.0 cancel catastrophically (x^4 is ~6e18), shifted ones do not.
+// The sums are compensated and rebuilt exactly from the window every n updates,
+// which also moves the shift to the current mean, so no drift builds up over long streams.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Moments {
+    container: Container,
+    nan_count: usize,
+    #[serde(with = "crate::utils::float")]
+    shift: f64,
+    // sums[p - 1] is the sum of (x - shift)^p over the valid values
+    sums: Vec<CompensatedSum>,
+    since_rebuild: usize,
+}
+
+// central sums of a window, sum of (x - mean)^p for p = 2, 3, 4
+#[derive(Debug, Clone, Copy)]
+pub struct Central {
+    pub mean: f64,
+    pub m2: f64,
+    pub m3: f64,
+    pub m4: f64,
+}
+
+impl Moments {
+    // order is the highest power kept, 2 for variance up to 4 for kurtosis
+    pub fn new(n: usize, order: usize) -> Self {
+        Self {
+            container: Container::new(n),
+            nan_count: n,
+            shift: f64::NAN,
+            sums: vec![CompensatedSum::new(); order],
+            since_rebuild: 0,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) {
+        let old_val = self.container.head();
+        self.container.update(new_val);
+
+        if is_nan_or_inf(old_val) {
+            self.nan_count -= 1;
+        } else {
+            self.accumulate(old_val, -1.0);
+        }
+
+        if is_nan_or_inf(new_val) {
+            self.nan_count += 1;
+        } else {
+            if self.shift.is_nan() {
+                self.shift = new_val;
+            }
+            self.accumulate(new_val, 1.0);
+        }
+
+        self.since_rebuild += 1;
+        if self.since_rebuild >= self.container.len() {
+            self.rebuild();
+        }
+    }
+
+    fn accumulate(&mut self, x: f64, sign: f64) {
+        let d = x - self.shift;
+        let mut term = sign;
+        for sum in self.sums.iter_mut() {
+            term *= d;
+            sum.add(term);
+        }
+    }
+
+    // exact recomputation from the window, re-centred on its mean
+    fn rebuild(&mut self) {
+        self.since_rebuild = 0;
+        let mut total = CompensatedSum::new();
+        let mut valid = 0;
+        for &x in self.container.iter().filter(|x| !is_nan_or_inf(**x)) {
+            total.add(x);
+            valid += 1;
+        }
+        self.shift = if valid > 0 {
+            total.value() / valid as f64
+        } else {
+            f64::NAN
+        };
+
+        self.sums.iter_mut().for_each(CompensatedSum::reset);
+        for i in 0..self.container.len() {
+            let x = self.container.get(i);
+            if !is_nan_or_inf(x) {
+                self.accumulate(x, 1.0);
+            }
+        }
+    }
+
+    fn sum(&self, p: usize) -> f64 {
+        self.sums.get(p - 1).map_or(0.0, CompensatedSum::value)
+    }
+
+    // central sums over the valid values, orders above the one kept are 0
+    pub fn central(&self) -> Central {
+        let (valid, _) = self.window_counts();
+        let n = valid as f64;
+        let (s1, s2, s3, s4) = (self.sum(1), self.sum(2), self.sum(3), self.sum(4));
+        let a = s1 / n;
+
+        Central {
+            mean: self.shift + a,
+            m2: s2 - s1 * a,
+            m3: s3 - 3.0 * a * s2 + 2.0 * n * a.powi(3),
+            m4: s4 - 4.0 * a * s3 + 6.0 * a * a * s2 - 3.0 * n * a.powi(4),
+        }
+    }
+
+    // (valid, invalid) values in the window
+    pub fn window_counts(&self) -> (usize, usize) {
+        window_counts(self.container.len(), self.container.count(), self.nan_count)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &f64> {
+        self.container.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.container.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.container.is_empty()
+    }
+
+    pub fn count(&self) -> usize {
+        self.container.count()
+    }
+
+    pub fn reset(&mut self) {
+        self.container.reset();
+        self.nan_count = self.container.len();
+        self.shift = f64::NAN;
+        self.sums.iter_mut().for_each(CompensatedSum::reset);
+        self.since_rebuild = 0;
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+// OrderStatTree is a multiset of finite f64 with O(log n) insert, remove, k-th smallest and rank.
+// It is a treap in an arena: nodes keep their subtree size, priorities come from a seeded
+// splitmix64 so a restored state behaves exactly like the original.
+// Keys are compared with `<`, so 0.0 and -0.0 are the same key like in a sorted Vec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderStatTree {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    seed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Node {
+    key: f64,
+    priority: u64,
+    size: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl OrderStatTree {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            seed: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size(self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    pub fn insert(&mut self, key: f64) {
+        let node = self.alloc(key);
+        let (left, right) = self.split(self.root, key, false);
+        let left = self.merge(left, Some(node));
+        self.root = self.merge(left, right);
+    }
+
+    // remove one occurrence of key, returns whether there was one
+    pub fn remove(&mut self, key: f64) -> bool {
+        let (left, rest) = self.split(self.root, key, false);
+        let (equal, right) = self.split(rest, key, true);
+        let found = equal.is_some();
+        let equal = equal.and_then(|idx| {
+            let (l, r) = (self.nodes[idx].left, self.nodes[idx].right);
+            self.free.push(idx);
+            self.merge(l, r)
+        });
+        let left = self.merge(left, equal);
+        self.root = self.merge(left, right);
+        found
+    }
+
+    // k-th smallest key, 0-based
+    pub fn kth(&self, mut k: usize) -> Option<f64> {
+        let mut cur = self.root;
+        while let Some(idx) = cur {
+            let node = &self.nodes[idx];
+            let left = self.size(node.left);
+            if k < left {
+                cur = node.left;
+            } else if k == left {
+                return Some(node.key);
+            } else {
+                k -= left + 1;
+                cur = node.right;
+            }
+        }
+        None
+    }
+
+    // linear interpolation between the closest ranks, like numpy and pandas, NaN when empty
+    pub fn quantile(&self, quantile: f64) -> f64 {
+        if self.is_empty() || !(0.0..=1.0).contains(&quantile) {
+            return f64::NAN;
+        }
+
+        let index = (self.len() - 1) as f64 * quantile;
+        let lower_index = index.floor() as usize;
+        let fraction = index - lower_index as f64;
+
+        let lower_value = self.kth(lower_index).unwrap_or(f64::NAN);
+        let upper_value = self.kth(lower_index + 1).unwrap_or(lower_value);
+
+        lower_value + fraction * (upper_value - lower_value)
+    }
+
+    // number of keys < key, or <= key when inclusive
+    pub fn rank(&self, key: f64, inclusive: bool) -> usize {
+        let mut rank = 0;
+        let mut cur = self.root;
+        while let Some(idx) = cur {
+            let node = &self.nodes[idx];
+            if node.key < key || (inclusive && node.key == key) {
+                rank += self.size(node.left) + 1;
+                cur = node.right;
+            } else {
+                cur = node.left;
+            }
+        }
+        rank
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.root = None;
+    }
+
+    fn size(&self, node: Option<usize>) -> usize {
+        node.map_or(0, |idx| self.nodes[idx].size)
+    }
+
+    fn update_size(&mut self, idx: usize) {
+        let node = &self.nodes[idx];
+        self.nodes[idx].size = self.size(node.left) + self.size(node.right) + 1;
+    }
+
+    fn alloc(&mut self, key: f64) -> usize {
+        // splitmix64
+        self.seed = self.seed.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        let node = Node {
+            key,
+            priority: z ^ (z >> 31),
+            size: 1,
+            left: None,
+            right: None,
+        };
+        match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = node;
+                idx
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    // split into (keys < key, keys >= key), or (keys <= key, keys > key) when inclusive
+    fn split(
+        &mut self,
+        node: Option<usize>,
+        key: f64,
+        inclusive: bool,
+    ) -> (Option<usize>, Option<usize>) {
+        let Some(idx) = node else {
+            return (None, None);
+        };
+        let node_key = self.nodes[idx].key;
+        if node_key < key || (inclusive && node_key == key) {
+            let (left, right) = self.split(self.nodes[idx].right, key, inclusive);
+            self.nodes[idx].right = left;
+            self.update_size(idx);
+            (Some(idx), right)
+        } else {
+            let (left, right) = self.split(self.nodes[idx].left, key, inclusive);
+            self.nodes[idx].left = right;
+            self.update_size(idx);
+            (left, Some(idx))
+        }
+    }
+
+    // every key of left is <= every key of right
+    fn merge(&mut self, left: Option<usize>, right: Option<usize>) -> Option<usize> {
+        match (left, right) {
+            (None, node) | (node, None) => node,
+            (Some(l), Some(r)) => {
+                if self.nodes[l].priority > self.nodes[r].priority {
+                    self.nodes[l].right = self.merge(self.nodes[l].right, Some(r));
+                    self.update_size(l);
+                    Some(l)
+                } else {
+                    self.nodes[r].left = self.merge(Some(l), self.nodes[r].left);
+                    self.update_size(r);
+                    Some(r)
+                }
+            }
+        }
+    }
+}
+
+impl Default for OrderStatTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,69 +1,308 @@
 use super::container::Container;
+use super::ostree::OrderStatTree;
+use crate::nan::{window_counts, NanGuard};
 use crate::utils::is_nan_or_inf;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
-pub struct Quantiler {
+// SortedWindow keeps the finite values of a rolling window ordered, shared by the quantile types
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SortedWindow {
     container: Container,
-    dataset: Vec<f64>,
+    tree: OrderStatTree,
     nan_count: usize,
-    quantile: f64,
+    guard: NanGuard,
 }
 
-#[pymethods]
-impl Quantiler {
-    #[new]
-    pub fn new(n: usize, quantile: f64) -> Self {
+impl SortedWindow {
+    fn new(n: usize) -> Self {
         Self {
             container: Container::new(n),
-            dataset: Vec::new(),
+            tree: OrderStatTree::new(),
             nan_count: n,
-            quantile,
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
-    pub fn update(&mut self, new_val: f64) -> f64 {
+    fn update(&mut self, new_val: f64) {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.container.head();
         self.container.update(new_val);
 
-        // Update nan_count and dataset based on new_val
         if is_nan_or_inf(new_val) {
             self.nan_count += 1;
         } else {
-            let pos = self
-                .dataset
-                .binary_search_by(|v| v.partial_cmp(&new_val).unwrap())
-                .unwrap_or_else(|e| e);
-            self.dataset.insert(pos, new_val);
+            self.tree.insert(new_val);
         }
 
-        // Update nan_count and dataset based on old_val
         if is_nan_or_inf(old_val) {
             self.nan_count -= 1;
         } else {
-            let pos = self
-                .dataset
-                .binary_search_by(|v| v.partial_cmp(&old_val).unwrap())
-                .unwrap();
-            self.dataset.remove(pos);
+            self.tree.remove(old_val);
         }
+    }
 
-        if self.nan_count > 0 {
-            NAN
-        } else {
-            let index = (self.dataset.len() - 1) as f64 * self.quantile;
-            let lower_index = index.floor() as usize;
-            let fraction = index - lower_index as f64;
-
-            let lower_value = self.dataset[lower_index];
-            let upper_value = if lower_index + 1 < self.dataset.len() {
-                self.dataset[lower_index + 1]
-            } else {
-                lower_value
-            };
-
-            lower_value + fraction * (upper_value - lower_value)
+    fn is_valid(&self) -> bool {
+        let (valid, invalid) =
+            window_counts(self.container.len(), self.container.count(), self.nan_count);
+        self.guard.accepts(valid, invalid)
+    }
+
+    fn quantile(&self, quantile: f64) -> f64 {
+        if !self.is_valid() {
+            return f64::NAN;
+        }
+        self.tree.quantile(quantile)
+    }
+
+    fn reset(&mut self) {
+        self.container.reset();
+        self.tree.clear();
+        self.nan_count = self.container.len();
+        self.guard.reset();
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard
+            .set_min_periods(min_periods.min(self.container.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quantiler {
+    window: SortedWindow,
+    #[serde(with = "crate::utils::float")]
+    quantile: f64,
+}
+
+impl Quantiler {
+    pub fn new(n: usize, quantile: f64) -> Self {
+        Self {
+            window: SortedWindow::new(n),
+            quantile,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        self.window.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Quantiler {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.window.quantile(self.quantile)
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.window.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.container.count()
+    }
+}
+
+impl NanHandling for Quantiler {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.window.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.window.set_min_periods(min_periods);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Medianer {
+    window: SortedWindow,
+}
+
+impl Medianer {
+    pub fn new(n: usize) -> Self {
+        Self {
+            window: SortedWindow::new(n),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        self.window.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Medianer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        self.window.quantile(0.5)
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.window.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.container.count()
+    }
+}
+
+impl NanHandling for Medianer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.window.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.window.set_min_periods(min_periods);
+    }
+}
+
+// percent rank of the latest value within its window, in (0, 1],
+// ties get their average rank like pandas rolling(n).rank(pct=True)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PctRanker {
+    window: SortedWindow,
+}
+
+impl PctRanker {
+    pub fn new(n: usize) -> Self {
+        Self {
+            window: SortedWindow::new(n),
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> f64 {
+        self.window.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for PctRanker {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let latest = self.window.container.tail();
+        if !self.window.is_valid() || is_nan_or_inf(latest) {
+            return f64::NAN;
         }
+
+        let below = self.window.tree.rank(latest, false);
+        let equal = self.window.tree.rank(latest, true) - below;
+        (below as f64 + (equal + 1) as f64 / 2.0) / self.window.tree.len() as f64
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.window.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.container.count()
+    }
+}
+
+impl NanHandling for PctRanker {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.window.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.window.set_min_periods(min_periods);
+    }
+}
+
+// several quantiles of one window, in the order given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultiQuantiler {
+    window: SortedWindow,
+    #[serde(with = "crate::utils::float")]
+    quantiles: Vec<f64>,
+}
+
+impl MultiQuantiler {
+    pub fn new(n: usize, quantiles: Vec<f64>) -> Self {
+        Self {
+            window: SortedWindow::new(n),
+            quantiles,
+        }
+    }
+
+    pub fn update(&mut self, new_val: f64) -> Vec<f64> {
+        self.window.update(new_val);
+
+        self.value()
+    }
+
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+}
+
+impl Indicator for MultiQuantiler {
+    type Input = f64;
+    type Output = Vec<f64>;
+
+    fn update(&mut self, input: f64) -> Vec<f64> {
+        self.update(input)
+    }
+
+    fn value(&self) -> Vec<f64> {
+        self.quantiles
+            .iter()
+            .map(|&q| self.window.quantile(q))
+            .collect()
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.window.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.container.count()
+    }
+}
+
+impl NanHandling for MultiQuantiler {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.window.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.window.set_min_periods(min_periods);
     }
 }
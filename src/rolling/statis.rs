@@ -1,27 +1,32 @@
 use super::container::Container;
-use crate::utils::is_nan_or_inf;
-use pyo3::prelude::*;
-use std::f64::NAN;
+use super::moments::Moments;
+use crate::nan::{window_counts, NanGuard};
+use crate::utils::{is_nan_or_inf, CompensatedSum};
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Sumer {
     container: Container,
     nan_count: usize,
-    sum: f64,
+    sum: CompensatedSum,
+    since_rebuild: usize,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Sumer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             container: Container::new(n),
             nan_count: n,
-            sum: 0.0,
+            sum: CompensatedSum::new(),
+            since_rebuild: 0,
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
         let old_val = self.container.head();
         self.container.update(new_val);
         // println!("{}", self.container);
@@ -29,37 +34,94 @@ impl Sumer {
         if is_nan_or_inf(old_val) {
             self.nan_count -= 1;
         } else {
-            self.sum -= old_val;
+            self.sum.sub(old_val);
         }
 
         if is_nan_or_inf(new_val) {
             self.nan_count += 1;
         } else {
-            self.sum += new_val;
+            self.sum.add(new_val);
         }
 
-        if self.nan_count > 0 {
-            NAN
-        } else {
-            self.sum
+        // an exact sum every window length keeps the error of the running sum bounded
+        self.since_rebuild += 1;
+        if self.since_rebuild >= self.container.len() {
+            self.since_rebuild = 0;
+            self.sum.reset();
+            for &x in self.container.iter().filter(|x| !is_nan_or_inf(**x)) {
+                self.sum.add(x);
+            }
         }
+
+        self.value()
     }
-}
 
-impl Sumer {
     pub fn iter(&self) -> impl Iterator<Item = &f64> {
         self.container.iter()
     }
+
+    // (valid, invalid) values in the window
+    pub fn window_counts(&self) -> (usize, usize) {
+        window_counts(self.container.len(), self.container.count(), self.nan_count)
+    }
+
+    // sum of the valid values in the window, whatever the policy
+    fn valid_sum(&self) -> f64 {
+        self.sum.value()
+    }
 }
 
-#[pyclass]
+impl Indicator for Sumer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.window_counts();
+        if self.guard.accepts(valid, invalid) {
+            self.valid_sum()
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.container.reset();
+        self.nan_count = self.container.len();
+        self.sum.reset();
+        self.since_rebuild = 0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.container.count()
+    }
+}
+
+impl NanHandling for Sumer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard
+            .set_min_periods(min_periods.min(self.container.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Meaner {
     sumer: Sumer,
 }
 
-#[pymethods]
 impl Meaner {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
             sumer: Sumer::new(n),
@@ -67,113 +129,262 @@ impl Meaner {
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        self.sumer.update(new_val) / self.sumer.container.len() as f64
+        self.sumer.update(new_val);
+
+        self.value()
     }
-}
 
-impl Meaner {
     pub fn iter(&self) -> impl Iterator<Item = &f64> {
         self.sumer.iter()
     }
+
+    pub fn window_counts(&self) -> (usize, usize) {
+        self.sumer.window_counts()
+    }
+}
+
+impl Indicator for Meaner {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, _) = self.sumer.window_counts();
+        self.sumer.value() / valid as f64
+    }
+
+    fn reset(&mut self) {
+        self.sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.sumer.count()
+    }
 }
 
-#[pyclass]
+impl NanHandling for Meaner {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.sumer.set_nan_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.sumer.set_min_periods(min_periods);
+    }
+}
+
+// the moment types below work on central sums of the window, see Moments,
+// and fill inputs and check the window with their own guard
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stder {
-    sumer: Sumer,
-    sq_sumer: Sumer,
-    n: usize,
+    moments: Moments,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Stder {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            sumer: Sumer::new(n),
-            sq_sumer: Sumer::new(n),
-            n,
+            moments: Moments::new(n, 2),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        let sum = self.sumer.update(new_val);
-        let sq_sum = self.sq_sumer.update(new_val * new_val);
+        let new_val = self.guard.fill(new_val);
+        self.moments.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Stder {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
 
-        let variance = (sq_sum - sum * sum / self.n as f64) / (self.n as f64 - 1.0);
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.moments.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            return f64::NAN;
+        }
+
+        let n = valid as f64;
+        // the central sum cannot be negative but rounding can make it so,
+        // a single value stays NaN
+        let m2 = self.moments.central().m2;
+        let variance = if m2 < 0.0 { 0.0 } else { m2 / (n - 1.0) };
         variance.sqrt()
     }
+
+    fn reset(&mut self) {
+        self.moments.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.moments.count()
+    }
 }
 
-#[pyclass]
+impl NanHandling for Stder {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard
+            .set_min_periods(min_periods.min(self.moments.len()));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skewer {
-    meaner: Meaner,
-    sq_sumer: Sumer,
-    cub_sumer: Sumer,
-    n: usize,
+    moments: Moments,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Skewer {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            meaner: Meaner::new(n),
-            sq_sumer: Sumer::new(n),
-            cub_sumer: Sumer::new(n),
-            n,
+            moments: Moments::new(n, 3),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        let mean = self.meaner.update(new_val);
-        let sq_sum = self.sq_sumer.update(new_val * new_val);
-        let cub_sum = self.cub_sumer.update(new_val * new_val * new_val);
+        let new_val = self.guard.fill(new_val);
+        self.moments.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Skewer {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.moments.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            return f64::NAN;
+        }
+
+        let n = valid as f64;
+        let central = self.moments.central();
+        let variance = central.m2 / n;
+
+        (central.m3 / n) / variance.powf(1.5)
+    }
+
+    fn reset(&mut self) {
+        self.moments.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
 
-        let variance = sq_sum / self.n as f64 - mean * mean;
+    fn count(&self) -> usize {
+        self.moments.count()
+    }
+}
+
+impl NanHandling for Skewer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
 
-        (cub_sum / self.n as f64 - 3.0 * mean * variance - mean.powi(3)) / variance.powf(1.5)
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard
+            .set_min_periods(min_periods.min(self.moments.len()));
     }
 }
 
-#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Kurter {
-    sumer: Sumer,
-    sq_sumer: Sumer,
-    cub_sumer: Sumer,
-    quad_sumer: Sumer,
-    n: usize,
-    factor01: f64,
-    factor02: f64,
+    moments: Moments,
+    guard: NanGuard,
 }
 
-#[pymethods]
 impl Kurter {
-    #[new]
     pub fn new(n: usize) -> Self {
         Self {
-            sumer: Sumer::new(n),
-            sq_sumer: Sumer::new(n),
-            cub_sumer: Sumer::new(n),
-            quad_sumer: Sumer::new(n),
-            n,
-            factor01: (n * (n + 1)) as f64 / ((n - 1) * (n - 2) * (n - 3)) as f64,
-            factor02: ((n - 1) * (n - 1)) as f64 / ((n - 2) * (n - 3)) as f64,
+            moments: Moments::new(n, 4),
+            guard: NanGuard::new(NanPolicy::Propagate, n),
         }
     }
 
     pub fn update(&mut self, new_val: f64) -> f64 {
-        let quad_sum = self.quad_sumer.update(new_val.powi(4));
-        let cub_sum = self.cub_sumer.update(new_val.powi(3));
-        let sq_sum = self.sq_sumer.update(new_val.powi(2));
-        let sum = self.sumer.update(new_val);
-        let mean = sum / self.n as f64;
-        let variance =
-            (sq_sum - 2.0 * sum * mean + self.n as f64 * mean.powi(2)) / (self.n as f64 - 1.0);
-
-        let value = quad_sum - 4.0 * cub_sum * mean + 6.0 * sq_sum * mean.powi(2)
-            - 4.0 * sum * mean.powi(3)
-            + self.n as f64 * mean.powi(4);
-
-        self.factor01 * value / variance.powi(2) - self.factor02
+        let new_val = self.guard.fill(new_val);
+        self.moments.update(new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Kurter {
+    type Input = f64;
+    type Output = f64;
+
+    fn update(&mut self, input: f64) -> f64 {
+        self.update(input)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.moments.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            return f64::NAN;
+        }
+
+        let n = valid as f64;
+        let central = self.moments.central();
+        let variance = central.m2 / (n - 1.0);
+
+        let factor01 = n * (n + 1.0) / ((n - 1.0) * (n - 2.0) * (n - 3.0));
+        let factor02 = (n - 1.0) * (n - 1.0) / ((n - 2.0) * (n - 3.0));
+
+        factor01 * central.m4 / variance.powi(2) - factor02
+    }
+
+    fn reset(&mut self) {
+        self.moments.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.moments.count()
+    }
+}
+
+impl NanHandling for Kurter {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard
+            .set_min_periods(min_periods.min(self.moments.len()));
     }
 }
@@ -0,0 +1,167 @@
+use super::window::TimeWindow;
+use crate::utils::{is_nan_or_inf, CompensatedSum};
+use crate::Indicator;
+use serde::{Deserialize, Serialize};
+
+// Pearson correlation of the pairs in the window, NaN until it holds two of them.
+// the co-moment sums are kept around shifts close to the window means and rebuilt exactly every
+// window length like Stder, raw E[xy] - E[x]E[y] cancels catastrophically for prices like 50000.0.
+// a pair with a NaN/inf value makes the output NaN while it is in the window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Correlationer {
+    xs: TimeWindow,
+    ys: TimeWindow,
+    #[serde(with = "crate::utils::float")]
+    shift_x: f64,
+    #[serde(with = "crate::utils::float")]
+    shift_y: f64,
+    sum_x: CompensatedSum,
+    sum_y: CompensatedSum,
+    sum_xx: CompensatedSum,
+    sum_yy: CompensatedSum,
+    sum_xy: CompensatedSum,
+    since_rebuild: usize,
+}
+
+impl Correlationer {
+    pub fn new(duration: i64) -> Self {
+        Self {
+            xs: TimeWindow::new(duration),
+            ys: TimeWindow::new(duration),
+            shift_x: f64::NAN,
+            shift_y: f64::NAN,
+            sum_x: CompensatedSum::new(),
+            sum_y: CompensatedSum::new(),
+            sum_xx: CompensatedSum::new(),
+            sum_yy: CompensatedSum::new(),
+            sum_xy: CompensatedSum::new(),
+            since_rebuild: 0,
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, x: f64, y: f64) -> f64 {
+        // both values of an invalid pair are kept as NaN, so the windows count alike
+        let (x, y) = if is_nan_or_inf(x) || is_nan_or_inf(y) {
+            (f64::NAN, f64::NAN)
+        } else {
+            (x, y)
+        };
+        self.xs.push(timestamp, x);
+        self.ys.push(timestamp, y);
+        if !x.is_nan() {
+            if self.shift_x.is_nan() {
+                self.shift_x = x;
+                self.shift_y = y;
+            }
+            self.accumulate(x, y, 1.0);
+        }
+        while let (Some(old_x), Some(old_y)) = (self.xs.pop_expired(), self.ys.pop_expired()) {
+            if !old_x.is_nan() {
+                self.accumulate(old_x, old_y, -1.0);
+            }
+        }
+
+        self.since_rebuild += 1;
+        if self.since_rebuild >= self.xs.len() {
+            self.rebuild();
+        }
+
+        self.value()
+    }
+
+    fn accumulate(&mut self, x: f64, y: f64, sign: f64) {
+        let (dx, dy) = (x - self.shift_x, y - self.shift_y);
+        self.sum_x.add(sign * dx);
+        self.sum_y.add(sign * dy);
+        self.sum_xx.add(sign * dx * dx);
+        self.sum_yy.add(sign * dy * dy);
+        self.sum_xy.add(sign * dx * dy);
+    }
+
+    // exact recomputation from the window, re-centred on its means
+    fn rebuild(&mut self) {
+        self.since_rebuild = 0;
+        let pairs = || {
+            self.xs
+                .iter()
+                .zip(self.ys.iter())
+                .filter(|(x, _)| !x.is_nan())
+        };
+        let (mut total_x, mut total_y) = (CompensatedSum::new(), CompensatedSum::new());
+        let mut valid = 0;
+        for (&x, &y) in pairs() {
+            total_x.add(x);
+            total_y.add(y);
+            valid += 1;
+        }
+        let (shift_x, shift_y) = if valid > 0 {
+            (
+                total_x.value() / valid as f64,
+                total_y.value() / valid as f64,
+            )
+        } else {
+            (f64::NAN, f64::NAN)
+        };
+
+        let window: Vec<(f64, f64)> = pairs().map(|(&x, &y)| (x, y)).collect();
+        self.shift_x = shift_x;
+        self.shift_y = shift_y;
+        self.sum_x.reset();
+        self.sum_y.reset();
+        self.sum_xx.reset();
+        self.sum_yy.reset();
+        self.sum_xy.reset();
+        for (x, y) in window {
+            self.accumulate(x, y, 1.0);
+        }
+    }
+}
+
+impl Indicator for Correlationer {
+    type Input = (i64, f64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, x, y): (i64, f64, f64)) -> f64 {
+        self.update(timestamp, x, y)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.xs.window_counts();
+        if invalid > 0 || valid < 2 {
+            return f64::NAN;
+        }
+
+        let n = valid as f64;
+        let (sx, sy) = (self.sum_x.value(), self.sum_y.value());
+        let var_x = self.sum_xx.value() - sx * sx / n;
+        let var_y = self.sum_yy.value() - sy * sy / n;
+        let cov = self.sum_xy.value() - sx * sy / n;
+        // a constant series has no correlation, rounding must not push it past +-1
+        if var_x <= 0.0 || var_y <= 0.0 {
+            return f64::NAN;
+        }
+        (cov / (var_x * var_y).sqrt()).clamp(-1.0, 1.0)
+    }
+
+    fn reset(&mut self) {
+        self.xs.reset();
+        self.ys.reset();
+        self.shift_x = f64::NAN;
+        self.shift_y = f64::NAN;
+        self.sum_x.reset();
+        self.sum_y.reset();
+        self.sum_xx.reset();
+        self.sum_yy.reset();
+        self.sum_xy.reset();
+        self.since_rebuild = 0;
+    }
+
+    // a correlation needs two pairs
+    fn lookback(&self) -> usize {
+        1
+    }
+
+    fn count(&self) -> usize {
+        self.xs.count()
+    }
+}
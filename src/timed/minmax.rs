@@ -0,0 +1,192 @@
+use super::window::TimeWindow;
+use crate::nan::NanGuard;
+use crate::utils::is_nan_or_inf;
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Extreme tracks the max or the min of the valid values of a time window,
+// with a monotonic deque of sequence numbers whose front is the extreme, O(1) amortized per update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Extreme {
+    seqs: VecDeque<usize>,
+    is_max: bool,
+}
+
+impl Extreme {
+    fn new(is_max: bool) -> Self {
+        Self {
+            seqs: VecDeque::new(),
+            is_max,
+        }
+    }
+
+    // call after the newest input was pushed and the expired ones popped
+    fn push(&mut self, window: &TimeWindow) {
+        while self
+            .seqs
+            .front()
+            .is_some_and(|&seq| seq < window.first_seq())
+        {
+            self.seqs.pop_front();
+        }
+
+        let x = window.newest();
+        if is_nan_or_inf(x) {
+            return;
+        }
+        while let Some(&back) = self.seqs.back() {
+            let y = window.get(back);
+            if (self.is_max && y < x) || (!self.is_max && y > x) {
+                self.seqs.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.seqs.push_back(window.count() - 1);
+    }
+
+    fn get(&self, window: &TimeWindow) -> f64 {
+        self.seqs.front().map_or(f64::NAN, |&seq| window.get(seq))
+    }
+
+    fn reset(&mut self) {
+        self.seqs.clear();
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Maxer {
+    window: TimeWindow,
+    maxes: Extreme,
+    guard: NanGuard,
+}
+
+impl Maxer {
+    pub fn new(duration: i64) -> Self {
+        Self {
+            window: TimeWindow::new(duration),
+            maxes: Extreme::new(true),
+            guard: NanGuard::new(NanPolicy::Propagate, 1),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
+        self.window.push(timestamp, new_val);
+        while self.window.pop_expired().is_some() {}
+        self.maxes.push(&self.window);
+
+        self.value()
+    }
+}
+
+impl Indicator for Maxer {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.window.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            f64::NAN
+        } else {
+            self.maxes.get(&self.window)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.maxes.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.count()
+    }
+}
+
+impl NanHandling for Maxer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Miner {
+    window: TimeWindow,
+    mins: Extreme,
+    guard: NanGuard,
+}
+
+impl Miner {
+    pub fn new(duration: i64) -> Self {
+        Self {
+            window: TimeWindow::new(duration),
+            mins: Extreme::new(false),
+            guard: NanGuard::new(NanPolicy::Propagate, 1),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
+        self.window.push(timestamp, new_val);
+        while self.window.pop_expired().is_some() {}
+        self.mins.push(&self.window);
+
+        self.value()
+    }
+}
+
+impl Indicator for Miner {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.window.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            f64::NAN
+        } else {
+            self.mins.get(&self.window)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.mins.reset();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.count()
+    }
+}
+
+impl NanHandling for Miner {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
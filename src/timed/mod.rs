@@ -0,0 +1,12 @@
+// Time-based rolling windows for irregular data, e.g. ticks.
+// Inputs are (timestamp, value) and the window holds the inputs of the last `duration`:
+// the window ending at timestamp t is (t - duration, t], like pandas rolling("5min").
+// Timestamps are i64 in any unit, e.g. epoch milliseconds, and durations are in the same unit.
+// They must not decrease, an earlier timestamp is taken as the latest one.
+// NaN/inf inputs follow the NanPolicy like the count-based rolling types,
+// min_periods defaults to 1 since the number of inputs in a window is not known in advance.
+pub mod corr;
+pub mod minmax;
+pub mod quantile;
+pub mod statis;
+pub mod window;
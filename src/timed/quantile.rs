@@ -0,0 +1,87 @@
+use super::window::TimeWindow;
+use crate::nan::NanGuard;
+use crate::rolling::ostree::OrderStatTree;
+use crate::utils::is_nan_or_inf;
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quantiler {
+    window: TimeWindow,
+    tree: OrderStatTree,
+    #[serde(with = "crate::utils::float")]
+    quantile: f64,
+    guard: NanGuard,
+}
+
+impl Quantiler {
+    pub fn new(duration: i64, quantile: f64) -> Self {
+        Self {
+            window: TimeWindow::new(duration),
+            tree: OrderStatTree::new(),
+            quantile,
+            guard: NanGuard::new(NanPolicy::Propagate, 1),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
+        self.window.push(timestamp, new_val);
+        if !is_nan_or_inf(new_val) {
+            self.tree.insert(new_val);
+        }
+        while let Some(old_val) = self.window.pop_expired() {
+            if !is_nan_or_inf(old_val) {
+                self.tree.remove(old_val);
+            }
+        }
+
+        self.value()
+    }
+
+    // any other quantile of the same window
+    pub fn get_quantile(&self, quantile: f64) -> f64 {
+        let (valid, invalid) = self.window.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            return f64::NAN;
+        }
+        self.tree.quantile(quantile)
+    }
+}
+
+impl Indicator for Quantiler {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        self.get_quantile(self.quantile)
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.tree.clear();
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.count()
+    }
+}
+
+impl NanHandling for Quantiler {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
@@ -0,0 +1,365 @@
+use super::window::TimeWindow;
+use crate::nan::NanGuard;
+use crate::utils::{is_nan_or_inf, CompensatedSum};
+use crate::{Indicator, NanHandling, NanPolicy};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sumer {
+    window: TimeWindow,
+    sum: CompensatedSum,
+    since_rebuild: usize,
+    guard: NanGuard,
+}
+
+impl Sumer {
+    pub fn new(duration: i64) -> Self {
+        Self {
+            window: TimeWindow::new(duration),
+            sum: CompensatedSum::new(),
+            since_rebuild: 0,
+            guard: NanGuard::new(NanPolicy::Propagate, 1),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
+        self.window.push(timestamp, new_val);
+        if !is_nan_or_inf(new_val) {
+            self.sum.add(new_val);
+        }
+        while let Some(old_val) = self.window.pop_expired() {
+            if !is_nan_or_inf(old_val) {
+                self.sum.sub(old_val);
+            }
+        }
+
+        // an exact sum every window length keeps the error of the running sum bounded
+        self.since_rebuild += 1;
+        if self.since_rebuild >= self.window.len() {
+            self.since_rebuild = 0;
+            self.sum.reset();
+            for &x in self.window.iter().filter(|x| !is_nan_or_inf(**x)) {
+                self.sum.add(x);
+            }
+        }
+
+        self.value()
+    }
+
+    // (valid, invalid) values in the window
+    pub fn window_counts(&self) -> (usize, usize) {
+        self.window.window_counts()
+    }
+}
+
+impl Indicator for Sumer {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.window.window_counts();
+        if self.guard.accepts(valid, invalid) {
+            self.sum.value()
+        } else {
+            f64::NAN
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.sum.reset();
+        self.since_rebuild = 0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.count()
+    }
+}
+
+impl NanHandling for Sumer {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Meaner {
+    sumer: Sumer,
+}
+
+impl Meaner {
+    pub fn new(duration: i64) -> Self {
+        Self {
+            sumer: Sumer::new(duration),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        self.sumer.update(timestamp, new_val);
+
+        self.value()
+    }
+}
+
+impl Indicator for Meaner {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, _) = self.sumer.window_counts();
+        self.sumer.value() / valid as f64
+    }
+
+    fn reset(&mut self) {
+        self.sumer.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.sumer.lookback()
+    }
+
+    fn count(&self) -> usize {
+        self.sumer.count()
+    }
+}
+
+impl NanHandling for Meaner {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.sumer.set_nan_policy(policy);
+    }
+
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.sumer.set_min_periods(min_periods);
+    }
+}
+
+// sample std, the sums of x - shift and (x - shift)^2 are kept around a shift close to the window mean
+// and rebuilt exactly every window length like rolling::moments::Moments.
+// min_periods is at least 2, a single value has no sample std
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stder {
+    window: TimeWindow,
+    #[serde(with = "crate::utils::float")]
+    shift: f64,
+    sum: CompensatedSum,
+    sum_sq: CompensatedSum,
+    since_rebuild: usize,
+    guard: NanGuard,
+}
+
+impl Stder {
+    pub fn new(duration: i64) -> Self {
+        Self {
+            window: TimeWindow::new(duration),
+            shift: f64::NAN,
+            sum: CompensatedSum::new(),
+            sum_sq: CompensatedSum::new(),
+            since_rebuild: 0,
+            guard: NanGuard::new(NanPolicy::Propagate, 2),
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        let new_val = self.guard.fill(new_val);
+        self.window.push(timestamp, new_val);
+        if !is_nan_or_inf(new_val) {
+            if self.shift.is_nan() {
+                self.shift = new_val;
+            }
+            self.accumulate(new_val, 1.0);
+        }
+        while let Some(old_val) = self.window.pop_expired() {
+            if !is_nan_or_inf(old_val) {
+                self.accumulate(old_val, -1.0);
+            }
+        }
+
+        self.since_rebuild += 1;
+        if self.since_rebuild >= self.window.len() {
+            self.rebuild();
+        }
+
+        self.value()
+    }
+
+    fn accumulate(&mut self, x: f64, sign: f64) {
+        let d = x - self.shift;
+        self.sum.add(sign * d);
+        self.sum_sq.add(sign * d * d);
+    }
+
+    // exact recomputation from the window, re-centred on its mean
+    fn rebuild(&mut self) {
+        self.since_rebuild = 0;
+        let mut total = CompensatedSum::new();
+        let (valid, _) = self.window.window_counts();
+        for &x in self.window.iter().filter(|x| !is_nan_or_inf(**x)) {
+            total.add(x);
+        }
+        self.shift = if valid > 0 {
+            total.value() / valid as f64
+        } else {
+            f64::NAN
+        };
+
+        self.sum.reset();
+        self.sum_sq.reset();
+        for &x in self.window.iter().filter(|x| !is_nan_or_inf(**x)) {
+            let d = x - self.shift;
+            self.sum.add(d);
+            self.sum_sq.add(d * d);
+        }
+    }
+}
+
+impl Indicator for Stder {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        let (valid, invalid) = self.window.window_counts();
+        if !self.guard.accepts(valid, invalid) {
+            return f64::NAN;
+        }
+
+        let n = valid as f64;
+        let (s1, s2) = (self.sum.value(), self.sum_sq.value());
+        // the central sum cannot be negative but rounding can make it so,
+        // a single value stays NaN
+        let m2 = s2 - s1 * s1 / n;
+        let variance = if m2 < 0.0 { 0.0 } else { m2 / (n - 1.0) };
+        variance.sqrt()
+    }
+
+    fn reset(&mut self) {
+        self.window.reset();
+        self.shift = f64::NAN;
+        self.sum.reset();
+        self.sum_sq.reset();
+        self.since_rebuild = 0;
+        self.guard.reset();
+    }
+
+    fn lookback(&self) -> usize {
+        self.guard.min_periods() - 1
+    }
+
+    fn count(&self) -> usize {
+        self.window.count()
+    }
+}
+
+impl NanHandling for Stder {
+    fn set_nan_policy(&mut self, policy: NanPolicy) {
+        self.guard.set_policy(policy);
+    }
+
+    // the sample std needs two values
+    fn set_min_periods(&mut self, min_periods: usize) {
+        self.guard.set_min_periods(min_periods.max(2));
+    }
+}
+
+// DecayMeaner is an EMA over irregular timestamps, an input weighs 0.5^(age / halflife)
+// with its age in the unit of the timestamps, like pandas ewm(halflife=..., times=...).mean().
+// NaN/inf inputs are left out, the weights decay with the elapsed time either way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecayMeaner {
+    #[serde(with = "crate::utils::float")]
+    halflife: f64,
+    #[serde(with = "crate::utils::float")]
+    weighted: f64,
+    #[serde(with = "crate::utils::float")]
+    old_wt: f64,
+    last_timestamp: i64,
+    count: usize,
+}
+
+impl DecayMeaner {
+    pub fn new(halflife: f64) -> Self {
+        Self {
+            halflife,
+            weighted: f64::NAN,
+            old_wt: 1.0,
+            last_timestamp: i64::MIN,
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, timestamp: i64, new_val: f64) -> f64 {
+        let timestamp = timestamp.max(self.last_timestamp);
+        let is_observation = !is_nan_or_inf(new_val);
+        self.count += 1;
+
+        if !self.weighted.is_nan() {
+            let elapsed = (timestamp - self.last_timestamp) as f64;
+            self.old_wt *= 0.5f64.powf(elapsed / self.halflife);
+            if is_observation {
+                // a constant series keeps its exact mean
+                if self.weighted != new_val {
+                    self.weighted = (self.old_wt * self.weighted + new_val) / (self.old_wt + 1.0);
+                }
+                self.old_wt += 1.0;
+            }
+        } else if is_observation {
+            self.weighted = new_val;
+        }
+        self.last_timestamp = timestamp;
+
+        self.value()
+    }
+
+    pub fn halflife(&self) -> f64 {
+        self.halflife
+    }
+}
+
+impl Indicator for DecayMeaner {
+    type Input = (i64, f64);
+    type Output = f64;
+
+    fn update(&mut self, (timestamp, new_val): (i64, f64)) -> f64 {
+        self.update(timestamp, new_val)
+    }
+
+    fn value(&self) -> f64 {
+        self.weighted
+    }
+
+    fn reset(&mut self) {
+        self.weighted = f64::NAN;
+        self.old_wt = 1.0;
+        self.last_timestamp = i64::MIN;
+        self.count = 0;
+    }
+
+    fn lookback(&self) -> usize {
+        0
+    }
+
+    fn count(&self) -> usize {
+        self.count
+    }
+}
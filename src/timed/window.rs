@@ -0,0 +1,103 @@
+use crate::utils::is_nan_or_inf;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// TimeWindow is the Container of the time-based types: the inputs of the last `duration`, oldest first.
+// Every input gets a sequence number, its position in the stream since the last reset,
+// so other structures can refer to inputs that are still in the window.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeWindow {
+    duration: i64,
+    times: VecDeque<i64>,
+    #[serde(with = "crate::utils::float")]
+    values: VecDeque<f64>,
+    nan_count: usize,
+    count: usize,
+}
+
+impl TimeWindow {
+    // duration is at least 1, the latest input is always in its window
+    pub fn new(duration: i64) -> Self {
+        Self {
+            duration: duration.max(1),
+            times: VecDeque::new(),
+            values: VecDeque::new(),
+            nan_count: 0,
+            count: 0,
+        }
+    }
+
+    pub fn push(&mut self, timestamp: i64, value: f64) {
+        let timestamp = self.times.back().map_or(timestamp, |&t| t.max(timestamp));
+        self.times.push_back(timestamp);
+        self.values.push_back(value);
+        if is_nan_or_inf(value) {
+            self.nan_count += 1;
+        }
+        self.count += 1;
+    }
+
+    // removes the oldest input once it has left the window, call it until None after a push
+    pub fn pop_expired(&mut self) -> Option<f64> {
+        let newest = *self.times.back()?;
+        if *self.times.front()? > newest.saturating_sub(self.duration) {
+            return None;
+        }
+        self.times.pop_front();
+        let value = self.values.pop_front()?;
+        if is_nan_or_inf(value) {
+            self.nan_count -= 1;
+        }
+        Some(value)
+    }
+
+    // value of the input with sequence number seq, NaN once it has left the window
+    pub fn get(&self, seq: usize) -> f64 {
+        seq.checked_sub(self.first_seq())
+            .and_then(|idx| self.values.get(idx))
+            .copied()
+            .unwrap_or(f64::NAN)
+    }
+
+    // sequence number of the oldest input in the window
+    pub fn first_seq(&self) -> usize {
+        self.count - self.values.len()
+    }
+
+    pub fn newest(&self) -> f64 {
+        self.values.back().copied().unwrap_or(f64::NAN)
+    }
+
+    // (valid, invalid) values in the window
+    pub fn window_counts(&self) -> (usize, usize) {
+        (self.values.len() - self.nan_count, self.nan_count)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &f64> {
+        self.values.iter()
+    }
+
+    pub fn duration(&self) -> i64 {
+        self.duration
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // number of inputs since construction or reset
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn reset(&mut self) {
+        self.times.clear();
+        self.values.clear();
+        self.nan_count = 0;
+        self.count = 0;
+    }
+}
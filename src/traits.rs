@@ -0,0 +1,78 @@
+use crate::NanPolicy;
+
+// Indicator is the common streaming interface of every rolling, cum and indicator type.
+// Input is a single f64 or a tuple for multi-input types, e.g. (high, low, close).
+// The inherent `update` methods keep their spread arguments, the trait `update` takes the tuple.
+pub trait Indicator {
+    type Input;
+    type Output: Clone;
+
+    // feed one input and return the new output
+    fn update(&mut self, input: Self::Input) -> Self::Output;
+
+    // feed a whole history in order and return every step output,
+    // e.g. warm up from history before feeding live ticks with update()
+    fn update_many<I>(&mut self, inputs: I) -> Vec<Self::Output>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Self::Input>,
+    {
+        inputs.into_iter().map(|input| self.update(input)).collect()
+    }
+
+    // what update(input) would return, the state is left untouched
+    // e.g. the RSI if the next price were X, for order placement or alert thresholds
+    fn peek(&self, input: Self::Input) -> Self::Output
+    where
+        Self: Sized + Clone,
+    {
+        self.clone().update(input)
+    }
+
+    // output computed from the current state, without consuming an input
+    fn value(&self) -> Self::Output;
+
+    // drop all state, as if newly constructed
+    fn reset(&mut self);
+
+    // number of inputs before the first valid output, like TA_*_Lookback.
+    // composite types add up the lookbacks of chained components, e.g. TRIMA is SMA of SMA,
+    // and take the max over parallel ones, e.g. the fast and slow lines of APO.
+    // EMA-based types are valid from the first input but have an unstable period,
+    // the seed still weighs (1 - alpha)^k after k more inputs, about 3.45 * (period + 1) inputs to fade below 0.1%.
+    fn lookback(&self) -> usize;
+
+    // number of inputs that advanced the state since construction or reset,
+    // cum types skip NaN inputs so they only count valid ones
+    fn count(&self) -> usize;
+
+    // whether the warmup is over and value() is meaningful
+    fn is_ready(&self) -> bool {
+        self.count() > self.lookback()
+    }
+}
+
+// NanHandling configures how rolling and cum statistics treat NaN/inf inputs.
+// min_periods is the number of valid values needed for an output, like pandas,
+// it defaults to the window size for rolling types and is clamped to 1..=window.
+pub trait NanHandling {
+    fn set_nan_policy(&mut self, policy: NanPolicy);
+
+    fn set_min_periods(&mut self, min_periods: usize);
+
+    fn with_nan_policy(mut self, policy: NanPolicy) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_nan_policy(policy);
+        self
+    }
+
+    fn with_min_periods(mut self, min_periods: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_min_periods(min_periods);
+        self
+    }
+}
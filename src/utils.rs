@@ -43,7 +43,7 @@ macro_rules! multiply {
 macro_rules! max {
     ($($x:expr),*) => {
         {
-            let mut max_val = std::f64::NEG_INFINITY;
+            let mut max_val = f64::NEG_INFINITY;
             $(
                 if $x as f64 > max_val {
                     max_val = $x as f64;
@@ -58,7 +58,7 @@ macro_rules! max {
 macro_rules! min {
     ($($x:expr),*) => {
         {
-            let mut min_val = std::f64::INFINITY;
+            let mut min_val = f64::INFINITY;
             $(
                 if $x as f64 < min_val {
                     min_val = $x as f64;
@@ -68,3 +68,166 @@ macro_rules! min {
         }
     };
 }
+
+// Neumaier compensated sum, keeps the low-order bits that plain `+=` drops,
+// so long add/subtract cycles of a rolling window do not drift
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub struct CompensatedSum {
+    #[serde(with = "crate::utils::float")]
+    sum: f64,
+    #[serde(with = "crate::utils::float")]
+    comp: f64,
+}
+
+impl CompensatedSum {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.comp += (self.sum - t) + x;
+        } else {
+            self.comp += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    pub fn sub(&mut self, x: f64) {
+        self.add(-x);
+    }
+
+    pub fn value(&self) -> f64 {
+        self.sum + self.comp
+    }
+
+    pub fn reset(&mut self) {
+        self.sum = 0.0;
+        self.comp = 0.0;
+    }
+}
+
+// serde helper for float fields, used as `#[serde(with = "crate::utils::float")]`
+// JSON has no NaN/inf, so human-readable formats write non-finite values as "NaN", "inf", "-inf";
+// binary formats keep the raw f64
+pub mod float {
+    use serde::de::{self, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::VecDeque;
+    use std::fmt;
+
+    struct Float(f64);
+
+    impl Serialize for Float {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() && !self.0.is_finite() {
+                serializer.serialize_str(&self.0.to_string())
+            } else {
+                serializer.serialize_f64(self.0)
+            }
+        }
+    }
+
+    struct FloatVisitor;
+
+    impl Visitor<'_> for FloatVisitor {
+        type Value = Float;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a number or one of \"NaN\", \"inf\", \"-inf\"")
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Float, E> {
+            Ok(Float(v))
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Float, E> {
+            Ok(Float(v as f64))
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Float, E> {
+            Ok(Float(v as f64))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Float, E> {
+            v.parse()
+                .map(Float)
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+        }
+
+        // plain serde_json writes NaN as null
+        fn visit_unit<E: de::Error>(self) -> Result<Float, E> {
+            Ok(Float(f64::NAN))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Float {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(FloatVisitor)
+            } else {
+                deserializer.deserialize_f64(FloatVisitor)
+            }
+        }
+    }
+
+    // Floats covers the float field shapes of the indicator structs
+    pub trait Floats: Sized {
+        fn ser<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+        fn de<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+    }
+
+    impl Floats for f64 {
+        fn ser<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            Float(*self).serialize(serializer)
+        }
+
+        fn de<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Float::deserialize(deserializer).map(|v| v.0)
+        }
+    }
+
+    impl Floats for Option<f64> {
+        fn ser<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.map(Float).serialize(serializer)
+        }
+
+        fn de<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Option::<Float>::deserialize(deserializer).map(|v| v.map(|v| v.0))
+        }
+    }
+
+    impl Floats for Vec<f64> {
+        fn ser<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(|&v| Float(v)))
+        }
+
+        fn de<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Vec::<Float>::deserialize(deserializer).map(|v| v.into_iter().map(|v| v.0).collect())
+        }
+    }
+
+    impl Floats for VecDeque<f64> {
+        fn ser<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(|&v| Float(v)))
+        }
+
+        fn de<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Vec::<Float>::deserialize(deserializer).map(|v| v.into_iter().map(|v| v.0).collect())
+        }
+    }
+
+    pub fn serialize<T: Floats, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.ser(serializer)
+    }
+
+    pub fn deserialize<'de, T: Floats, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::de(deserializer)
+    }
+}